@@ -23,6 +23,7 @@ fn divide(a: i32, b: i32) -> Result<i32, String> {
 }
 
 /// Logs errors at the `error` level. No log output for `Ok` variant.
+#[allow(clippy::manual_checked_ops)]
 #[logcall(err = "error")]
 fn divide2(a: usize, b: usize) -> Result<usize, String> {
     if b == 0 {
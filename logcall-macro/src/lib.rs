@@ -0,0 +1,5123 @@
+//! Procedural macros for the [`logcall`](https://docs.rs/logcall) crate.
+//!
+//! This crate is an implementation detail of `logcall` and should not be used directly.
+
+// Instrumenting the async fn is not as straight forward as expected because `async_trait` rewrites `async fn`
+// into a normal fn which returns `Box<impl Future>`, and this stops the macro from distinguishing `async fn` from `fn`.
+// The following code reused the `async_trait` probes from [tokio-tracing](https://github.com/tokio-rs/tracing/blob/6a61897a5e834988ad9ac709e28c93c4dbf29116/tracing-attributes/src/expand.rs).
+
+extern crate proc_macro;
+
+#[macro_use]
+extern crate proc_macro_error;
+
+use std::time::Duration;
+
+use proc_macro2::Span;
+use syn::spanned::Spanned;
+use syn::visit_mut::VisitMut;
+use syn::Ident;
+use syn::*;
+
+// Binds each name to the identifier of the same name in the expansion, e.g. `__ret_value`, so
+// that it can be written as `#__ret_value` in the quoted code.
+macro_rules! bindings {
+    ($($name:ident),*) => {
+        $(let $name = binding(stringify!($name));)*
+    };
+}
+
+#[derive(Clone)]
+struct Args {
+    // The arguments as written in the attribute, for the `schema` record.
+    options: String,
+    levels: Levels,
+    span: Option<String>,
+    low_overhead: bool,
+    // Whether the records show the numeric ID of the function from the registry instead of its path.
+    fn_id: bool,
+    test_level: Option<String>,
+    input_format: Option<LitStr>,
+    target: Option<LitStr>,
+    ok_target: Option<LitStr>,
+    err_target: Option<LitStr>,
+    // The predicate on the `Ok` value, bound to `ret`, deciding whether it is logged.
+    ok_if: Option<Expr>,
+    accumulate: Option<Accumulate>,
+    sink: Option<Sink>,
+    heapless: Option<usize>,
+    budget: Option<Budget>,
+    defer: bool,
+    dedup: Option<Duration>,
+    auto_mute: Option<AutoMute>,
+    slow: Option<Slow>,
+    elapsed_levels: Option<ElapsedLevels>,
+    audit: bool,
+    tokenize: Vec<Ident>,
+    head: Option<Head>,
+    // The number of elements above which the array arguments are summarized.
+    array_summary: Option<usize>,
+    // The levels whose records render the arguments summarized, or in full.
+    detail: Vec<(String, bool)>,
+    // The fields of the return value masked in the records.
+    ret_redact: Vec<Ident>,
+    heartbeat: Option<Duration>,
+    progress_every: Option<Duration>,
+    // The interval of the summaries logged instead of the records when `summarize` is specified.
+    summarize: Option<Duration>,
+    // Whether only the name and the duration of the call are logged, with `only = "timing"` or
+    // `#[logcall::timeit]`.
+    timing: bool,
+    in_flight_warn: Option<Duration>,
+    // The level of the record of the async calls dropped before completion.
+    cancelled: Option<String>,
+    exec_context: bool,
+    max_depth: Option<usize>,
+    record_span: bool,
+    metrics: bool,
+    // The function mapping the error of the `Err` calls to the `error` label of `fn_errors`, when
+    // specified with `metrics(error_label = "..")`.
+    metrics_error_label: Option<Expr>,
+    journald: bool,
+    undocumented: bool,
+    try_iter: bool,
+    outcome_only: bool,
+    err_fingerprint: bool,
+    // Only set for `unsafe fn`s, see `expand_fn`.
+    unsafe_tag: bool,
+    // Set by `expand_fn` for `#[track_caller]` functions.
+    track_caller: bool,
+    // With `location`, the span of the function name, see `expand_fn`.
+    location: Option<Span>,
+    // Set by `expand_fn` with the `valuable` feature: the arguments recorded as fields of the
+    // `tracing` events, and whether they are kept as shared references, rather than rendered
+    // before the call, as the body may move them.
+    valuable_args: Vec<(Ident, bool)>,
+    timestamp: bool,
+    elapsed: bool,
+    cpu_time: bool,
+    allocations: bool,
+    poll_stats: bool,
+    first_poll_delay: bool,
+    catch_panic: bool,
+    // `format = "json"`, with the `serde` feature.
+    json: bool,
+    schema: bool,
+    lint_attrs: bool,
+    after_error: Option<AfterError>,
+    sample: Option<Sample>,
+    diff_by: Option<Ident>,
+    groups: Vec<Group>,
+    filter: Filter,
+    backend: Backend,
+}
+
+#[derive(Clone)]
+enum Levels {
+    Simple(String),
+    Result {
+        ok_level: Option<String>,
+        err_level: Option<String>,
+    },
+    Either {
+        left_level: Option<String>,
+        right_level: Option<String>,
+    },
+}
+
+// The boosted verbosity that follows an `Err` when `after_error` is specified.
+#[derive(Clone)]
+struct AfterError {
+    level: String,
+    count: usize,
+}
+
+// Arguments rendered under a single named field by the structured formats, i.e. `kv` and
+// `format = "json"`, when `group(a, b, as = "name")` is specified.
+#[derive(Clone)]
+struct Group {
+    members: Vec<Ident>,
+    name: LitStr,
+}
+
+// The size of the inputs and the return value above which the record is summarized, and the
+// parameters and return type of the function, which the summary renders instead of the values.
+#[derive(Clone)]
+struct Budget {
+    size: usize,
+    params: String,
+    ret: String,
+}
+
+// The number of items of the collections rendered in the arguments when `head` is specified,
+// for all of them unless some are named.
+#[derive(Clone)]
+struct Head {
+    args: Vec<Ident>,
+    items: usize,
+}
+
+// The rate of records above which the function is muted for `cooldown` when `auto_mute` is
+// specified, i.e. more than `threshold` records within `window`. The original strings are kept
+// for the notice.
+#[derive(Clone)]
+struct AutoMute {
+    threshold: u64,
+    window: Duration,
+    cooldown: Duration,
+    rate: String,
+    cooldown_str: String,
+}
+
+// The level the records of the calls lasting at least `threshold` are escalated to when `slow`
+// is specified. Without a level of their own, only the slow calls are logged.
+#[derive(Clone)]
+struct Slow {
+    threshold: Duration,
+    level: String,
+    only: bool,
+}
+
+// The levels the records of the calls lasting at least each threshold are escalated to when
+// `elapsed_levels` is specified, by increasing threshold. Without a level of their own, only
+// the calls lasting at least the first threshold are logged.
+#[derive(Clone)]
+struct ElapsedLevels {
+    rungs: Vec<(Duration, String)>,
+    only: bool,
+}
+
+// The consistent sampling of records when `sample_by` is specified.
+#[derive(Clone)]
+struct Sample {
+    key: Ident,
+    rate: f64,
+}
+
+// The methods or functions instrumented by the blanket modes, i.e. `#[logcall]` on an `impl`
+// block or a module, and `#[wrap_dyn]`.
+#[derive(Clone, Default)]
+struct Filter {
+    only_pub: bool,
+    only_async: bool,
+    pattern: Option<LitStr>,
+}
+
+impl Filter {
+    fn is_set(&self) -> bool {
+        self.only_pub || self.only_async || self.pattern.is_some()
+    }
+
+    fn matches(&self, is_pub: bool, sig: &Signature) -> bool {
+        (!self.only_pub || is_pub)
+            && (!self.only_async || sig.asyncness.is_some())
+            && self
+                .pattern
+                .as_ref()
+                .is_none_or(|pattern| glob_match(&pattern.value(), &sig.ident.to_string()))
+    }
+}
+
+// The facade the records are emitted through, selected by `backend`, `slog`, `emit` or `logger`.
+#[derive(Clone)]
+enum Backend {
+    Log,
+    // `tracing::event!`, with the `tracing` feature.
+    Tracing,
+    // `slog::log!` against the logger expression, with the `slog` feature.
+    Slog(Box<Expr>),
+    // `defmt::info!` and co., the default with the `defmt` feature on `no_std` targets.
+    Defmt,
+    // `eprintln!` with a level prefix, for programs that don't install a logger.
+    Stderr,
+    // `println!` with a level prefix.
+    Stdout,
+    // `console.info` and co., with the `wasm-console` feature.
+    Console,
+    // Android logcat or Apple `os_log`, tagged with the target, with the `mobile` feature.
+    Mobile,
+    // A user macro wrapping `log`, invoked as `path!(level, "{}", message)`.
+    Macro(Box<Path>),
+}
+
+// The user sink the structured records are handed to when `sink` is specified, and whether
+// they are also logged, as with `accumulate`.
+#[derive(Clone)]
+struct Sink {
+    sink: Expr,
+    mode: Accumulate,
+}
+
+// Where the records go when `accumulate` or `sink` is specified.
+#[derive(Clone, Copy)]
+enum Accumulate {
+    // Append to the accumulator, and only emit the log if there is no accumulator.
+    Only,
+    // Append to the accumulator and emit the log.
+    Both,
+}
+
+impl Levels {
+    // The specified levels, validated.
+    fn checked(&self) -> Vec<String> {
+        let levels = match self {
+            Levels::Simple(level) => vec![level],
+            Levels::Result {
+                ok_level,
+                err_level,
+            } => ok_level.iter().chain(err_level).collect(),
+            Levels::Either {
+                left_level,
+                right_level,
+            } => left_level.iter().chain(right_level).collect(),
+        };
+        levels.into_iter().map(|level| check_level(level)).collect()
+    }
+}
+
+impl Args {
+    // Whether the records are emitted with structured key-values, with the `kv` feature. The
+    // records rendered ahead of time by `dedup`, `defer` and `audit`, and those of `journald`,
+    // which have fields of their own, keep the flat message.
+    fn kv(&self) -> bool {
+        cfg!(feature = "kv")
+            && !self.timing
+            && matches!(self.backend, Backend::Log)
+            && !self.defer
+            && !self.journald
+            && !self.audit
+            && !self.json
+            && self.dedup.is_none()
+            && self.detail.is_empty()
+    }
+
+    // Whether the arguments and the return value are recorded as fields of the `tracing`
+    // events, through `valuable` when possible.
+    fn valuable(&self) -> bool {
+        cfg!(feature = "valuable") && matches!(self.backend, Backend::Tracing) && !self.timing
+    }
+
+    // Whether the arguments are passed to the console as separate values, rather than the
+    // rendered message.
+    fn console(&self) -> bool {
+        matches!(self.backend, Backend::Console)
+            && !self.audit
+            && !self.json
+            && self.budget.is_none()
+            && self.dedup.is_none()
+    }
+
+    // The first specified argument, other than the levels, that the `defmt` backend doesn't
+    // support.
+    fn unsupported_with_defmt(&self) -> Option<&'static str> {
+        [
+            (self.span.is_some(), "span"),
+            (self.low_overhead, "low_overhead"),
+            (self.fn_id, "fn_id"),
+            (self.test_level.is_some(), "test_level"),
+            (self.input_format.is_some(), "input"),
+            (self.target.is_some(), "target"),
+            (self.ok_target.is_some(), "ok_target"),
+            (self.err_target.is_some(), "err_target"),
+            (self.ok_if.is_some(), "ok_if"),
+            (self.accumulate.is_some(), "accumulate"),
+            (self.sink.is_some(), "sink"),
+            (self.heapless.is_some(), "heapless"),
+            (self.budget.is_some(), "budget"),
+            (self.defer, "defer"),
+            (self.dedup.is_some(), "dedup"),
+            (self.audit, "audit"),
+            (!self.tokenize.is_empty(), "tokenize"),
+            (self.head.is_some(), "head"),
+            (self.array_summary.is_some(), "array_summary"),
+            (!self.detail.is_empty(), "detail"),
+            (!self.ret_redact.is_empty(), "ret_redact"),
+            (self.heartbeat.is_some(), "heartbeat"),
+            (self.progress_every.is_some(), "progress_every"),
+            (self.summarize.is_some(), "summarize"),
+            (self.in_flight_warn.is_some(), "in_flight_warn"),
+            (self.cancelled.is_some(), "cancelled"),
+            (self.exec_context, "exec_context"),
+            (self.max_depth.is_some(), "max_depth"),
+            (self.record_span, "record_span"),
+            (self.metrics, "metrics"),
+            (self.journald, "journald"),
+            (self.try_iter, "try_iter"),
+            (self.outcome_only, "outcome_only"),
+            (self.err_fingerprint, "err_fingerprint"),
+            (self.unsafe_tag, "unsafe_tag"),
+            (self.location.is_some(), "location"),
+            (self.timestamp, "timestamp"),
+            (self.elapsed, "elapsed"),
+            (self.cpu_time, "cpu_time"),
+            (self.allocations, "allocations"),
+            (self.poll_stats, "poll_stats"),
+            (self.first_poll_delay, "first_poll_delay"),
+            (self.catch_panic, "catch_panic"),
+            (self.json, "format"),
+            (self.schema, "schema"),
+            (self.lint_attrs, "lint_attrs"),
+            (self.after_error.is_some(), "after_error"),
+            (self.slow.is_some(), "slow"),
+            (self.elapsed_levels.is_some(), "elapsed_levels"),
+            (self.auto_mute.is_some(), "auto_mute"),
+            (self.sample.is_some(), "sample"),
+            (self.diff_by.is_some(), "diff_by"),
+        ]
+        .into_iter()
+        .find_map(|(used, name)| used.then_some(name))
+    }
+
+    fn parse(input: AttributeArgs) -> Args {
+        let options = input
+            .iter()
+            .map(|arg| quote::quote!(#arg).to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut simple_level = None;
+        let mut ok_level = None;
+        let mut err_level = None;
+        let mut left_level = None;
+        let mut right_level = None;
+        let mut input_format = None;
+        let mut target = None;
+        let mut ok_target = None;
+        let mut test_level = None;
+        let mut span = None;
+        let mut err_target = None;
+        let mut ok_if = None;
+        let mut accumulate = None;
+        let mut sink = None;
+        let mut sink_mode = None;
+        let mut heapless = None;
+        let mut budget = None;
+        let mut defer = false;
+        let mut dedup = None;
+        let mut auto_mute = None;
+        let mut slow = None;
+        let mut slow_level = None;
+        let mut elapsed_levels = None;
+        let mut audit = false;
+        let mut tokenize = Vec::new();
+        let mut head = None;
+        let mut array_summary = None;
+        let mut detail = Vec::new();
+        let mut ret_redact = Vec::new();
+        let mut heartbeat = None;
+        let mut progress_every = None;
+        let mut summarize = None;
+        let mut timing = false;
+        let mut in_flight_warn = None;
+        let mut cancelled = None;
+        let mut exec_context = false;
+        let mut max_depth = None;
+        let mut record_span = false;
+        let mut metrics = false;
+        let mut metrics_error_label = None;
+        let mut journald = false;
+        let mut undocumented = false;
+        let mut try_iter = false;
+        let mut low_overhead = false;
+        let mut fn_id = false;
+        let mut outcome_only = false;
+        let mut err_fingerprint = false;
+        let mut unsafe_tag = false;
+        let mut location = None;
+        let mut timestamp = false;
+        let mut elapsed = false;
+        let mut cpu_time = false;
+        let mut allocations = false;
+        let mut poll_stats = false;
+        let mut first_poll_delay = false;
+        let mut catch_panic = false;
+        let mut json = false;
+        let mut schema = false;
+        let mut lint_attrs = false;
+        let mut after_error = None;
+        let mut sample_by = None;
+        let mut sample_rate = None;
+        let mut diff_by = None;
+        let mut groups = Vec::new();
+        let mut filter = Filter::default();
+        let mut backend = if cfg!(feature = "defmt") && !cfg!(feature = "std") {
+            Backend::Defmt
+        } else {
+            Backend::Log
+        };
+
+        for arg in input {
+            match arg {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(lit_str),
+                    ..
+                })) => {
+                    let ident = path.get_ident().unwrap().to_string();
+                    match ident.as_str() {
+                        "ok" => {
+                            ok_level = Some(lit_str.value());
+                        }
+                        "err" => {
+                            err_level = Some(lit_str.value());
+                        }
+                        "left" => {
+                            left_level = Some(lit_str.value());
+                        }
+                        "right" => {
+                            right_level = Some(lit_str.value());
+                        }
+                        "input" => {
+                            input_format = Some(lit_str);
+                        }
+                        "test_level" => {
+                            test_level = Some(check_level(&lit_str.value()));
+                        }
+                        "span" => {
+                            span = Some(check_level(&lit_str.value()));
+                        }
+                        "target" => {
+                            target = Some(parse_target(lit_str));
+                        }
+                        "ok_target" => {
+                            ok_target = Some(parse_target(lit_str));
+                        }
+                        "err_target" => {
+                            err_target = Some(parse_target(lit_str));
+                        }
+                        "ok_if" => match lit_str.parse::<Expr>() {
+                            Ok(expr) => ok_if = Some(expr),
+                            Err(_) => abort!(lit_str.span(), "expected an expression"),
+                        },
+                        "sample_by" => match lit_str.parse::<Ident>() {
+                            Ok(ident) => sample_by = Some(ident),
+                            Err(_) => abort!(lit_str.span(), "expected an argument name"),
+                        },
+                        "diff_by" => match lit_str.parse::<Ident>() {
+                            Ok(ident) => diff_by = Some(ident),
+                            Err(_) => abort!(lit_str.span(), "expected an argument name"),
+                        },
+                        "accumulate" => match lit_str.value().as_str() {
+                            "only" => accumulate = Some(Accumulate::Only),
+                            "both" => accumulate = Some(Accumulate::Both),
+                            _ => abort!(lit_str.span(), "expected `only` or `both`"),
+                        },
+                        "sink" => match lit_str.parse::<Expr>() {
+                            Ok(expr) => sink = Some(expr),
+                            Err(_) => abort!(lit_str.span(), "expected a sink expression"),
+                        },
+                        "sink_mode" => match lit_str.value().as_str() {
+                            "only" => sink_mode = Some(Accumulate::Only),
+                            "both" => sink_mode = Some(Accumulate::Both),
+                            _ => abort!(lit_str.span(), "expected `only` or `both`"),
+                        },
+                        "dedup" => {
+                            dedup = Some(parse_duration(&lit_str));
+                        }
+                        "slow" => {
+                            slow = Some(parse_duration(&lit_str));
+                        }
+                        "slow_level" => {
+                            slow_level = Some(check_level(&lit_str.value()));
+                        }
+                        "heartbeat" => {
+                            heartbeat = Some(parse_duration(&lit_str));
+                        }
+                        "progress_every" => {
+                            progress_every = Some(parse_duration(&lit_str));
+                        }
+                        "summarize" => {
+                            summarize = Some(parse_duration(&lit_str));
+                        }
+                        "only" => match lit_str.value().as_str() {
+                            "timing" => timing = true,
+                            _ => abort!(lit_str.span(), "expected `only = \"timing\"`"),
+                        },
+                        "in_flight_warn" => {
+                            in_flight_warn = Some(parse_duration(&lit_str));
+                        }
+                        "cancelled" => {
+                            cancelled = Some(check_level(&lit_str.value()));
+                        }
+                        "budget" => {
+                            budget = Some(Budget {
+                                size: parse_size(&lit_str),
+                                params: String::new(),
+                                ret: String::new(),
+                            });
+                        }
+                        "backend" => match lit_str.value().as_str() {
+                            "log" => backend = Backend::Log,
+                            "tracing" => backend = Backend::Tracing,
+                            "defmt" => backend = Backend::Defmt,
+                            _ => abort!(lit_str.span(), "expected `log`, `tracing` or `defmt`"),
+                        },
+                        "format" => match lit_str.value().as_str() {
+                            "text" => json = false,
+                            "json" => json = true,
+                            _ => abort!(lit_str.span(), "expected `text` or `json`"),
+                        },
+                        "emit" => match lit_str.value().as_str() {
+                            "stderr" => backend = Backend::Stderr,
+                            "stdout" => backend = Backend::Stdout,
+                            "console" => backend = Backend::Console,
+                            "mobile" => backend = Backend::Mobile,
+                            _ => abort!(
+                                lit_str.span(),
+                                "expected `stderr`, `stdout`, `console` or `mobile`"
+                            ),
+                        },
+                        "logger" => match lit_str.parse::<Path>() {
+                            Ok(path) => backend = Backend::Macro(Box::new(path)),
+                            Err(_) => abort!(lit_str.span(), "expected a macro path"),
+                        },
+                        "slog" => match lit_str.parse::<Expr>() {
+                            Ok(logger) => backend = Backend::Slog(Box::new(logger)),
+                            Err(_) => abort!(lit_str.span(), "expected a logger expression"),
+                        },
+                        "level" => {
+                            if simple_level.is_some() {
+                                abort!(lit_str.span(), "level has already been specified");
+                            }
+                            simple_level = Some(lit_str.value());
+                        }
+                        "match" => {
+                            filter.pattern = Some(parse_pattern(lit_str));
+                        }
+                        _ => {
+                            abort!(ident.span(), "unexpected argument");
+                        }
+                    }
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Int(lit_int),
+                    ..
+                })) => {
+                    let ident = path.get_ident().unwrap().to_string();
+                    match ident.as_str() {
+                        "heapless" => {
+                            heapless = Some(parse_buffer_size(&lit_int));
+                        }
+                        "max_depth" => {
+                            max_depth = Some(parse_max_depth(&lit_int));
+                        }
+                        "array_summary" => {
+                            array_summary = Some(parse_array_summary(&lit_int));
+                        }
+                        _ => {
+                            abort!(path.span(), "unexpected argument");
+                        }
+                    }
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Float(lit_float),
+                    ..
+                })) if path.is_ident("sample") => {
+                    sample_rate = Some(parse_sample_rate(&lit_float));
+                }
+                NestedMeta::Meta(Meta::Path(path)) => {
+                    let ident = path.get_ident().unwrap().to_string();
+                    match ident.as_str() {
+                        "accumulate" => {
+                            accumulate = Some(Accumulate::Only);
+                        }
+                        "heapless" => {
+                            heapless = Some(DEFAULT_BUFFER_SIZE);
+                        }
+                        "defer" => {
+                            defer = true;
+                        }
+                        "audit" => {
+                            audit = true;
+                        }
+                        "exec_context" => {
+                            exec_context = true;
+                        }
+                        "record_span" => {
+                            record_span = true;
+                        }
+                        "metrics" => {
+                            metrics = true;
+                        }
+                        "journald" => {
+                            journald = true;
+                        }
+                        "undocumented" => {
+                            undocumented = true;
+                        }
+                        "try_iter" => {
+                            try_iter = true;
+                        }
+                        "low_overhead" => {
+                            low_overhead = true;
+                        }
+                        "fn_id" => {
+                            fn_id = true;
+                        }
+                        "outcome_only" => {
+                            outcome_only = true;
+                        }
+                        "err_fingerprint" => {
+                            err_fingerprint = true;
+                        }
+                        "unsafe_tag" => {
+                            unsafe_tag = true;
+                        }
+                        "location" => {
+                            location = Some(Span::call_site());
+                        }
+                        "timestamp" => {
+                            timestamp = true;
+                        }
+                        "elapsed" => {
+                            elapsed = true;
+                        }
+                        "cpu_time" => {
+                            cpu_time = true;
+                        }
+                        "allocations" => {
+                            allocations = true;
+                        }
+                        "poll_stats" => {
+                            poll_stats = true;
+                        }
+                        "first_poll_delay" => {
+                            first_poll_delay = true;
+                        }
+                        "catch_panic" => {
+                            catch_panic = true;
+                        }
+                        "schema" => {
+                            schema = true;
+                        }
+                        "lint_attrs" => {
+                            lint_attrs = true;
+                        }
+                        "after_error" => {
+                            after_error = Some(parse_after_error(Vec::new()));
+                        }
+                        "only_pub" => {
+                            filter.only_pub = true;
+                        }
+                        "only_async" => {
+                            filter.only_async = true;
+                        }
+                        _ => {
+                            abort!(path.span(), "unexpected argument");
+                        }
+                    }
+                }
+                NestedMeta::Meta(Meta::List(MetaList { path, nested, .. })) => {
+                    let ident = path.get_ident().unwrap().to_string();
+                    match ident.as_str() {
+                        "tokenize" => {
+                            tokenize.extend(parse_idents(nested));
+                        }
+                        "head" => {
+                            head = Some(parse_head(path.span(), nested));
+                        }
+                        "detail" => {
+                            detail.extend(parse_detail(nested));
+                        }
+                        "ret_redact" => {
+                            ret_redact.extend(parse_idents(nested));
+                        }
+                        "metrics" => {
+                            metrics = true;
+                            metrics_error_label = parse_metrics(nested);
+                        }
+                        "after_error" => {
+                            after_error = Some(parse_after_error(nested));
+                        }
+                        "auto_mute" => {
+                            auto_mute = Some(parse_auto_mute(path.span(), nested));
+                        }
+                        "elapsed_levels" => {
+                            elapsed_levels = Some(parse_elapsed_levels(path.span(), nested));
+                        }
+                        "group" => {
+                            groups.push(parse_group(path.span(), nested));
+                        }
+                        _ => {
+                            abort!(path.span(), "unexpected argument");
+                        }
+                    }
+                }
+                NestedMeta::Lit(Lit::Str(lit_str)) => {
+                    if simple_level.is_some() {
+                        abort!(lit_str.span(), "level has already been specified");
+                    }
+                    simple_level = Some(lit_str.value());
+                }
+                _ => {
+                    abort!(arg.span(), "unexpected argument");
+                }
+            }
+        }
+
+        let sample = match (sample_by, sample_rate) {
+            (Some(key), Some(rate)) => Some(Sample { key, rate }),
+            (None, None) => None,
+            (Some(_), None) => abort_call_site!("`sample_by` requires a `sample` rate"),
+            (None, Some(_)) => abort_call_site!("`sample` requires a `sample_by` argument"),
+        };
+
+        let mut slow = match (slow, slow_level) {
+            (Some(threshold), level) => Some(Slow {
+                threshold,
+                level: level.unwrap_or_else(|| "warn".to_string()),
+                only: false,
+            }),
+            (None, None) => None,
+            (None, Some(_)) => abort_call_site!("`slow_level` requires a `slow` threshold"),
+        };
+        if slow.is_some() && elapsed_levels.is_some() {
+            abort_call_site!("`slow` cannot be used with `elapsed_levels`");
+        }
+
+        let sink = match (sink, sink_mode) {
+            (Some(sink), mode) => Some(Sink {
+                sink,
+                mode: mode.unwrap_or(Accumulate::Only),
+            }),
+            (None, None) => None,
+            (None, Some(_)) => abort_call_site!("`sink_mode` requires a `sink`"),
+        };
+        if sink.is_some() && try_iter {
+            abort_call_site!("`sink` cannot be used with `try_iter`");
+        }
+
+        let backend_name = match backend {
+            Backend::Log => None,
+            Backend::Tracing => Some("`backend = \"tracing\"`"),
+            Backend::Slog(_) => Some("`slog`"),
+            Backend::Stderr => Some("`emit = \"stderr\"`"),
+            Backend::Stdout => Some("`emit = \"stdout\"`"),
+            Backend::Console => Some("`emit = \"console\"`"),
+            Backend::Mobile => Some("`emit = \"mobile\"`"),
+            Backend::Macro(_) => Some("`logger`"),
+            // Checked by `gen_defmt_block`, which supports the levels only.
+            Backend::Defmt => None,
+        };
+        if let Some(backend_name) = backend_name {
+            for (used, name) in [
+                (defer, "defer"),
+                (journald, "journald"),
+                (try_iter, "try_iter"),
+                (heartbeat.is_some(), "heartbeat"),
+                (progress_every.is_some(), "progress_every"),
+                (summarize.is_some(), "summarize"),
+                (in_flight_warn.is_some(), "in_flight_warn"),
+                (cancelled.is_some(), "cancelled"),
+                (max_depth.is_some(), "max_depth"),
+            ] {
+                if used {
+                    abort_call_site!("`{}` cannot be used with {}", name, backend_name);
+                }
+            }
+        }
+        if let Some(backend_name) = backend_name {
+            if !matches!(backend, Backend::Tracing | Backend::Mobile)
+                && (target.is_some() || ok_target.is_some() || err_target.is_some())
+            {
+                abort_call_site!(
+                    "`target`, `ok_target` or `err_target` cannot be used with {}",
+                    backend_name
+                );
+            }
+        }
+
+        // Neither the arguments nor the return value are rendered, only the duration.
+        elapsed |= timing;
+        if timing {
+            for (used, name) in [
+                (input_format.is_some(), "input"),
+                (json, "format = \"json\""),
+                (budget.is_some(), "budget"),
+                (head.is_some(), "head"),
+                (heapless.is_some(), "heapless"),
+                (!detail.is_empty(), "detail"),
+                (diff_by.is_some(), "diff_by"),
+                (!ret_redact.is_empty(), "ret_redact"),
+                (outcome_only, "outcome_only"),
+                (err_fingerprint, "err_fingerprint"),
+                (journald, "journald"),
+                (accumulate.is_some(), "accumulate"),
+                (sink.is_some(), "sink"),
+                (record_span, "record_span"),
+                (try_iter, "try_iter"),
+                (progress_every.is_some(), "progress_every"),
+                (in_flight_warn.is_some(), "in_flight_warn"),
+                (cancelled.is_some(), "cancelled"),
+            ] {
+                if used {
+                    abort_call_site!("`{}` cannot be used with `only = \"timing\"`", name);
+                }
+            }
+        }
+
+        // The calls are not rendered, nor logged, one by one.
+        if summarize.is_some() {
+            for (used, name) in [
+                (defer, "defer"),
+                (dedup.is_some(), "dedup"),
+                (journald, "journald"),
+                (json, "format = \"json\""),
+                (diff_by.is_some(), "diff_by"),
+                (accumulate.is_some(), "accumulate"),
+                (sink.is_some(), "sink"),
+                (auto_mute.is_some(), "auto_mute"),
+                (try_iter, "try_iter"),
+                (in_flight_warn.is_some(), "in_flight_warn"),
+                (cancelled.is_some(), "cancelled"),
+            ] {
+                if used {
+                    abort_call_site!("`{}` cannot be used with `summarize`", name);
+                }
+            }
+        }
+
+        if outcome_only && ok_level.is_none() && err_level.is_none() {
+            abort_call_site!("`outcome_only` can only be used with `ok` or `err` levels");
+        }
+        if err_fingerprint && ok_level.is_none() && err_level.is_none() {
+            abort_call_site!("`err_fingerprint` can only be used with `ok` or `err` levels");
+        }
+        if err_fingerprint && try_iter {
+            abort_call_site!("`err_fingerprint` cannot be used with `try_iter`");
+        }
+        if json {
+            for (used, name) in [
+                (input_format.is_some(), "input"),
+                (heapless.is_some(), "heapless"),
+                (budget.is_some(), "budget"),
+                (head.is_some(), "head"),
+                (!detail.is_empty(), "detail"),
+                (diff_by.is_some(), "diff_by"),
+                (outcome_only, "outcome_only"),
+                (try_iter, "try_iter"),
+                (catch_panic, "catch_panic"),
+            ] {
+                if used {
+                    abort_call_site!("`{}` cannot be used with `format = \"json\"`", name);
+                }
+            }
+        }
+
+        // Every record would be unique.
+        if timestamp && dedup.is_some() {
+            abort_call_site!("`timestamp` cannot be used with `dedup`");
+        }
+        if elapsed && dedup.is_some() {
+            abort_call_site!("`elapsed` cannot be used with `dedup`");
+        }
+        if cpu_time && dedup.is_some() {
+            abort_call_site!("`cpu_time` cannot be used with `dedup`");
+        }
+        if allocations && dedup.is_some() {
+            abort_call_site!("`allocations` cannot be used with `dedup`");
+        }
+        if poll_stats && dedup.is_some() {
+            abort_call_site!("`poll_stats` cannot be used with `dedup`");
+        }
+        if first_poll_delay && dedup.is_some() {
+            abort_call_site!("`first_poll_delay` cannot be used with `dedup`");
+        }
+        if ok_if.is_some() && ok_level.is_none() {
+            abort_call_site!("`ok_if` can only be used with an `ok` level");
+        }
+        if ok_if.is_some() && (try_iter || after_error.is_some()) {
+            abort_call_site!("`ok_if` cannot be used with `try_iter` or `after_error`");
+        }
+        if outcome_only && try_iter {
+            abort_call_site!("`outcome_only` cannot be used with `try_iter`");
+        }
+
+        if !detail.is_empty() && (input_format.is_some() || diff_by.is_some()) {
+            abort_call_site!("`detail` cannot be used with `input` or `diff_by`");
+        }
+        if diff_by.is_some() && input_format.is_some() {
+            abort_call_site!("`diff_by` cannot be used with `input`");
+        }
+        if diff_by.is_some() && heapless.is_some() {
+            abort_call_site!("`diff_by` cannot be used with `heapless`");
+        }
+        if budget.is_some() && heapless.is_some() {
+            abort_call_site!("`budget` cannot be used with `heapless`");
+        }
+        if catch_panic && heapless.is_some() {
+            abort_call_site!("`catch_panic` cannot be used with `heapless`");
+        }
+
+        if (ok_target.is_some() || err_target.is_some())
+            && ok_level.is_none()
+            && err_level.is_none()
+        {
+            abort_call_site!(
+                "`ok_target` or `err_target` can only be used with `ok` or `err` levels"
+            );
+        }
+
+        let levels = if ok_level.is_some() || err_level.is_some() {
+            if simple_level.is_some() {
+                abort_call_site!("plain level cannot be specified with `ok` or `err` levels");
+            }
+            if left_level.is_some() || right_level.is_some() {
+                abort_call_site!(
+                    "`left` or `right` levels cannot be specified with `ok` or `err` levels"
+                );
+            }
+
+            Levels::Result {
+                ok_level,
+                err_level,
+            }
+        } else if left_level.is_some() || right_level.is_some() {
+            if simple_level.is_some() {
+                abort_call_site!("plain level cannot be specified with `left` or `right` levels");
+            }
+
+            Levels::Either {
+                left_level,
+                right_level,
+            }
+        } else {
+            match (simple_level, &mut slow, &mut elapsed_levels) {
+                (Some(level), _, _) => Levels::Simple(level),
+                (None, Some(slow), _) => {
+                    slow.only = true;
+                    Levels::Simple(slow.level.clone())
+                }
+                (None, None, Some(elapsed_levels)) => {
+                    elapsed_levels.only = true;
+                    Levels::Simple(elapsed_levels.rungs[0].1.clone())
+                }
+                (None, None, None) => Levels::Simple("debug".to_string()),
+            }
+        };
+
+        Args {
+            options,
+            levels,
+            span,
+            low_overhead,
+            fn_id,
+            test_level,
+            input_format,
+            target,
+            ok_target,
+            err_target,
+            ok_if,
+            accumulate,
+            sink,
+            heapless,
+            budget,
+            defer,
+            dedup,
+            auto_mute,
+            slow,
+            elapsed_levels,
+            audit,
+            tokenize,
+            head,
+            array_summary,
+            detail,
+            ret_redact,
+            heartbeat,
+            progress_every,
+            summarize,
+            timing,
+            in_flight_warn,
+            cancelled,
+            exec_context,
+            max_depth,
+            record_span,
+            metrics,
+            metrics_error_label,
+            journald,
+            undocumented,
+            try_iter,
+            outcome_only,
+            err_fingerprint,
+            unsafe_tag,
+            track_caller: false,
+            location,
+            valuable_args: Vec::new(),
+            timestamp,
+            elapsed,
+            cpu_time,
+            allocations,
+            poll_stats,
+            first_poll_delay,
+            catch_panic,
+            json,
+            schema,
+            lint_attrs,
+            after_error,
+            sample,
+            diff_by,
+            groups,
+            filter,
+            backend,
+        }
+    }
+}
+
+// (a, b, c)
+fn parse_idents(nested: impl IntoIterator<Item = NestedMeta>) -> Vec<Ident> {
+    nested
+        .into_iter()
+        .map(|meta| match meta {
+            NestedMeta::Meta(Meta::Path(path)) if path.get_ident().is_some() => {
+                path.get_ident().unwrap().clone()
+            }
+            _ => abort!(meta.span(), "expected an argument name"),
+        })
+        .collect()
+}
+
+// (trace = "full", info = "summary")
+fn parse_detail(nested: impl IntoIterator<Item = NestedMeta>) -> Vec<(String, bool)> {
+    nested
+        .into_iter()
+        .map(|meta| match meta {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(lit_str),
+                ..
+            })) if path.get_ident().is_some() => {
+                let summary = match lit_str.value().as_str() {
+                    "full" => false,
+                    "summary" => true,
+                    _ => abort!(lit_str.span(), "expected `full` or `summary`"),
+                };
+                (check_level(&path.get_ident().unwrap().to_string()), summary)
+            }
+            _ => abort!(meta.span(), "expected a level, e.g. `info = \"summary\"`"),
+        })
+        .collect()
+}
+
+// (a, b, items = 5)
+fn parse_head(span: Span, nested: impl IntoIterator<Item = NestedMeta>) -> Head {
+    let mut args = Vec::new();
+    let mut items = None;
+    for meta in nested {
+        match meta {
+            NestedMeta::Meta(Meta::Path(path)) if path.get_ident().is_some() => {
+                args.push(path.get_ident().unwrap().clone());
+            }
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Int(lit_int),
+                ..
+            })) if path.is_ident("items") => {
+                items = match lit_int.base10_parse() {
+                    Ok(0) | Err(_) => abort!(lit_int.span(), "expected a positive number of items"),
+                    Ok(items) => Some(items),
+                };
+            }
+            _ => abort!(meta.span(), "expected an argument name or `items`"),
+        }
+    }
+    match items {
+        Some(items) => Head { args, items },
+        None => abort!(span, "expected the number of items, e.g. `items = 5`"),
+    }
+}
+
+// (a, b, as = "name")
+fn parse_group(span: Span, nested: impl IntoIterator<Item = NestedMeta>) -> Group {
+    let mut members = Vec::new();
+    let mut name = None;
+    for meta in nested {
+        match meta {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(lit_str),
+                ..
+            })) if path.is_ident("as") => {
+                name = Some(lit_str);
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.get_ident().is_some() => {
+                members.push(path.get_ident().unwrap().clone());
+            }
+            _ => abort!(meta.span(), "expected an argument name or `as`"),
+        }
+    }
+    match name {
+        Some(name) if !members.is_empty() => Group { members, name },
+        Some(name) => abort!(name.span(), "expected at least one argument"),
+        None => abort!(span, "expected the name of the group, e.g. `as = \"meta\"`"),
+    }
+}
+
+// (level = "debug", count = 20)
+fn parse_after_error(nested: impl IntoIterator<Item = NestedMeta>) -> AfterError {
+    let mut level = "debug".to_string();
+    let mut count = 20;
+    for meta in nested {
+        match meta {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(lit_str),
+                ..
+            })) if path.is_ident("level") => {
+                level = lit_str.value();
+            }
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Int(lit_int),
+                ..
+            })) if path.is_ident("count") => {
+                count = match lit_int.base10_parse() {
+                    Ok(0) | Err(_) => abort!(lit_int.span(), "expected a positive count"),
+                    Ok(count) => count,
+                };
+            }
+            _ => abort!(meta.span(), "expected `level` or `count`"),
+        }
+    }
+    AfterError {
+        level: check_level(&level),
+        count,
+    }
+}
+
+// auto_mute(threshold = "1000/min", for = "5m")
+fn parse_auto_mute(span: Span, nested: impl IntoIterator<Item = NestedMeta>) -> AutoMute {
+    let mut threshold = None;
+    let mut cooldown = None;
+    for meta in nested {
+        match meta {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(lit_str),
+                ..
+            })) if path.is_ident("threshold") => {
+                threshold = Some(parse_rate(&lit_str));
+            }
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(lit_str),
+                ..
+            })) if path.is_ident("for") => {
+                cooldown = Some((parse_duration(&lit_str), lit_str.value()));
+            }
+            _ => abort!(meta.span(), "expected `threshold` or `for`"),
+        }
+    }
+    let Some((threshold, window, rate)) = threshold else {
+        abort!(span, "expected a `threshold` such as `\"1000/min\"`");
+    };
+    let (cooldown, cooldown_str) = cooldown.unwrap_or((Duration::from_secs(5 * 60), "5m".into()));
+    AutoMute {
+        threshold,
+        window,
+        cooldown,
+        rate,
+        cooldown_str,
+    }
+}
+
+// metrics(error_label = "ErrorKind::as_str")
+fn parse_metrics(nested: impl IntoIterator<Item = NestedMeta>) -> Option<Expr> {
+    let mut error_label = None;
+    for meta in nested {
+        match meta {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(lit_str),
+                ..
+            })) if path.is_ident("error_label") => match lit_str.parse::<Expr>() {
+                Ok(expr) => error_label = Some(expr),
+                Err(_) => abort!(
+                    lit_str.span(),
+                    "expected a function such as `Error::as_str`"
+                ),
+            },
+            _ => abort!(meta.span(), "expected `error_label`"),
+        }
+    }
+    error_label
+}
+
+// elapsed_levels(100ms = "debug", 1s = "info", 5s = "warn"), normalized by `normalize_args`
+// into `elapsed_levels(_100ms = "debug", ..)`
+fn parse_elapsed_levels(span: Span, nested: impl IntoIterator<Item = NestedMeta>) -> ElapsedLevels {
+    const SEVERITY: [&str; 5] = ["trace", "debug", "info", "warn", "error"];
+    let severity = |level: &str| SEVERITY.iter().position(|s| *s == level);
+    let mut rungs: Vec<(Duration, String)> = Vec::new();
+    for meta in nested {
+        let (threshold, level) = match &meta {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(lit_str),
+                ..
+            })) => match path.get_ident().map(Ident::to_string) {
+                Some(ident) if ident.starts_with('_') => {
+                    let threshold = LitStr::new(&ident[1..], path.span());
+                    (parse_duration(&threshold), check_level(&lit_str.value()))
+                }
+                _ => abort!(meta.span(), "expected a rung such as `1s = \"info\"`"),
+            },
+            _ => abort!(meta.span(), "expected a rung such as `1s = \"info\"`"),
+        };
+        if let Some((last_threshold, last_level)) = rungs.last() {
+            if threshold <= *last_threshold {
+                abort!(
+                    meta.span(),
+                    "the thresholds of `elapsed_levels` must increase"
+                );
+            }
+            if severity(&level) < severity(last_level) {
+                abort!(
+                    meta.span(),
+                    "the levels of `elapsed_levels` must not decrease in severity"
+                );
+            }
+        }
+        rungs.push((threshold, level));
+    }
+    if rungs.is_empty() {
+        abort!(span, "expected at least one rung such as `1s = \"info\"`");
+    }
+    ElapsedLevels { rungs, only: false }
+}
+
+// "1000/s", "1000/min", "1000/h"
+fn parse_rate(lit_str: &LitStr) -> (u64, Duration, String) {
+    let value = lit_str.value();
+    let rate = value.split_once('/').and_then(|(count, unit)| {
+        let count = count.trim().parse().ok().filter(|count| *count > 0)?;
+        let window = match unit.trim() {
+            "s" => Duration::from_secs(1),
+            "min" => Duration::from_secs(60),
+            "h" => Duration::from_secs(60 * 60),
+            _ => return None,
+        };
+        Some((count, window))
+    });
+    match rate {
+        Some((count, window)) => (count, window, value),
+        None => abort!(
+            lit_str.span(),
+            "expected a rate such as `1000/s`, `1000/min` or `1000/h`"
+        ),
+    }
+}
+
+fn parse_sample_rate(lit_float: &LitFloat) -> f64 {
+    match lit_float.base10_parse() {
+        Ok(rate) if rate > 0.0 && rate <= 1.0 => rate,
+        _ => abort!(lit_float.span(), "expected a sample rate in (0, 1]"),
+    }
+}
+
+fn parse_pattern(lit_str: LitStr) -> LitStr {
+    let value = lit_str.value();
+    if value.is_empty()
+        || !value
+            .chars()
+            .all(|c| c == '*' || c == '_' || c.is_alphanumeric())
+    {
+        abort!(
+            lit_str.span(),
+            "expected a function name pattern such as `handle_*`"
+        );
+    }
+    lit_str
+}
+
+// `*` matches any sequence of characters, including an empty one.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, rest)) => {
+            let Some(name) = name.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=name.len())
+                .filter(|&i| name.is_char_boundary(i))
+                .any(|i| glob_match(rest, &name[i..]))
+        }
+    }
+}
+
+fn parse_target(lit_str: LitStr) -> LitStr {
+    if lit_str.value().is_empty() {
+        abort!(lit_str.span(), "expected a non-empty target");
+    }
+    lit_str
+}
+
+// The capacity of the stack buffers used by `heapless` when no size is specified.
+const DEFAULT_BUFFER_SIZE: usize = 256;
+
+// "250ms", "10s", "5m", "1h"
+fn parse_duration(lit_str: &LitStr) -> Duration {
+    let value = lit_str.value();
+    let unit_start = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(unit_start);
+    let amount: u64 = match amount.parse() {
+        Ok(amount) => amount,
+        Err(_) => abort!(
+            lit_str.span(),
+            "expected a duration such as `250ms` or `10s`"
+        ),
+    };
+    let duration = match unit {
+        "ns" => Some(Duration::from_nanos(amount)),
+        "us" => Some(Duration::from_micros(amount)),
+        "ms" => Some(Duration::from_millis(amount)),
+        "s" => Some(Duration::from_secs(amount)),
+        "m" => amount.checked_mul(60).map(Duration::from_secs),
+        "h" => amount.checked_mul(60 * 60).map(Duration::from_secs),
+        _ => abort!(
+            lit_str.span(),
+            "expected a duration unit: `ns`, `us`, `ms`, `s`, `m` or `h`"
+        ),
+    };
+    // Generated as nanoseconds by `gen_duration`.
+    match duration.filter(|duration| u64::try_from(duration.as_nanos()).is_ok()) {
+        Some(duration) => duration,
+        None => abort!(
+            lit_str.span(),
+            "the duration is too long, expected at most {} seconds",
+            u64::MAX / 1_000_000_000
+        ),
+    }
+}
+
+// Duration::from_nanos(..)
+fn gen_duration(duration: Duration) -> proc_macro2::TokenStream {
+    let nanos = match u64::try_from(duration.as_nanos()) {
+        Ok(nanos) => nanos,
+        Err(_) => abort_call_site!("the duration {:?} is too long", duration),
+    };
+    quote::quote!(::std::time::Duration::from_nanos(#nanos))
+}
+
+// "512", "512B", "4KB", "1KiB", "1MiB"
+fn parse_size(lit_str: &LitStr) -> usize {
+    let value = lit_str.value();
+    let unit_start = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(unit_start);
+    let amount: usize = match amount.parse() {
+        Ok(amount) if amount > 0 => amount,
+        _ => abort!(lit_str.span(), "expected a size such as `512B` or `1KiB`"),
+    };
+    let unit = match unit {
+        "" | "B" => 1,
+        "KB" => 1000,
+        "KiB" => 1024,
+        "MB" => 1000 * 1000,
+        "MiB" => 1024 * 1024,
+        _ => abort!(
+            lit_str.span(),
+            "expected a size unit: `B`, `KB`, `KiB`, `MB` or `MiB`"
+        ),
+    };
+    amount * unit
+}
+
+fn parse_buffer_size(lit_int: &LitInt) -> usize {
+    match lit_int.base10_parse() {
+        Ok(0) | Err(_) => abort!(lit_int.span(), "expected a positive buffer size"),
+        Ok(size) => size,
+    }
+}
+
+fn parse_max_depth(lit_int: &LitInt) -> usize {
+    match lit_int.base10_parse() {
+        Ok(0) | Err(_) => abort!(lit_int.span(), "expected a positive depth"),
+        Ok(max_depth) => max_depth,
+    }
+}
+
+fn parse_array_summary(lit_int: &LitInt) -> usize {
+    match lit_int.base10_parse() {
+        Ok(0) | Err(_) => abort!(lit_int.span(), "expected a positive number of elements"),
+        Ok(limit) => limit,
+    }
+}
+
+/// `logcall` attribute macro that logs the function inputs and return values.
+///
+/// On an `impl` block or an inline module, every method or function is instrumented with the
+/// same arguments, except those carrying their own `#[logcall]` attribute, or
+/// `#[logcall::skip]`. The `only_pub`,
+/// `only_async` and `match = "handle_*"` arguments restrict the instrumentation to the public,
+/// the async, or the matching functions respectively.
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn logcall(
+    args: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    expand_logcall(args.into(), item)
+}
+
+/// Logs only the name and the duration of the calls, e.g. `main::render elapsed=1.52ms`,
+/// without rendering the arguments and the return value, which need not implement `Debug`.
+///
+/// Shorthand for `#[logcall(only = "timing")]`, taking the same arguments otherwise.
+///
+/// ```ignore
+/// #[logcall::timeit("info")]
+/// fn render(scene: &Scene) -> Frame {
+///     // ...
+/// }
+/// ```
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn timeit(
+    args: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let args = proc_macro2::TokenStream::from(args);
+    let args = if args.is_empty() {
+        quote::quote!(only = "timing")
+    } else {
+        quote::quote!(only = "timing", #args)
+    };
+    expand_logcall(args, item)
+}
+
+fn expand_logcall(
+    args: proc_macro2::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(item as Item);
+    let args = normalize_args(args).into();
+    let args = Args::parse(syn::parse_macro_input!(args as AttributeArgs));
+
+    match input {
+        Item::Fn(input) => {
+            if args.filter.is_set() {
+                abort_call_site!(
+                    "`only_pub`, `only_async` and `match` can only be used on `impl` blocks or modules"
+                );
+            }
+            expand_fn(input, args).into()
+        }
+        Item::Impl(input) => expand_impl(input, args).into(),
+        Item::Mod(input) => expand_mod(input, args).into(),
+        _ => abort!(input.span(), "expected `fn`, `impl` or `mod`"),
+    }
+}
+
+// Instruments the methods of an `impl` block. Methods of a trait implementation are as public
+// as the trait.
+fn expand_impl(mut input: ItemImpl, args: Args) -> proc_macro2::TokenStream {
+    let is_trait_impl = input.trait_.is_some();
+    for item in &mut input.items {
+        if let ImplItem::Method(method) = item {
+            let is_pub = is_trait_impl || matches!(method.vis, Visibility::Public(_));
+            if has_logcall_attr(&method.attrs)
+                || has_skip_attr(&method.attrs)
+                || !args.filter.matches(is_pub, &method.sig)
+            {
+                continue;
+            }
+            let ImplItemMethod {
+                attrs,
+                vis,
+                sig,
+                block,
+                ..
+            } = method.clone();
+            let item_fn = ItemFn {
+                attrs,
+                vis,
+                sig,
+                block: Box::new(block),
+            };
+            *item = ImplItem::Verbatim(expand_fn(item_fn, args.clone()));
+        }
+    }
+    quote::quote!(#input)
+}
+
+// Instruments the functions of an inline module, not descending into nested modules.
+fn expand_mod(mut input: ItemMod, args: Args) -> proc_macro2::TokenStream {
+    let Some((_, items)) = &mut input.content else {
+        abort!(input.span(), "expected an inline module");
+    };
+    for item in items {
+        if let Item::Fn(item_fn) = item {
+            let is_pub = matches!(item_fn.vis, Visibility::Public(_));
+            if has_logcall_attr(&item_fn.attrs)
+                || has_skip_attr(&item_fn.attrs)
+                || !args.filter.matches(is_pub, &item_fn.sig)
+            {
+                continue;
+            }
+            *item = Item::Verbatim(expand_fn(item_fn.clone(), args.clone()));
+        }
+    }
+    quote::quote!(#input)
+}
+
+// `#[logcall]`, `#[logcall::logcall]` or `#[logcall::timeit]`, which take precedence over the
+// blanket attribute.
+fn has_logcall_attr(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "logcall" || segment.ident == "timeit")
+    })
+}
+
+// `#[logcall::skip]`, or `#[skip]` if imported.
+fn has_skip_attr(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        let segments = attr
+            .path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect::<Vec<_>>();
+        match segments.as_slice() {
+            [name] => name == "skip",
+            [krate, name] => krate == "logcall" && name == "skip",
+            _ => false,
+        }
+    })
+}
+
+/// Exempts a function from the `#[logcall]` attribute of its `impl` block or inline module, or
+/// a method from the decorator generated by `#[logcall::wrap_dyn]`, which delegates to it
+/// without logging.
+///
+/// ```ignore
+/// #[logcall::logcall("info")]
+/// impl Vault {
+///     pub fn open(&self, id: u32) -> Result<Entry, Error> {
+///         // ...
+///     }
+///
+///     #[logcall::skip]
+///     pub fn unseal(&self, passphrase: &str) -> Key {
+///         // ...
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn skip(
+    args: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    if !args.is_empty() {
+        abort_call_site!("`skip` takes no arguments");
+    }
+    item
+}
+
+/// Implements `logcall::Render` for a type, so that its arguments are rendered in the records by
+/// a format string instead of its `Debug` implementation, e.g. `user:12345`.
+///
+/// The fields of a tuple struct are passed to the format in order, and the fields of a struct
+/// with named fields can be captured by name. Without a format, the type is rendered by the
+/// renderer installed at runtime with `logcall::set_renderer`, if any, and with `Debug`
+/// otherwise.
+///
+/// ```ignore
+/// #[logcall::render_as("user:{}")]
+/// struct UserId(u64);
+///
+/// #[logcall::render_as("order:{region}-{number}")]
+/// struct OrderId {
+///     region: u16,
+///     number: u64,
+/// }
+///
+/// #[derive(Debug)]
+/// #[logcall::render_as]
+/// struct SessionId(u128);
+/// ```
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn render_as(
+    args: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let args = syn::parse_macro_input!(args as AttributeArgs);
+    let input = syn::parse_macro_input!(item as DeriveInput);
+    let format = match args.as_slice() {
+        [] => None,
+        [NestedMeta::Lit(Lit::Str(format))] => Some(format),
+        [arg, ..] => abort!(arg.span(), "expected a format string such as `\"user:{}\"`"),
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let render = match (format, &input.data) {
+        (None, _) => quote::quote!(::logcall::__private::render_registered(self, f)),
+        (Some(format), Data::Struct(DataStruct { fields, .. })) => match fields {
+            Fields::Named(fields) => {
+                // Bound with the span of the format, so that it can capture them.
+                let names = fields.named.iter().map(|field| {
+                    let name = field.ident.as_ref().unwrap();
+                    Ident::new(&name.to_string(), format.span())
+                });
+                quote::quote!(
+                    #[allow(unused_variables)]
+                    let Self { #(#names),* } = self;
+                    ::std::write!(f, #format)
+                )
+            }
+            Fields::Unnamed(fields) => {
+                let indices = (0..fields.unnamed.len()).map(syn::Index::from);
+                quote::quote!(::std::write!(f, #format, #(self.#indices),*))
+            }
+            Fields::Unit => quote::quote!(::std::write!(f, #format)),
+        },
+        (Some(format), _) => abort!(
+            format.span(),
+            "a format can only be given for a struct, use `#[logcall::render_as]` and `logcall::set_renderer` otherwise"
+        ),
+    };
+    quote::quote!(
+        #input
+
+        impl #impl_generics ::logcall::Render for #ident #ty_generics #where_clause {
+            fn render(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                #render
+            }
+        }
+    )
+    .into()
+}
+
+/// Overrides the levels of the `#[logcall]` attributes of an inline module, including those of
+/// its `impl` blocks, of its nested modules and of the module itself.
+///
+/// The level replaces the level given by an attribute, e.g. `ok`, or is added to the attributes
+/// without one. The `err` level, `slow_level`, `elapsed_levels` and `after_error` are kept, as
+/// with the runtime overrides. It can in turn be overridden by the `LOGCALL_LEVEL` environment
+/// variable and by `logcall::set_level_override`.
+///
+/// ```ignore
+/// #[logcall::module_defaults(level = "debug")]
+/// mod db {
+///     #[logcall::logcall(ok = "info", err = "error")]
+///     pub fn query(sql: &str) -> Result<Rows, Error> {
+///         // ...
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn module_defaults(
+    args: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let args = syn::parse_macro_input!(args as AttributeArgs);
+    let mut input = syn::parse_macro_input!(item as ItemMod);
+
+    let mut level = None;
+    for arg in args {
+        match arg {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(lit_str),
+                ..
+            })) if path.is_ident("level") => {
+                check_level(&lit_str.value());
+                level = Some(lit_str);
+            }
+            _ => abort!(arg.span(), "unexpected argument"),
+        }
+    }
+    let Some(level) = level else {
+        abort_call_site!("expected a `level` argument");
+    };
+    if input.content.is_none() {
+        abort!(input.span(), "expected an inline module");
+    }
+
+    ModuleDefaults { level }.visit_item_mod_mut(&mut input);
+    quote::quote!(#input).into()
+}
+
+struct ModuleDefaults {
+    level: LitStr,
+}
+
+impl VisitMut for ModuleDefaults {
+    fn visit_attribute_mut(&mut self, attr: &mut Attribute) {
+        if !has_logcall_attr(std::slice::from_ref(attr)) {
+            return;
+        }
+        attr.tokens = normalize_args(attr.tokens.clone());
+        let mut args = match attr.parse_meta() {
+            Ok(Meta::Path(_)) => Vec::new(),
+            Ok(Meta::List(MetaList { nested, .. })) => nested.into_iter().collect(),
+            // Reported by `#[logcall]` itself.
+            _ => return,
+        };
+        let level = &self.level;
+        let mut replaced = false;
+        // Like the runtime overrides, the defaults leave the `err` level and the escalations
+        // alone, and don't log the calls of an attribute that only logs errors or slow calls.
+        let mut kept = false;
+        for arg in &mut args {
+            match arg {
+                NestedMeta::Lit(Lit::Str(lit_str)) => {
+                    *lit_str = level.clone();
+                    replaced = true;
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(lit_str),
+                    ..
+                })) if ["level", "ok", "left", "right"]
+                    .iter()
+                    .any(|name| path.is_ident(name)) =>
+                {
+                    *lit_str = level.clone();
+                    replaced = true;
+                }
+                NestedMeta::Meta(meta)
+                    if ["err", "slow", "elapsed_levels"]
+                        .iter()
+                        .any(|name| meta.path().is_ident(name)) =>
+                {
+                    kept = true;
+                }
+                _ => {}
+            }
+        }
+        if !replaced && !kept {
+            args.insert(0, NestedMeta::Lit(Lit::Str(level.clone())));
+        }
+        attr.tokens = quote::quote!((#(#args),*));
+    }
+}
+
+/// Generates thin logged wrappers for functions defined outside of the current crate.
+///
+/// Each entry names the wrapped function, followed by `as` and the signature of the wrapper.
+/// The signature is required because a macro cannot see the declaration of a foreign function.
+/// The wrapper is instrumented as if it were annotated with `#[logcall]`; an optional
+/// `#[logcall(...)]` attribute on the entry accepts the same arguments as the attribute macro.
+///
+/// ```ignore
+/// logcall::instrument_extern! {
+///     std::fs::read_to_string as pub fn read_to_string_logged(path: &str) -> std::io::Result<String>;
+///     #[logcall(ok = "info", err = "error")]
+///     reqwest::get as pub async fn get_logged(url: &str) -> reqwest::Result<reqwest::Response>;
+/// }
+/// ```
+#[proc_macro]
+#[proc_macro_error]
+pub fn instrument_extern(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as ExternFns);
+
+    let mut output = proc_macro2::TokenStream::new();
+    for extern_fn in input.fns {
+        output.extend(extern_fn.expand());
+    }
+    output.into()
+}
+
+struct ExternFns {
+    fns: Vec<ExternFn>,
+}
+
+impl parse::Parse for ExternFns {
+    fn parse(input: parse::ParseStream) -> Result<Self> {
+        let mut fns = Vec::new();
+        while !input.is_empty() {
+            fns.push(input.parse()?);
+        }
+        Ok(ExternFns { fns })
+    }
+}
+
+// `#[logcall("info")] path::to::foreign as pub fn wrapper(a: usize) -> usize;`
+struct ExternFn {
+    attrs: Vec<Attribute>,
+    path: Path,
+    vis: Visibility,
+    sig: Signature,
+}
+
+impl parse::Parse for ExternFn {
+    fn parse(input: parse::ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let path = input.call(Path::parse_mod_style)?;
+        input.parse::<Token![as]>()?;
+        let vis = input.parse()?;
+        let sig = input.parse()?;
+        input.parse::<Token![;]>()?;
+        Ok(ExternFn {
+            attrs,
+            path,
+            vis,
+            sig,
+        })
+    }
+}
+
+impl ExternFn {
+    fn expand(self) -> proc_macro2::TokenStream {
+        let ExternFn {
+            attrs,
+            path,
+            vis,
+            sig,
+        } = self;
+
+        let mut args = None;
+        let mut fn_attrs = Vec::new();
+        for attr in attrs {
+            if attr.path.is_ident("logcall") {
+                if args.is_some() {
+                    abort!(attr.span(), "duplicated `logcall` attribute");
+                }
+                args = Some(parse_attribute_args(&attr));
+            } else {
+                fn_attrs.push(attr);
+            }
+        }
+        let args = args.unwrap_or_else(|| Args::parse(AttributeArgs::new()));
+
+        let mut call_args = Vec::new();
+        for input in &sig.inputs {
+            match input {
+                FnArg::Typed(PatType { pat, .. }) => match &**pat {
+                    Pat::Ident(pat_ident) => call_args.push(&pat_ident.ident),
+                    _ => abort!(pat.span(), "expected an identifier"),
+                },
+                FnArg::Receiver(receiver) => {
+                    abort!(
+                        receiver.span(),
+                        "wrapper of foreign function cannot take `self`"
+                    )
+                }
+            }
+        }
+        let call = if sig.asyncness.is_some() {
+            quote::quote!(#path(#(#call_args),*).await)
+        } else {
+            quote::quote!(#path(#(#call_args),*))
+        };
+
+        let item = ItemFn {
+            attrs: fn_attrs,
+            vis,
+            sig,
+            block: Box::new(parse_quote!({ #call })),
+        };
+        expand_fn(item, args)
+    }
+}
+
+/// Declares foreign functions and generates logged wrappers for them.
+///
+/// Each declaration is placed in an `extern` block of a hidden module, and a wrapper with the
+/// same name, signature and visibility calls it, instrumented as if it were annotated with
+/// `#[logcall]`. An optional `#[logcall(...)]` attribute on the declaration accepts the same
+/// arguments as the attribute macro. Bare declarations are declared with the `"C"` ABI, while
+/// declarations in an `extern` block keep its ABI and attributes, e.g. `#[link]`.
+///
+/// The wrappers are `unsafe fn`, unless the declaration is marked `unsafe`, either as an
+/// `unsafe fn` or in an `unsafe extern` block, asserting that the foreign function is sound to
+/// call with any arguments. Its wrapper is then safe to call. Raw pointer, reference and slice
+/// arguments and return values are rejected.
+///
+/// ```ignore
+/// logcall::extern_shim! {
+///     // Called as `unsafe { c_compute(1) }`.
+///     fn c_compute(x: i32) -> i32;
+///     // Called as `cbrt(8.0)`.
+///     #[link(name = "m")]
+///     unsafe extern "C" {
+///         #[logcall("debug")]
+///         pub fn cbrt(x: f64) -> f64;
+///     }
+/// }
+/// ```
+#[proc_macro]
+#[proc_macro_error]
+pub fn extern_shim(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as ExternShims);
+
+    let mut output = proc_macro2::TokenStream::new();
+    for shim in input.shims {
+        output.extend(shim.expand());
+    }
+    output.into()
+}
+
+struct ExternShims {
+    shims: Vec<ExternShim>,
+}
+
+impl parse::Parse for ExternShims {
+    fn parse(input: parse::ParseStream) -> Result<Self> {
+        let mut shims = Vec::new();
+        while !input.is_empty() {
+            let attrs = input.call(Attribute::parse_outer)?;
+            let mut safe = false;
+            if input.peek(Token![unsafe]) && input.peek2(Token![extern]) {
+                input.parse::<Token![unsafe]>()?;
+                safe = true;
+            }
+            if input.peek(Token![extern]) {
+                let block = ItemForeignMod {
+                    attrs,
+                    ..input.parse()?
+                };
+                for item in block.items {
+                    match item {
+                        ForeignItem::Fn(item) => shims.push(ExternShim {
+                            abi: block.abi.clone(),
+                            block_attrs: block.attrs.clone(),
+                            safe,
+                            item,
+                        }),
+                        item => abort!(item.span(), "expected a foreign function"),
+                    }
+                }
+            } else {
+                let mut item = ForeignItemFn {
+                    attrs,
+                    ..input.parse()?
+                };
+                // Declared without the qualifier, which only marks the wrapper as safe.
+                let safe = item.sig.unsafety.take().is_some();
+                shims.push(ExternShim {
+                    abi: parse_quote!(extern "C"),
+                    block_attrs: Vec::new(),
+                    safe,
+                    item,
+                });
+            }
+        }
+        Ok(ExternShims { shims })
+    }
+}
+
+// `#[logcall("info")] fn c_compute(x: i32) -> i32;`, in an `extern` block or bare
+struct ExternShim {
+    abi: Abi,
+    block_attrs: Vec<Attribute>,
+    // Whether the declaration is marked `unsafe`, so that the wrapper is safe to call.
+    safe: bool,
+    item: ForeignItemFn,
+}
+
+impl ExternShim {
+    // mod __logcall_ffi_c_compute { use super::*; extern "C" { pub fn c_compute(x: i32) -> i32; } }
+    // unsafe fn c_compute(x: i32) -> i32 { unsafe { __logcall_ffi_c_compute::c_compute(x) } }
+    fn expand(self) -> proc_macro2::TokenStream {
+        let ExternShim {
+            abi,
+            block_attrs,
+            safe,
+            item: ForeignItemFn {
+                attrs, vis, sig, ..
+            },
+        } = self;
+
+        let mut args = None;
+        let mut fn_attrs = Vec::new();
+        let mut decl_attrs = Vec::new();
+        for attr in attrs {
+            if attr.path.is_ident("logcall") {
+                if args.is_some() {
+                    abort!(attr.span(), "duplicated `logcall` attribute");
+                }
+                args = Some(parse_attribute_args(&attr));
+            } else if attr.path.is_ident("link_name") {
+                decl_attrs.push(attr);
+            } else if attr.path.is_ident("cfg") {
+                decl_attrs.push(attr.clone());
+                fn_attrs.push(attr);
+            } else {
+                fn_attrs.push(attr);
+            }
+        }
+        let args = args.unwrap_or_else(|| Args::parse(AttributeArgs::new()));
+
+        if let Some(variadic) = &sig.variadic {
+            abort!(
+                variadic.span(),
+                "wrapper of foreign function cannot be variadic"
+            );
+        }
+        let mut call_args = Vec::new();
+        for input in &sig.inputs {
+            match input {
+                FnArg::Typed(PatType { pat, ty, .. }) => {
+                    if let Some(ty) = find_borrow(ty) {
+                        abort!(
+                            ty.span(),
+                            "wrapper of foreign function cannot take raw pointers, references or slices"
+                        );
+                    }
+                    match &**pat {
+                        Pat::Ident(pat_ident) => call_args.push(&pat_ident.ident),
+                        _ => abort!(pat.span(), "expected an identifier"),
+                    }
+                }
+                FnArg::Receiver(receiver) => {
+                    abort!(
+                        receiver.span(),
+                        "wrapper of foreign function cannot take `self`"
+                    )
+                }
+            }
+        }
+
+        if let ReturnType::Type(_, ty) = &sig.output {
+            if let Some(ty) = find_borrow(ty) {
+                abort!(
+                    ty.span(),
+                    "wrapper of foreign function cannot return raw pointers, references or slices"
+                );
+            }
+        }
+
+        let ident = &sig.ident;
+        let module = Ident::new(&format!("__logcall_ffi_{}", ident), ident.span());
+        let decl_cfgs = decl_attrs.iter().filter(|attr| attr.path.is_ident("cfg"));
+        let mut wrapper_sig = sig.clone();
+        if !safe {
+            wrapper_sig.unsafety = Some(Default::default());
+        }
+        let item = ItemFn {
+            attrs: fn_attrs,
+            vis,
+            sig: wrapper_sig,
+            block: Box::new(parse_quote!({
+                #[allow(unused_unsafe)]
+                unsafe {
+                    #module::#ident(#(#call_args),*)
+                }
+            })),
+        };
+        let wrapper = expand_fn(item, args);
+        quote::quote!(
+            #(#decl_cfgs)*
+            #[doc(hidden)]
+            #[allow(non_snake_case)]
+            mod #module {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #(#block_attrs)*
+                unsafe #abi {
+                    #(#decl_attrs)*
+                    pub #sig;
+                }
+            }
+
+            #wrapper
+        )
+    }
+}
+
+// The first raw pointer, reference or slice in the type, which the safety of the foreign function
+// would depend on, e.g. `&u8` in `Option<&u8>`.
+fn find_borrow(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Ptr(_) | Type::Reference(_) | Type::Slice(_) => Some(ty),
+        Type::Array(array) => find_borrow(&array.elem),
+        Type::Group(group) => find_borrow(&group.elem),
+        Type::Paren(paren) => find_borrow(&paren.elem),
+        Type::Tuple(tuple) => tuple.elems.iter().find_map(find_borrow),
+        Type::Path(path) => path
+            .path
+            .segments
+            .iter()
+            .filter_map(|segment| match &segment.arguments {
+                PathArguments::AngleBracketed(args) => Some(&args.args),
+                _ => None,
+            })
+            .flatten()
+            .find_map(|arg| match arg {
+                GenericArgument::Type(ty) => find_borrow(ty),
+                _ => None,
+            }),
+        _ => None,
+    }
+}
+
+/// Generates a logging decorator for a trait.
+///
+/// Annotating `trait MyTrait` generates a `LoggedMyTrait<T>` struct which implements `MyTrait`
+/// for any `T: MyTrait` by logging the inputs and outputs of every method before delegating
+/// to the wrapped value. The attribute accepts the same arguments as `#[logcall]`, and they
+/// apply to every method of the trait, or to those selected by the `only_async` and `match`
+/// filters. The other methods delegate without logging.
+///
+/// ```ignore
+/// #[logcall::wrap_dyn("info")]
+/// trait Plugin {
+///     fn handle(&self, request: u32) -> u32;
+/// }
+///
+/// let plugin: Box<dyn Plugin> = Box::new(LoggedPlugin::new(MyPlugin));
+/// ```
+///
+/// Supertraits other than auto traits are not implemented by the decorator.
+#[proc_macro_attribute]
+#[proc_macro_error]
+pub fn wrap_dyn(
+    args: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(item as ItemTrait);
+    let args = normalize_args(args.into()).into();
+    let args = Args::parse(syn::parse_macro_input!(args as AttributeArgs));
+
+    let ItemTrait {
+        vis,
+        unsafety,
+        ident: trait_ident,
+        generics: trait_generics,
+        items,
+        ..
+    } = &input;
+
+    let logged_ident = Ident::new(&format!("Logged{trait_ident}"), trait_ident.span());
+    let inner_ty: Ident = parse_quote!(__LogcallInner);
+    let (_, trait_ty_generics, _) = trait_generics.split_for_impl();
+
+    // Lifetimes have to come before the inner type parameter.
+    let mut impl_generics = trait_generics.clone();
+    let inner_param: GenericParam = parse_quote!(#inner_ty: #trait_ident #trait_ty_generics);
+    let lifetimes = impl_generics.lifetimes().count();
+    impl_generics.params.insert(lifetimes, inner_param);
+    let (impl_generics, _, where_clause) = impl_generics.split_for_impl();
+
+    let mut impl_items = Vec::new();
+    for item in items {
+        match item {
+            TraitItem::Const(TraitItemConst {
+                attrs, ident, ty, ..
+            }) => {
+                let attrs = attrs.iter().filter(|attr| attr.path.is_ident("cfg"));
+                impl_items.push(quote::quote!(
+                    #(#attrs)*
+                    const #ident: #ty = <#inner_ty as #trait_ident #trait_ty_generics>::#ident;
+                ));
+            }
+            TraitItem::Type(TraitItemType {
+                attrs,
+                ident,
+                generics,
+                ..
+            }) => {
+                let attrs = attrs.iter().filter(|attr| attr.path.is_ident("cfg"));
+                let (_, ty_generics, where_clause) = generics.split_for_impl();
+                impl_items.push(quote::quote!(
+                    #(#attrs)*
+                    type #ident #generics = <#inner_ty as #trait_ident #trait_ty_generics>::#ident #ty_generics #where_clause;
+                ));
+            }
+            TraitItem::Method(TraitItemMethod { attrs, sig, .. }) => {
+                let mut sig = sig.clone();
+                let mut call_args = Vec::new();
+                for (i, input) in sig.inputs.iter_mut().enumerate() {
+                    match input {
+                        FnArg::Receiver(receiver) => {
+                            let arg = match (&receiver.reference, &receiver.mutability) {
+                                (Some(_), Some(_)) => quote::quote!(&mut self.inner),
+                                (Some(_), None) => quote::quote!(&self.inner),
+                                (None, _) => quote::quote!(self.inner),
+                            };
+                            call_args.push(arg);
+                        }
+                        FnArg::Typed(PatType { pat, ty, .. }) => {
+                            let ident = match &**pat {
+                                Pat::Ident(PatIdent { ident, .. }) if ident == "self" => {
+                                    abort!(ty.span(), "receiver of this type is not supported")
+                                }
+                                Pat::Ident(PatIdent { ident, .. }) => ident.clone(),
+                                _ => {
+                                    // Name the argument so that it can be forwarded.
+                                    let ident = Ident::new(&format!("__arg{i}"), pat.span());
+                                    **pat = parse_quote!(#ident);
+                                    ident
+                                }
+                            };
+                            call_args.push(quote::quote!(#ident));
+                        }
+                    }
+                }
+
+                let method_ident = &sig.ident;
+                let call = quote::quote!(
+                    <#inner_ty as #trait_ident #trait_ty_generics>::#method_ident(#(#call_args),*)
+                );
+                let call = if sig.asyncness.is_some() {
+                    quote::quote!(#call.await)
+                } else {
+                    call
+                };
+
+                let item = ItemFn {
+                    attrs: attrs
+                        .iter()
+                        .filter(|attr| attr.path.is_ident("cfg") || attr.path.is_ident("allow"))
+                        .cloned()
+                        .collect(),
+                    vis: Visibility::Inherited,
+                    sig,
+                    block: Box::new(parse_quote!({ #call })),
+                };
+                // Trait methods are as public as the trait.
+                if !has_skip_attr(attrs) && args.filter.matches(true, &item.sig) {
+                    impl_items.push(expand_fn(item, args.clone()));
+                } else {
+                    impl_items.push(quote::quote!(#item));
+                }
+            }
+            TraitItem::Macro(mac) => abort!(mac.span(), "macro invocations are not supported"),
+            _ => abort!(item.span(), "unsupported trait item"),
+        }
+    }
+
+    let struct_doc = format!("A decorator that logs every call to [`{trait_ident}`] before delegating to the inner value.");
+
+    quote::quote!(
+        #input
+
+        #[doc = #struct_doc]
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+        #vis struct #logged_ident<T> {
+            inner: T,
+        }
+
+        impl<T> #logged_ident<T> {
+            /// Wraps the value so that every call to the trait is logged.
+            #vis fn new(inner: T) -> Self {
+                Self { inner }
+            }
+
+            /// Returns a reference to the wrapped value.
+            #vis fn inner(&self) -> &T {
+                &self.inner
+            }
+
+            /// Returns a mutable reference to the wrapped value.
+            #vis fn inner_mut(&mut self) -> &mut T {
+                &mut self.inner
+            }
+
+            /// Unwraps the decorator, returning the wrapped value.
+            #vis fn into_inner(self) -> T {
+                self.inner
+            }
+        }
+
+        #unsafety impl #impl_generics #trait_ident #trait_ty_generics for #logged_ident<#inner_ty> #where_clause {
+            #(#impl_items)*
+        }
+    )
+    .into()
+}
+
+fn parse_attribute_args(attr: &Attribute) -> Args {
+    let attr = Attribute {
+        tokens: normalize_args(attr.tokens.clone()),
+        ..attr.clone()
+    };
+    match attr.parse_meta() {
+        Ok(Meta::Path(_)) => Args::parse(AttributeArgs::new()),
+        Ok(Meta::List(list)) => Args::parse(list.nested.into_iter().collect()),
+        Ok(meta) => abort!(meta.span(), "unexpected argument"),
+        Err(err) => abort!(err.span(), err),
+    }
+}
+
+// Rewrites the duration thresholds of `elapsed_levels(100ms = "debug")`, which are not valid
+// meta syntax, into identifiers: `elapsed_levels(_100ms = "debug")`.
+fn normalize_args(tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let mut output = Vec::new();
+    let mut in_elapsed_levels = false;
+    for token in tokens {
+        let token = match token {
+            proc_macro2::TokenTree::Group(group) => {
+                let stream = if in_elapsed_levels
+                    && group.delimiter() == proc_macro2::Delimiter::Parenthesis
+                {
+                    normalize_rungs(group.stream())
+                } else {
+                    normalize_args(group.stream())
+                };
+                let mut normalized = proc_macro2::Group::new(group.delimiter(), stream);
+                normalized.set_span(group.span());
+                proc_macro2::TokenTree::Group(normalized)
+            }
+            token => token,
+        };
+        in_elapsed_levels =
+            matches!(&token, proc_macro2::TokenTree::Ident(ident) if ident == "elapsed_levels");
+        output.push(token);
+    }
+    output.into_iter().collect()
+}
+
+fn normalize_rungs(tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let mut tokens: Vec<proc_macro2::TokenTree> = tokens.into_iter().collect();
+    for i in 0..tokens.len() {
+        let proc_macro2::TokenTree::Literal(lit) = &tokens[i] else {
+            continue;
+        };
+        let is_key = matches!(tokens.get(i + 1), Some(proc_macro2::TokenTree::Punct(punct)) if punct.as_char() == '=');
+        let threshold = lit.to_string();
+        if is_key && threshold.starts_with(|c: char| c.is_ascii_digit()) {
+            tokens[i] =
+                proc_macro2::TokenTree::Ident(Ident::new(&format!("_{threshold}"), lit.span()));
+        }
+    }
+    tokens.into_iter().collect()
+}
+
+fn expand_fn(input: ItemFn, mut args: Args) -> proc_macro2::TokenStream {
+    // `unsafe_tag` may be specified on a whole `impl` block or module, but only tags the
+    // records of its `unsafe fn`s.
+    args.unsafe_tag &= input.sig.unsafety.is_some();
+    args.track_caller = input
+        .attrs
+        .iter()
+        .any(|attr| attr.path.is_ident("track_caller"));
+    // `location` may be specified on a whole `impl` block or module, but points to the
+    // definition of each function.
+    if args.location.is_some() {
+        args.location = Some(input.sig.ident.span());
+    }
+    if args.valuable() {
+        args.valuable_args = typed_args(&input.sig)
+            .map(|(ident, ty)| {
+                let shared_ref =
+                    matches!(ty, Type::Reference(reference) if reference.mutability.is_none());
+                let rendered = args.tokenize.contains(ident)
+                    || fn_arity(ty, &input.sig.generics).is_some()
+                    || args
+                        .head
+                        .as_ref()
+                        .is_some_and(|head| head.args.is_empty() || head.args.contains(ident));
+                (ident.clone(), shared_ref && !rendered)
+            })
+            .collect();
+    }
+    let doc_note = gen_doc_note(&input, &args);
+    let lint_attrs = if args.lint_attrs {
+        input
+            .attrs
+            .iter()
+            .filter(|attr| {
+                ["allow", "warn", "deny", "forbid", "expect"]
+                    .iter()
+                    .any(|lint| attr.path.is_ident(lint))
+            })
+            .cloned()
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let desugar_async = args.first_poll_delay && input.sig.asyncness.is_some();
+
+    // check for async_trait-like patterns in the block, and instrument
+    // the future instead of the wrapper
+    let func_body = if let Some(internal_fun) =
+        get_async_trait_info(&input.block, input.sig.asyncness.is_some())
+    {
+        // let's rewrite some statements!
+        match internal_fun.kind {
+            // async-trait <= 0.1.43
+            AsyncTraitKind::Function => {
+                unimplemented!(
+                    "Please upgrade the crate `async-trait` to a version higher than 0.1.44"
+                )
+            }
+            // async-trait >= 0.1.44
+            AsyncTraitKind::Async(async_expr) => {
+                // fallback if we couldn't find the '__async_trait' binding, might be
+                // useful for crates exhibiting the same behaviors as async-trait
+                let instrumented_block = gen_block(
+                    &async_expr.block,
+                    true,
+                    false,
+                    &input.sig,
+                    &lint_attrs,
+                    args,
+                );
+                let async_attrs = &async_expr.attrs;
+                quote::quote_spanned! {async_expr.span()=>
+                    Box::pin(#(#async_attrs) * #instrumented_block )
+                }
+            }
+        }
+    } else {
+        gen_block(
+            &input.block,
+            input.sig.asyncness.is_some(),
+            input.sig.asyncness.is_some() && !desugar_async,
+            &input.sig,
+            &lint_attrs,
+            args,
+        )
+    };
+
+    // The instrumentation is still generated and discarded, so that toggling the feature
+    // doesn't hide mistakes in the attributes.
+    if cfg!(feature = "disabled") {
+        return quote::quote!(#input);
+    }
+
+    let ItemFn {
+        attrs, vis, sig, ..
+    } = input.clone();
+    let sig = if desugar_async {
+        desugar_async_fn(sig)
+    } else {
+        sig
+    };
+
+    let Signature {
+        output: return_type,
+        inputs: params,
+        unsafety,
+        constness,
+        abi,
+        ident,
+        asyncness,
+        generics:
+            Generics {
+                params: gen_params,
+                where_clause,
+                ..
+            },
+        ..
+    } = sig;
+
+    let name = ident.to_string();
+
+    quote::quote_spanned!(input.span()=>
+        #(#attrs) *
+        #doc_note
+        #vis #constness #unsafety #asyncness #abi fn #ident<#gen_params>(#params) #return_type
+        #where_clause
+        {
+            ::logcall::__register!(#name);
+            #func_body
+        }
+    )
+}
+
+// With `first_poll_delay`, `async fn f(&self, a: &str) -> T` is desugared into
+// `fn f<'__logcall_0, '__logcall_1>(&'__logcall_0 self, a: &'__logcall_1 str) -> impl Future<Output = T>`,
+// so that the future is created by the function rather than on its first poll. The elided
+// lifetimes of the inputs are named, so that the future captures them, along with `Self`, as
+// the future of the `async fn` does.
+fn desugar_async_fn(mut sig: Signature) -> Signature {
+    sig.asyncness = None;
+    let mut lifetimes = InputLifetimes::default();
+    for input in &mut sig.inputs {
+        lifetimes.visit_fn_arg_mut(input);
+    }
+    for (i, lifetime) in lifetimes.named.iter().enumerate() {
+        let param = GenericParam::Lifetime(LifetimeDef::new(lifetime.clone()));
+        sig.generics.params.insert(i, param);
+    }
+    let mut captures = sig
+        .generics
+        .lifetimes()
+        .map(|def| &def.lifetime)
+        .chain(&lifetimes.mentioned)
+        .fold(Vec::new(), |mut captures, lifetime| {
+            if !captures.contains(lifetime) {
+                captures.push(lifetime.clone());
+            }
+            captures
+        })
+        .into_iter()
+        .map(|lifetime| quote::quote!(&#lifetime ()))
+        .collect::<Vec<_>>();
+    if sig.receiver().is_some() || mentions(quote::ToTokens::to_token_stream(&sig.inputs), "Self") {
+        captures.push(quote::quote!(Self));
+    }
+    // The elided lifetimes of the output are named after the input they are elided to, as the
+    // lifetimes of the inputs are now named.
+    let elided_to = lifetimes
+        .receiver
+        .clone()
+        .or_else(|| match &lifetimes.mentioned[..] {
+            [lifetime] => Some(lifetime.clone()),
+            _ => None,
+        });
+    if let (ReturnType::Type(_, ty), Some(lifetime)) = (&mut sig.output, elided_to) {
+        OutputLifetimes(lifetime).visit_type_mut(ty);
+    }
+    let output = match &sig.output {
+        ReturnType::Default => quote::quote!(()),
+        ReturnType::Type(_, ty) => quote::quote!(#ty),
+    };
+    sig.output = parse_quote!(
+        -> impl ::std::future::Future<Output = #output>
+            + ::logcall::__private::Captures<(#(#captures,)*)>
+    );
+    sig
+}
+
+// Names the elided lifetimes of the inputs, and collects the lifetimes they mention, but the
+// higher-ranked ones of `Fn(&str)`, `fn(&str)` and `for<'a>` bounds.
+#[derive(Default)]
+struct InputLifetimes {
+    named: Vec<Lifetime>,
+    mentioned: Vec<Lifetime>,
+    // The lifetime of `&self` or `&mut self`.
+    receiver: Option<Lifetime>,
+}
+
+impl InputLifetimes {
+    fn name(&mut self) -> Lifetime {
+        let lifetime = Lifetime::new(
+            &format!("'__logcall_{}", self.named.len()),
+            Span::call_site(),
+        );
+        self.named.push(lifetime.clone());
+        lifetime
+    }
+}
+
+impl VisitMut for InputLifetimes {
+    fn visit_receiver_mut(&mut self, receiver: &mut Receiver) {
+        if let Some((_, lifetime @ None)) = &mut receiver.reference {
+            *lifetime = Some(self.name());
+        }
+        if let Some((_, lifetime)) = &receiver.reference {
+            self.receiver = lifetime.clone();
+        }
+        visit_mut::visit_receiver_mut(self, receiver);
+    }
+
+    fn visit_type_reference_mut(&mut self, reference: &mut TypeReference) {
+        if reference.lifetime.is_none() {
+            reference.lifetime = Some(self.name());
+        }
+        visit_mut::visit_type_reference_mut(self, reference);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = self.name();
+        }
+        if lifetime.ident != "static" && !self.mentioned.contains(lifetime) {
+            self.mentioned.push(lifetime.clone());
+        }
+    }
+
+    fn visit_parenthesized_generic_arguments_mut(&mut self, _: &mut ParenthesizedGenericArguments) {
+    }
+
+    fn visit_type_bare_fn_mut(&mut self, _: &mut TypeBareFn) {}
+
+    fn visit_trait_bound_mut(&mut self, bound: &mut TraitBound) {
+        if bound.lifetimes.is_none() {
+            visit_mut::visit_trait_bound_mut(self, bound);
+        }
+    }
+}
+
+// Names the elided lifetimes of the output, e.g. `Option<&str>` into `Option<&'__logcall_0 str>`
+// with `&'__logcall_0 self`, but those of `Fn(&str)`, `fn(&str)` and `for<'a>` bounds.
+struct OutputLifetimes(Lifetime);
+
+impl VisitMut for OutputLifetimes {
+    fn visit_type_reference_mut(&mut self, reference: &mut TypeReference) {
+        if reference.lifetime.is_none() {
+            reference.lifetime = Some(self.0.clone());
+        }
+        visit_mut::visit_type_reference_mut(self, reference);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = self.0.clone();
+        }
+    }
+
+    fn visit_parenthesized_generic_arguments_mut(&mut self, _: &mut ParenthesizedGenericArguments) {
+    }
+
+    fn visit_type_bare_fn_mut(&mut self, _: &mut TypeBareFn) {}
+
+    fn visit_trait_bound_mut(&mut self, bound: &mut TraitBound) {
+        if bound.lifetimes.is_none() {
+            visit_mut::visit_trait_bound_mut(self, bound);
+        }
+    }
+}
+
+// A one-time record describing the parameters, the return type and the options of the
+// function, logged on the first call at the most severe of its levels, so that downstream
+// parsers can interpret the records that follow.
+//
+// main::add schema: params = (a: u32, b: u32), ret = u32, options = ("info", schema)
+fn gen_schema(sig: &Signature, args: &Args) -> Option<proc_macro2::TokenStream> {
+    if !args.schema {
+        return None;
+    }
+    let (params, ret) = signature_strings(sig);
+    let schema = format!(
+        "schema: params = ({}), ret = {}, options = ({})",
+        params, ret, args.options
+    );
+    let level = args
+        .levels
+        .checked()
+        .into_iter()
+        .min_by_key(|level| severity(level))
+        .unwrap();
+    let level = gen_level(&level);
+    bindings!(__fn_name);
+    let target = gen_target(args, None);
+    let log = match &args.backend {
+        Backend::Log => quote::quote!(::logcall::__private::log::log!(
+            target: #target,
+            #level,
+            "{} {}",
+            #__fn_name,
+            #schema
+        )),
+        Backend::Tracing => quote::quote!(::logcall::__event!(
+            #target,
+            #level,
+            format_args!("{} {}", #__fn_name, #schema)
+        )),
+        Backend::Slog(logger) => quote::quote!(::logcall::__slog!(
+            #logger,
+            #level,
+            format_args!("{} {}", #__fn_name, #schema)
+        )),
+        Backend::Defmt => abort_call_site!("`schema` cannot be used with `backend = \"defmt\"`"),
+        Backend::Stderr => {
+            quote::quote!(::std::eprintln!("[{}] {} {}", #level, #__fn_name, #schema))
+        }
+        Backend::Stdout => {
+            quote::quote!(::std::println!("[{}] {} {}", #level, #__fn_name, #schema))
+        }
+        Backend::Console => quote::quote!(::logcall::__console!(
+            #level,
+            format_args!("{} {}", #__fn_name, #schema)
+        )),
+        Backend::Mobile => quote::quote!(::logcall::__mobile!(
+            #target,
+            #level,
+            format_args!("{} {}", #__fn_name, #schema)
+        )),
+        Backend::Macro(path) => quote::quote!(#path!(#level, "{} {}", #__fn_name, #schema)),
+    };
+    Some(quote::quote! {
+        static __LOGCALL_SCHEMA: ::std::sync::atomic::AtomicBool = ::std::sync::atomic::AtomicBool::new(false);
+        if !__LOGCALL_SCHEMA.swap(true, ::std::sync::atomic::Ordering::Relaxed) {
+            #log;
+        }
+    })
+}
+
+// `a: u32, b: &str` and `bool`
+fn signature_strings(sig: &Signature) -> (String, String) {
+    let params: Vec<_> = sig
+        .inputs
+        .iter()
+        .map(|input| match input {
+            FnArg::Typed(PatType { pat, ty, .. }) => {
+                format!("{}: {}", tokens_string(pat), tokens_string(ty))
+            }
+            FnArg::Receiver(receiver) => tokens_string(receiver),
+        })
+        .collect();
+    let ret = match &sig.output {
+        ReturnType::Type(_, ty) => tokens_string(ty),
+        ReturnType::Default => "()".to_string(),
+    };
+    (params.join(", "), ret)
+}
+
+// The start of the call, for the duration of `record_span`, of `metrics`, of `elapsed` and of
+// the structured records. In async functions, it is taken on the first poll.
+fn gen_call_start(args: &Args) -> proc_macro2::TokenStream {
+    bindings!(__call_start);
+    if args.record_span
+        || args.metrics
+        || args.elapsed
+        || args.cpu_time
+        || args.kv()
+        || args.slow.is_some()
+        || args.elapsed_levels.is_some()
+        || args.summarize.is_some()
+        || args.accumulate.is_some()
+        || args.sink.is_some()
+    {
+        quote::quote!(let #__call_start = ::std::time::Instant::now();)
+    } else {
+        quote::quote!()
+    }
+}
+
+// Renders tokens the way they are usually written, e.g. `&'a str` rather than `& 'a str`.
+fn tokens_string(tokens: impl quote::ToTokens) -> String {
+    let mut string = tokens.to_token_stream().to_string();
+    for (from, to) in [
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" < ", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        ("& ", "&"),
+        (" ( ", "("),
+        ("( ", "("),
+        (" )", ")"),
+        ("[ ", "["),
+        (" ]", "]"),
+        (" ; ", "; "),
+    ] {
+        string = string.replace(from, to);
+    }
+    string
+}
+
+// A paragraph appended to the doc comment of the function, telling rustdoc readers at which
+// levels the function logs. Functions without a doc comment are left undocumented, so that
+// `missing_docs` still applies to them.
+fn gen_doc_note(input: &ItemFn, args: &Args) -> proc_macro2::TokenStream {
+    let has_doc = input.attrs.iter().any(|attr| attr.path.is_ident("doc"));
+    if args.undocumented || !has_doc {
+        return quote::quote!();
+    }
+    let diverging =
+        matches!(&input.sig.output, ReturnType::Type(_, ty) if matches!(**ty, Type::Never(_)));
+    let variants = |first: (&str, &Option<String>), second: (&str, &Option<String>)| {
+        let variants: Vec<_> = [first, second]
+            .into_iter()
+            .filter_map(|(variant, level)| {
+                let level = check_level(level.as_ref()?);
+                Some(format!("`{variant}` results at `{level}`"))
+            })
+            .collect();
+        variants.join(" and ")
+    };
+    let note = match &args.levels {
+        Levels::Simple(level) if diverging => format!(
+            "Logs calls on entry at `{}`, with the arguments.",
+            check_level(level)
+        ),
+        Levels::Simple(level) => format!(
+            "Logs calls at `{}`, with the arguments and the return value.",
+            check_level(level)
+        ),
+        Levels::Result {
+            ok_level,
+            err_level,
+        } => format!(
+            "Logs {}, with the arguments.",
+            variants(("Ok", ok_level), ("Err", err_level))
+        ),
+        Levels::Either {
+            left_level,
+            right_level,
+        } => format!(
+            "Logs {}, with the arguments.",
+            variants(("Left", left_level), ("Right", right_level))
+        ),
+    };
+    quote::quote!(
+        #[doc = ""]
+        #[doc = #note]
+    )
+}
+
+/// Instrument a block
+///
+/// The `lint_attrs` of the function are copied onto the statement running the original body,
+/// so that they apply to it as they would without the macro.
+fn gen_block(
+    block: &Block,
+    async_context: bool,
+    async_keyword: bool,
+    sig: &Signature,
+    lint_attrs: &[Attribute],
+    mut args: Args,
+) -> proc_macro2::TokenStream {
+    bindings!(
+        __ret_value,
+        __span,
+        __polling_guard,
+        __input_string,
+        __trace_guard,
+        __marker_guard,
+        __profile_scope,
+        __depth_guard,
+        __progress,
+        __span_guard,
+        __call_guard,
+        __call_start,
+        __cpu_time,
+        __allocs,
+        __alloc_start,
+        __poll_stats,
+        __created,
+        __first_poll_delay,
+        __in_flight,
+        __cancel_guard,
+        __stats_start,
+        __fn_name
+    );
+    let fn_name = gen_fn_name(&args);
+    let schema = gen_schema(sig, &args);
+    if let Backend::Defmt = args.backend {
+        return gen_defmt_block(block, async_context, async_keyword, sig, &args);
+    }
+    if let Some(budget) = &mut args.budget {
+        (budget.params, budget.ret) = signature_strings(sig);
+    }
+    let input_string = gen_input_string(&args, sig);
+    let captures = gen_captures(&args, sig);
+    let record = gen_call_record(block);
+    let captures = quote::quote!(#fn_name #schema #captures #record);
+
+    if let ReturnType::Type(_, ty) = &sig.output {
+        if let Type::Never(_) = &**ty {
+            return gen_diverging_block(
+                block,
+                async_context,
+                async_keyword,
+                args,
+                input_string,
+                captures,
+            );
+        }
+    }
+    if args.heartbeat.is_some() {
+        abort_call_site!("`heartbeat` can only be used on functions returning `!`");
+    }
+    if args.after_error.is_some() && !matches!(args.levels, Levels::Result { .. }) {
+        abort_call_site!("`after_error` can only be used with `ok` or `err` levels");
+    }
+    if args.journald && args.defer {
+        abort_call_site!("`journald` cannot be used with `defer`");
+    }
+    let ret_type = gen_ret_type(sig, async_context, async_keyword);
+
+    let log = match &args.levels {
+        _ if args.try_iter => gen_try_iter(block, &args, sig),
+        Levels::Simple(level) => {
+            let log = gen_log(
+                level,
+                &args,
+                "__input_string",
+                "__ret_value",
+                None,
+                None,
+                false,
+            );
+            quote::quote_spanned!(block.span()=>
+                #log;
+                #__ret_value
+            )
+        }
+        Levels::Result {
+            ok_level,
+            err_level,
+        } if args.after_error.is_some() => gen_after_error_match(block, &args, ok_level, err_level),
+        Levels::Result {
+            ok_level,
+            err_level,
+        } => {
+            let ok_arm = gen_variant_arm(
+                block,
+                &args,
+                quote::quote!(Ok),
+                ok_level.as_deref(),
+                args.ok_target.as_ref(),
+                args.ok_if.as_ref(),
+                false,
+            );
+            let err_arm = gen_variant_arm(
+                block,
+                &args,
+                quote::quote!(Err),
+                err_level.as_deref(),
+                args.err_target.as_ref(),
+                None,
+                true,
+            );
+            quote::quote_spanned!(block.span()=>
+                match #__ret_value {
+                    #ok_arm
+                    #err_arm
+                }
+            )
+        }
+        Levels::Either {
+            left_level,
+            right_level,
+        } => {
+            let left_arm = gen_variant_arm(
+                block,
+                &args,
+                quote::quote!(::either::Either::Left),
+                left_level.as_deref(),
+                None,
+                None,
+                false,
+            );
+            let right_arm = gen_variant_arm(
+                block,
+                &args,
+                quote::quote!(::either::Either::Right),
+                right_level.as_deref(),
+                None,
+                None,
+                false,
+            );
+            quote::quote_spanned!(block.span()=>
+                match #__ret_value {
+                    #left_arm
+                    #right_arm
+                }
+            )
+        }
+    };
+
+    let call_start = gen_call_start(&args);
+    // Recorded regardless of the level and the sampling of the record.
+    let metrics = args.metrics.then(|| match &args.metrics_error_label {
+        Some(error_label) => quote::quote!(
+            ::logcall::__metrics!(#__fn_name, &#__ret_value, #__call_start, #error_label);
+        ),
+        None => quote::quote!(::logcall::__metrics!(#__fn_name, &#__ret_value, #__call_start);),
+    });
+    // With the `stats` feature, every call is aggregated in the statistics of the function.
+    let stats_start = quote::quote!(::logcall::__stats_start!(#__stats_start););
+    let stats = quote::quote!(::logcall::__stats!(#__fn_name, &#__ret_value, #__stats_start););
+
+    let span = args.span.as_ref().map(|level| {
+        let level = Ident::new(&level.to_uppercase(), Span::call_site());
+        let name = sig.ident.to_string();
+        quote::quote!(::logcall::__span!(#level, #name))
+    });
+
+    if args.poll_stats && !async_context {
+        abort_call_site!("`poll_stats` can only be used on async functions");
+    }
+    if args.first_poll_delay && !async_context {
+        abort_call_site!("`first_poll_delay` can only be used on async functions");
+    }
+    if args.in_flight_warn.is_some() && !async_context {
+        abort_call_site!("`in_flight_warn` can only be used on async functions");
+    }
+    if args.cancelled.is_some() && !async_context {
+        abort_call_site!("`cancelled` can only be used on async functions");
+    }
+
+    // Generate the instrumented function body.
+    // If the function is an `async fn`, this will wrap it in an async block.
+    if async_context {
+        if args.max_depth.is_some() {
+            abort_call_site!("`max_depth` can only be used on synchronous functions");
+        }
+        if args.progress_every.is_some() {
+            abort_call_site!("`progress_every` can only be used on synchronous functions");
+        }
+        // The record is logged after the body completes, outside of `Polling`.
+        let polling_guard = if args.exec_context {
+            quote::quote!(let #__polling_guard = ::logcall::__private::PollingGuard::enter();)
+        } else {
+            quote::quote!()
+        };
+        // The call is watched from its first poll until it completes or is dropped.
+        let (in_flight, in_flight_end) = match args.in_flight_warn {
+            Some(threshold) => {
+                let threshold = gen_duration(threshold);
+                let target = gen_target(&args, None);
+                (
+                    quote::quote!(
+                        let #__in_flight = ::logcall::__private::InFlight::watch(
+                            #threshold,
+                            #target,
+                            #__fn_name,
+                            &#__input_string,
+                        );
+                    ),
+                    quote::quote!(::std::mem::drop(#__in_flight);),
+                )
+            }
+            None => (quote::quote!(), quote::quote!()),
+        };
+        // Armed from the first poll, and disarmed once the body completes.
+        let (cancel_guard, cancel_end) = match &args.cancelled {
+            Some(level) => {
+                let level = gen_level(level);
+                let target = gen_target(&args, None);
+                (
+                    quote::quote!(
+                        let #__cancel_guard = ::logcall::__private::CancelGuard::arm(
+                            #level,
+                            #target,
+                            #__fn_name,
+                            &#__input_string,
+                        );
+                    ),
+                    quote::quote!(#__cancel_guard.disarm();),
+                )
+            }
+            None => (quote::quote!(), quote::quote!()),
+        };
+        // Only the polls of the body are measured, on whichever threads they run.
+        let mut measures = Vec::new();
+        let mut body = quote::quote!(async { #block });
+        if args.allocations {
+            measures.push(quote::quote!(let #__allocs = ::logcall::__alloc_count!();));
+            body = quote::quote!(#__allocs.instrument(#body));
+        }
+        if args.cpu_time {
+            measures.push(quote::quote!(let #__cpu_time = ::logcall::__cpu_time!(polls);));
+            body = quote::quote!(#__cpu_time.instrument(#body));
+        }
+        if args.poll_stats {
+            measures
+                .push(quote::quote!(let #__poll_stats = ::logcall::__private::PollStats::new();));
+            body = quote::quote!(#__poll_stats.instrument(#body));
+        }
+        let block = quote::quote_spanned!(block.span()=>
+            #[allow(unknown_lints)]
+            #[allow(clippy::useless_format)]
+            let #__input_string = #input_string;
+            #captures
+            ::logcall::__trace_guard!(begin #__trace_guard, #__fn_name);
+            ::logcall::__marker_guard!(begin_async #__marker_guard, #__fn_name);
+            #call_start
+            #stats_start
+            #(#measures)*
+            #in_flight
+            #cancel_guard
+            #[allow(unknown_lints)]
+            #(#lint_attrs)*
+            let #__ret_value #ret_type = ::logcall::__call_guard!(
+                scope #__fn_name,
+                ::logcall::__private::Polling::new(::logcall::__profile_scope!(
+                    instrument #__fn_name,
+                    #body
+                ))
+            )
+            .await;
+            #cancel_end
+            #in_flight_end
+            ::logcall::__trace_guard!(end #__trace_guard);
+            ::logcall::__marker_guard!(end #__marker_guard);
+            #metrics
+            #stats
+            #polling_guard
+            #log
+        );
+        // The span covers every poll of the body, and the record.
+        let block = match &span {
+            Some(span) => quote::quote_spanned!(block.span()=>
+                let #__span = #span;
+                ::logcall::__private::Instrument::instrument(async move { #block }, #__span).await
+            ),
+            None => block,
+        };
+
+        if async_keyword {
+            block
+        } else if args.first_poll_delay {
+            // The future is created by the function, see `desugar_async_fn`, and first polled
+            // by the executor. The creation time is bound by a `match`, which is an expression
+            // in `Box::pin(..)` as well.
+            quote::quote_spanned!(block.span()=>
+                match ::std::time::Instant::now() {
+                    #__created => async move {
+                        let #__first_poll_delay = ::logcall::__private::FirstPollDelay(#__created.elapsed());
+                        #block
+                    },
+                }
+            )
+        } else {
+            quote::quote_spanned!(block.span()=>
+                async move {
+                    #block
+                }
+            )
+        }
+    } else {
+        let depth_guard = args.max_depth.map(|max_depth| {
+            let target = gen_target(&args, None);
+            quote::quote!(
+                ::std::thread_local! {
+                    static __LOGCALL_DEPTH: ::std::cell::Cell<usize> = const { ::std::cell::Cell::new(0) };
+                }
+                static __LOGCALL_DEPTH_REPORTED: ::std::sync::atomic::AtomicBool =
+                    ::std::sync::atomic::AtomicBool::new(false);
+                let #__depth_guard = ::logcall::__private::DepthGuard::enter(
+                    &__LOGCALL_DEPTH,
+                    &__LOGCALL_DEPTH_REPORTED,
+                    #max_depth,
+                    #target,
+                    #__fn_name,
+                    &#__input_string,
+                );
+            )
+        });
+        let progress = args.progress_every.map(|interval| {
+            let level = gen_level(progress_level(&args.levels));
+            let interval = gen_duration(interval);
+            let target = gen_target(&args, None);
+            quote::quote!(
+                let #__progress = ::logcall::__private::Progress::start(
+                    #interval,
+                    #level,
+                    #target,
+                    #__fn_name,
+                    #__input_string.to_string(),
+                );
+            )
+        });
+        let span = span.map(|span| {
+            quote::quote!(
+                let #__span = #span;
+                let #__span_guard = #__span.enter();
+            )
+        });
+        // The progress records stop before the record of the call.
+        let progress_end = progress
+            .is_some()
+            .then(|| quote::quote!(::std::mem::drop(#__progress);));
+        let body = if args.track_caller {
+            gen_inline_body(block, sig)
+        } else {
+            quote::quote!((move || #block)())
+        };
+        let cpu_time = args
+            .cpu_time
+            .then(|| quote::quote!(let #__cpu_time = ::logcall::__cpu_time!(thread);));
+        // Only the allocations of the body are counted, not those of the record.
+        let (alloc_start, alloc_end) = if args.allocations {
+            (
+                quote::quote!(
+                    let #__allocs = ::logcall::__alloc_count!();
+                    let #__alloc_start = #__allocs.snapshot();
+                ),
+                quote::quote!(#__allocs.add_since(#__alloc_start);),
+            )
+        } else {
+            (quote::quote!(), quote::quote!())
+        };
+        quote::quote_spanned!(block.span()=>
+            #span
+            #[allow(unknown_lints)]
+            #[allow(clippy::useless_format)]
+            let #__input_string = #input_string;
+            #captures
+            #depth_guard
+            #progress
+            ::logcall::__call_guard!(enter #__call_guard, #__fn_name);
+            ::logcall::__trace_guard!(begin #__trace_guard, #__fn_name);
+            ::logcall::__marker_guard!(begin #__marker_guard, #__fn_name);
+            ::logcall::__profile_scope!(enter #__profile_scope, #__fn_name);
+            #call_start
+            #stats_start
+            #cpu_time
+            #alloc_start
+            #[allow(unknown_lints)]
+            #[allow(clippy::redundant_closure_call)]
+            #[allow(clippy::let_unit_value)]
+            #[allow(unused_labels)]
+            #(#lint_attrs)*
+            let #__ret_value #ret_type = #body;
+            #alloc_end
+            ::logcall::__profile_scope!(end #__profile_scope);
+            ::logcall::__trace_guard!(end #__trace_guard);
+            ::logcall::__marker_guard!(end #__marker_guard);
+            #metrics
+            #stats
+            #progress_end
+            #log
+        )
+    }
+}
+
+// With a body that mentions `__logcall_record`, the call is bound to it as rendered in the
+// record, for the custom log lines and error messages of the body. The name is not hygienic,
+// so that the body can refer to it.
+fn gen_call_record(block: &Block) -> Option<proc_macro2::TokenStream> {
+    bindings!(__input_string, __fn_name);
+    if !mentions(quote::ToTokens::to_token_stream(block), "__logcall_record") {
+        return None;
+    }
+    let record = Ident::new("__logcall_record", Span::call_site());
+    Some(quote::quote!(
+        #[allow(unused_variables)]
+        let #record = ::logcall::CallRecord::new(#__fn_name, &#__input_string);
+    ))
+}
+
+// The literals are searched as well, for the arguments captured by format strings, e.g.
+// `format!("{__logcall_record}")`.
+fn mentions(tokens: proc_macro2::TokenStream, name: &str) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => ident == name,
+        proc_macro2::TokenTree::Literal(literal) => literal.to_string().contains(name),
+        proc_macro2::TokenTree::Group(group) => mentions(group.stream(), name),
+        _ => false,
+    })
+}
+
+// The body of a `#[track_caller]` function is run inline rather than in a closure, which
+// would become the caller seen by `Location::caller()` and the panics of the body. Its early
+// exits, i.e. `return` and `?`, are rewritten into breaks out of a labeled block, so that the
+// record is still logged. Those expanded from macros can't be rewritten.
+//
+// '__logcall_body: { let a = f()?; if a { break '__logcall_body Ok(1); } Ok(2) }
+fn gen_inline_body(block: &Block, sig: &Signature) -> proc_macro2::TokenStream {
+    // `io::Result<T>` and the like are matched by the name of the type.
+    let returns = match &sig.output {
+        ReturnType::Type(_, ty) => match &**ty {
+            Type::Path(TypePath { qself: None, path }) => match path.segments.last() {
+                Some(segment) if segment.ident == "Option" => Some(TryType::Option),
+                Some(segment) if segment.ident.to_string().ends_with("Result") => {
+                    Some(TryType::Result)
+                }
+                _ => None,
+            },
+            _ => None,
+        },
+        ReturnType::Default => None,
+    };
+    let mut block = block.clone();
+    InlineBody { returns }.visit_block_mut(&mut block);
+    let label = body_label();
+    quote::quote!(#label: #block)
+}
+
+struct InlineBody {
+    // The type whose `?` is rewritten, if the function returns an `Option` or a `Result`.
+    returns: Option<TryType>,
+}
+
+#[derive(Clone, Copy)]
+enum TryType {
+    Option,
+    Result,
+}
+
+impl VisitMut for InlineBody {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        bindings!(__value, __err);
+        let label = body_label();
+        match expr {
+            // Their `return` and `?` exit themselves, not the function.
+            Expr::Closure(_) | Expr::Async(_) => {}
+            Expr::Return(ExprReturn { expr: value, .. }) => {
+                if let Some(value) = value {
+                    self.visit_expr_mut(value);
+                }
+                let value = value
+                    .take()
+                    .map_or_else(|| parse_quote!(()), |value| *value);
+                *expr = parse_quote!(break #label #value);
+            }
+            Expr::Try(ExprTry {
+                expr: value,
+                question_token,
+                ..
+            }) => {
+                let Some(returns) = self.returns else {
+                    abort!(
+                        question_token.span(),
+                        "`?` can only be used in `#[track_caller]` functions returning `Option` or `Result`"
+                    );
+                };
+                self.visit_expr_mut(value);
+                *expr = match returns {
+                    TryType::Option => parse_quote!(match #value {
+                        ::std::option::Option::Some(#__value) => #__value,
+                        ::std::option::Option::None => break #label ::std::option::Option::None,
+                    }),
+                    TryType::Result => parse_quote!(match #value {
+                        ::std::result::Result::Ok(#__value) => #__value,
+                        ::std::result::Result::Err(#__err) => break #label ::std::result::Result::Err(::std::convert::From::from(#__err)),
+                    }),
+                };
+            }
+            _ => visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    // Nested functions have their own exits.
+    fn visit_item_mut(&mut self, _: &mut Item) {}
+}
+
+// The body with `backend = "defmt"`, for `no_std` firmware. The record is logged with `defmt`,
+// whose deferred formatting needs neither `format!` nor an allocator, and none of the runtime
+// of `logcall` is used.
+//
+// The arguments are logged after the call, so the body borrows them instead of taking them by
+// value, and arguments it moves can't be logged.
+//
+// defmt::info!("{=str}(a = {=?}, b = {=?}) => {=?}", fn_name, a, b, __ret_value)
+// defmt::error!("{=str}(a = {=?}, b = {=?}) => Err({=?})", fn_name, a, b, __value)
+fn gen_defmt_block(
+    block: &Block,
+    async_context: bool,
+    async_keyword: bool,
+    sig: &Signature,
+    args: &Args,
+) -> proc_macro2::TokenStream {
+    bindings!(__ret_value, __value, __fn_name);
+    if let Some(name) = args.unsupported_with_defmt() {
+        abort_call_site!("`{}` cannot be used with `backend = \"defmt\"`", name);
+    }
+    if let ReturnType::Type(_, ty) = &sig.output {
+        if let Type::Never(_) = &**ty {
+            abort_call_site!("`backend = \"defmt\"` cannot be used on functions returning `!`");
+        }
+    }
+
+    let mut format = "{=str}(".to_string();
+    let mut input_args = Vec::new();
+    for (i, input) in sig.inputs.iter().enumerate() {
+        if i > 0 {
+            format.push_str(", ");
+        }
+        match input {
+            FnArg::Typed(PatType { pat, .. }) => {
+                if let Pat::Ident(pat_ident) = &**pat {
+                    let ident = &pat_ident.ident;
+                    format.push_str(&format!("{ident} = {{=?}}"));
+                    input_args.push(ident);
+                }
+            }
+            FnArg::Receiver(_) => {
+                format.push_str("self");
+            }
+        }
+    }
+    format.push_str(") => ");
+    let gen_log = |level: &str, ret: &str, value: &Ident| {
+        let level = Ident::new(&check_level(level), Span::call_site());
+        let format = format!("{format}{ret}");
+        quote::quote!(::defmt::#level!(#format, #__fn_name, #(#input_args,)* #value))
+    };
+    // The value of the variant is logged, rather than the return value, as `Either` doesn't
+    // implement `defmt::Format`.
+    let gen_arm =
+        |variant: proc_macro2::TokenStream, name: &str, level: &Option<String>| match level {
+            Some(level) => {
+                let log = gen_log(level, &format!("{name}({{=?}})"), &__value);
+                quote::quote!(#variant(#__value) => { #log })
+            }
+            None => quote::quote!(#variant(_) => {}),
+        };
+    let log = match &args.levels {
+        Levels::Simple(level) => gen_log(level, "{=?}", &__ret_value),
+        Levels::Result {
+            ok_level,
+            err_level,
+        } => {
+            let ok_arm = gen_arm(quote::quote!(Ok), "Ok", ok_level);
+            let err_arm = gen_arm(quote::quote!(Err), "Err", err_level);
+            quote::quote!(match &#__ret_value { #ok_arm #err_arm })
+        }
+        Levels::Either {
+            left_level,
+            right_level,
+        } => {
+            let left_arm = gen_arm(quote::quote!(::either::Either::Left), "Left", left_level);
+            let right_arm = gen_arm(quote::quote!(::either::Either::Right), "Right", right_level);
+            quote::quote!(match &#__ret_value { #left_arm #right_arm })
+        }
+    };
+
+    let ret_type = gen_ret_type(sig, async_context, async_keyword);
+    let call = if async_context {
+        quote::quote_spanned!(block.span()=> async #block.await)
+    } else {
+        quote::quote_spanned!(block.span()=> (|| #block)())
+    };
+    let fn_name = gen_fn_name(args);
+    let block = quote::quote_spanned!(block.span()=>
+        #fn_name
+        #[allow(unknown_lints)]
+        #[allow(clippy::redundant_closure_call)]
+        let #__ret_value #ret_type = #call;
+        #log;
+        #__ret_value
+    );
+    if async_context && !async_keyword {
+        quote::quote!(
+            async move {
+                #block
+            }
+        )
+    } else {
+        block
+    }
+}
+
+/// Annotate the return value with the declared return type, so that it is fully inferred
+/// before it is inspected by the log statement.
+///
+/// `impl Trait` can't be named in a `let` binding, and the `Future` returned by an
+/// `async-trait` method is not the type of the inner block, so those are left to inference.
+fn gen_ret_type(
+    sig: &Signature,
+    async_context: bool,
+    async_keyword: bool,
+) -> proc_macro2::TokenStream {
+    // An `async fn` desugared for `first_poll_delay` still returns the output of its body.
+    match &sig.output {
+        ReturnType::Type(_, ty) if !async_context || async_keyword || sig.asyncness.is_some() => {
+            let is_impl_trait = quote::quote!(#ty)
+                .to_string()
+                .split_whitespace()
+                .any(|token| token == "impl");
+            if is_impl_trait {
+                quote::quote!()
+            } else {
+                quote::quote!(: #ty)
+            }
+        }
+        _ => quote::quote!(),
+    }
+}
+
+// The match arm of a single-field variant, such as `Ok` or `Either::Left`, logging the return
+// value at `level` to `target` if any, and passing it through otherwise.
+fn gen_variant_arm(
+    block: &Block,
+    args: &Args,
+    variant: proc_macro2::TokenStream,
+    level: Option<&str>,
+    target: Option<&LitStr>,
+    filter: Option<&Expr>,
+    is_err: bool,
+) -> proc_macro2::TokenStream {
+    bindings!(__ret_value);
+    if let Some(level) = level {
+        let log = gen_log(
+            level,
+            args,
+            "__input_string",
+            "__ret_value",
+            None,
+            target,
+            is_err,
+        );
+        // The variant is only logged if the predicate holds for its value, bound to `ret`.
+        let log = match filter {
+            Some(filter) => quote::quote!(
+                if match &#__ret_value {
+                    #variant(ret) => #filter,
+                    _ => true,
+                } {
+                    #log;
+                }
+            ),
+            None => log,
+        };
+        quote::quote_spanned!(block.span()=>
+            #__ret_value@#variant(_) => {
+                #log;
+                #__ret_value
+            }
+        )
+    } else {
+        quote::quote_spanned!(block.span()=>
+            #variant(#__ret_value) => #variant(#__ret_value),
+        )
+    }
+}
+
+// Wraps the returned iterator of `Result` items, which logs the first `Err` item, or the
+// number of `Ok` items once it is exhausted.
+fn gen_try_iter(block: &Block, args: &Args, sig: &Signature) -> proc_macro2::TokenStream {
+    bindings!(__ret_value, __input_string, __fn_name);
+    let returns_iterator = match &sig.output {
+        ReturnType::Type(_, ty) => match &**ty {
+            Type::ImplTrait(TypeImplTrait { bounds, .. }) => bounds.iter().any(|bound| {
+                matches!(bound, TypeParamBound::Trait(TraitBound { path, .. })
+                    if path.segments.last().is_some_and(|segment| segment.ident == "Iterator"))
+            }),
+            _ => false,
+        },
+        ReturnType::Default => false,
+    };
+    if !returns_iterator {
+        abort_call_site!("`try_iter` can only be used on functions returning `impl Iterator`");
+    }
+    if args.defer
+        || args.dedup.is_some()
+        || args.journald
+        || args.accumulate.is_some()
+        || args.record_span
+        || args.after_error.is_some()
+        || args.sample.is_some()
+        || args.audit
+    {
+        abort_call_site!(
+            "`try_iter` cannot be used with `defer`, `dedup`, `journald`, `accumulate`, `record_span`, `after_error`, `sample_by` or `audit`"
+        );
+    }
+    let (ok_level, err_level) = match &args.levels {
+        Levels::Simple(level) => (Some(level), Some(level)),
+        Levels::Result {
+            ok_level,
+            err_level,
+        } => (ok_level.as_ref(), err_level.as_ref()),
+        Levels::Either { .. } => {
+            abort_call_site!("`left` or `right` levels cannot be used with `try_iter`")
+        }
+    };
+    let gen_level = |level: Option<&String>| match level {
+        Some(level) => {
+            let level = gen_level(level);
+            quote::quote!(::std::option::Option::Some(#level))
+        }
+        None => quote::quote!(::std::option::Option::None),
+    };
+    let ok_level = gen_level(ok_level);
+    let err_level = gen_level(err_level);
+    let ok_target = gen_target(args, args.ok_target.as_ref());
+    let err_target = gen_target(args, args.err_target.as_ref());
+    quote::quote_spanned!(block.span()=>
+        ::logcall::__private::TryIter::new(
+            #__ret_value,
+            #ok_level,
+            #err_level,
+            #ok_target,
+            #err_target,
+            #__fn_name,
+            #__input_string.to_string(),
+        )
+    )
+}
+
+// The match on a `Result` with `after_error`: an `Err` opens a window over the next
+// invocations, whose `Ok` records are boosted, or logged at the boosted level if `Ok` is not
+// logged otherwise.
+fn gen_after_error_match(
+    block: &Block,
+    args: &Args,
+    ok_level: &Option<String>,
+    err_level: &Option<String>,
+) -> proc_macro2::TokenStream {
+    bindings!(__ret_value);
+    let after_error = args.after_error.as_ref().unwrap();
+    let ok_log = match ok_level {
+        Some(level) => gen_log(
+            level,
+            args,
+            "__input_string",
+            "__ret_value",
+            Some(&after_error.level),
+            args.ok_target.as_ref(),
+            false,
+        ),
+        None => {
+            let log = gen_log(
+                &after_error.level,
+                args,
+                "__input_string",
+                "__ret_value",
+                None,
+                args.ok_target.as_ref(),
+                false,
+            );
+            quote::quote!(
+                if __LOGCALL_AFTER_ERROR.boost() {
+                    #log
+                }
+            )
+        }
+    };
+    let err_log = err_level.as_deref().map(|level| {
+        let log = gen_log(
+            level,
+            args,
+            "__input_string",
+            "__ret_value",
+            None,
+            args.err_target.as_ref(),
+            true,
+        );
+        quote::quote!(#log;)
+    });
+    let count = after_error.count;
+    quote::quote_spanned!(block.span()=>
+        static __LOGCALL_AFTER_ERROR: ::logcall::__private::AfterError =
+            ::logcall::__private::AfterError::new();
+        match #__ret_value {
+            #__ret_value@Ok(_) => {
+                #ok_log;
+                #__ret_value
+            }
+            #__ret_value@Err(_) => {
+                __LOGCALL_AFTER_ERROR.trigger(#count);
+                #err_log
+                #__ret_value
+            }
+        }
+    )
+}
+
+/// Instrument the block of a function returning `!`
+///
+/// The function never returns, so the call is logged on entry instead, optionally followed
+/// by periodic heartbeats while it is running.
+fn gen_diverging_block(
+    block: &Block,
+    async_context: bool,
+    async_keyword: bool,
+    args: Args,
+    input_string: proc_macro2::TokenStream,
+    captures: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    bindings!(__ret_value, __input_string, __fn_name);
+    if args.timing {
+        abort_call_site!("`only = \"timing\"` cannot be used on functions returning `!`");
+    }
+    let level = match &args.levels {
+        Levels::Simple(level) => level,
+        Levels::Result { .. } => {
+            abort_call_site!("`ok` or `err` levels cannot be used on functions returning `!`")
+        }
+        Levels::Either { .. } => {
+            abort_call_site!("`left` or `right` levels cannot be used on functions returning `!`")
+        }
+    };
+    if args.record_span {
+        abort_call_site!("`record_span` cannot be used on functions returning `!`");
+    }
+    if args.span.is_some() {
+        abort_call_site!("`span` cannot be used on functions returning `!`");
+    }
+    if args.progress_every.is_some() {
+        abort_call_site!(
+            "`progress_every` cannot be used on functions returning `!`, use `heartbeat`"
+        );
+    }
+    if args.in_flight_warn.is_some() {
+        abort_call_site!(
+            "`in_flight_warn` cannot be used on functions returning `!`, use `heartbeat`"
+        );
+    }
+    if args.cancelled.is_some() {
+        abort_call_site!("`cancelled` cannot be used on functions returning `!`");
+    }
+    if args.metrics {
+        abort_call_site!("`metrics` cannot be used on functions returning `!`");
+    }
+    if args.elapsed {
+        abort_call_site!("`elapsed` cannot be used on functions returning `!`");
+    }
+    if args.slow.is_some() {
+        abort_call_site!("`slow` cannot be used on functions returning `!`");
+    }
+    if args.elapsed_levels.is_some() {
+        abort_call_site!("`elapsed_levels` cannot be used on functions returning `!`");
+    }
+    if args.summarize.is_some() {
+        abort_call_site!("`summarize` cannot be used on functions returning `!`");
+    }
+    if args.cpu_time {
+        abort_call_site!("`cpu_time` cannot be used on functions returning `!`");
+    }
+    if args.allocations {
+        abort_call_site!("`allocations` cannot be used on functions returning `!`");
+    }
+    if args.poll_stats {
+        abort_call_site!("`poll_stats` cannot be used on functions returning `!`");
+    }
+    if args.first_poll_delay {
+        abort_call_site!("`first_poll_delay` cannot be used on functions returning `!`");
+    }
+    let log = gen_log(
+        level,
+        &args,
+        "__input_string",
+        "__ret_value",
+        None,
+        None,
+        false,
+    );
+    let call_start = gen_call_start(&args);
+    let heartbeat = args.heartbeat.map(|interval| {
+        let level = gen_level(level);
+        let interval = gen_duration(interval);
+        let target = gen_target(&args, None);
+        quote::quote!(
+            ::logcall::__private::heartbeat(
+                #interval,
+                #level,
+                #target,
+                #__fn_name,
+                #__input_string.to_string(),
+            );
+        )
+    });
+
+    let block = quote::quote_spanned!(block.span()=>
+        #[allow(unknown_lints)]
+        #[allow(clippy::useless_format)]
+        let #__input_string = #input_string;
+        #captures
+        #call_start
+        {
+            let #__ret_value = ::logcall::__private::Diverging;
+            #log;
+        }
+        #heartbeat
+        #block
+    );
+
+    if async_context && !async_keyword {
+        quote::quote_spanned!(block.span()=>
+            async move {
+                #block
+            }
+        )
+    } else {
+        block
+    }
+}
+
+// With `boost`, the level is decided at runtime by the `after_error` state of the function.
+// With `slow`, it is escalated at runtime for the calls lasting at least the threshold, and with
+// `elapsed_levels` for the calls lasting at least each threshold of the ladder.
+// With `test_level`, the record is logged at that level instead when compiled with
+// `cfg(test)`. The record goes to `target` if any, and to the module path of the function
+// otherwise.
+fn gen_log(
+    level: &str,
+    args: &Args,
+    input_string: &str,
+    return_value: &str,
+    boost: Option<&str>,
+    target: Option<&LitStr>,
+    is_err: bool,
+) -> proc_macro2::TokenStream {
+    bindings!(
+        __call_start,
+        __cpu_time,
+        __allocs,
+        __poll_stats,
+        __first_poll_delay,
+        __level,
+        __slog_logger,
+        __journal_fields,
+        __kv_fields,
+        __console_fields,
+        __valuable_args,
+        __message,
+        __sampled,
+        __fn_name
+    );
+    if let Some(test_level) = &args.test_level {
+        let args = Args {
+            test_level: None,
+            ..args.clone()
+        };
+        let test_log = gen_log(
+            test_level,
+            &args,
+            input_string,
+            return_value,
+            boost,
+            target,
+            is_err,
+        );
+        let log = gen_log(
+            level,
+            &args,
+            input_string,
+            return_value,
+            boost,
+            target,
+            is_err,
+        );
+        return quote::quote!({
+            #[cfg(test)]
+            {
+                #test_log
+            }
+            #[cfg(not(test))]
+            {
+                #log
+            }
+        });
+    }
+
+    let level = check_level(level);
+    let level_variant: Ident = Ident::new(&capitalize(&level), Span::call_site());
+    let level_value = quote::quote!(#__level);
+    let target = gen_target(args, target);
+    let input_string = binding(input_string);
+    let return_value = binding(return_value);
+    let rendered_value = if args.timing {
+        quote::quote!(())
+    } else if args.outcome_only {
+        quote::quote!(::logcall::__private::Outcome(&#return_value))
+    } else {
+        quote::quote!({
+            #[allow(unused_imports)]
+            use ::logcall::__private::{NoReportKind as _, ReportKind as _};
+            (&&::logcall::__private::ReportProbe(&#return_value)).render_report()
+        })
+    };
+    let redacted = args.ret_redact.iter().map(arg_name);
+    let redacted = quote::quote!(&[#(#redacted),*]);
+    let rendered_value = if args.ret_redact.is_empty() {
+        rendered_value
+    } else {
+        quote::quote!(::logcall::__private::Redact(&#rendered_value, #redacted))
+    };
+    let return_value_ref = match args.heapless {
+        Some(size) => quote::quote!(::logcall::__private::Truncate::<_, #size>(&#rendered_value)),
+        None => quote::quote!(&#rendered_value),
+    };
+    let io_error_fields = quote::quote!({
+        #[allow(unused_imports)]
+        use ::logcall::__private::{IoErrorFieldsKind as _, NoIoErrorFieldsKind as _};
+        (&&::logcall::__private::IoErrorProbe(&#return_value)).io_error_fields()
+    });
+    let fingerprint = if args.err_fingerprint {
+        quote::quote!(::logcall::__private::Fingerprint(&#return_value))
+    } else {
+        quote::quote!("")
+    };
+    let unsafe_tag = if args.unsafe_tag {
+        quote::quote!(" unsafe=true")
+    } else {
+        quote::quote!("")
+    };
+    // A constant resolved at the definition of the function, rather than at the expansion of
+    // the record.
+    let location = match args.location {
+        Some(span) => quote::quote_spanned!(span=> concat!(" at=", file!(), ":", line!())),
+        None => quote::quote!(""),
+    };
+    let timestamp = if args.timestamp {
+        quote::quote!(::logcall::__private::Timestamp::now())
+    } else {
+        quote::quote!("")
+    };
+    // The measurements of the call: the wall time, followed by the CPU time with `cpu_time`, the
+    // allocations, and the polls of async functions and the delay of the first one.
+    let mut measures = Vec::new();
+    if args.elapsed || args.cpu_time {
+        measures.push(quote::quote!(::logcall::__private::Elapsed(#__call_start.elapsed())));
+    }
+    if args.cpu_time {
+        measures.push(quote::quote!(#__cpu_time));
+    }
+    if args.allocations {
+        measures.push(quote::quote!(#__allocs));
+    }
+    if args.poll_stats {
+        measures.push(quote::quote!(#__poll_stats));
+    }
+    if args.first_poll_delay {
+        measures.push(quote::quote!(#__first_poll_delay));
+    }
+    let measures = if measures.is_empty() {
+        quote::quote!("")
+    } else {
+        let format = "{}".repeat(measures.len());
+        quote::quote!(format_args!(#format, #(#measures),*))
+    };
+    let exec_context = if args.exec_context {
+        quote::quote!(::logcall::__private::ExecContext)
+    } else {
+        quote::quote!("")
+    };
+    // The arguments rendered in the message, summarized at the levels of `detail`.
+    let message_input = if args.detail.is_empty() {
+        quote::quote!(#input_string)
+    } else {
+        let detail = args.detail.iter().map(|(level, summary)| {
+            let level = gen_level(level);
+            quote::quote!((#level, #summary))
+        });
+        quote::quote!(::logcall::__private::Detail(&#input_string, #level_value, &[#(#detail),*]))
+    };
+    let message = if args.timing {
+        quote::quote!(
+            format_args!("{}{}{}{}{}{}{}{}", #__fn_name, #io_error_fields, #unsafe_tag, #location, #timestamp, #measures, #exec_context, ::logcall::__private::ContextFields)
+        )
+    } else if args.json {
+        quote::quote!(format_args!(
+            "{}",
+            ::logcall::__json_record!(
+                #__fn_name,
+                &#input_string,
+                #return_value,
+                #redacted,
+                format_args!("{}{}{}{}{}{}{}{}", #io_error_fields, #fingerprint, #unsafe_tag, #location, #timestamp, #measures, #exec_context, ::logcall::__private::ContextFields)
+            )
+        ))
+    } else if let Some(Budget { size, params, ret }) = &args.budget {
+        quote::quote!(
+            format_args!("{}{}{}{}{}{}{}{}{}{}", #__fn_name, ::logcall::__private::Budget {
+                args: &#message_input,
+                ret: &#return_value_ref,
+                size: #size,
+                params: #params,
+                ret_type: #ret,
+            }, #io_error_fields, #fingerprint, #unsafe_tag, #location, #timestamp, #measures, #exec_context, ::logcall::__private::ContextFields)
+        )
+    } else {
+        quote::quote!(
+            format_args!("{}({}) => {:?}{}{}{}{}{}{}{}{}", #__fn_name, #message_input, #return_value_ref, #io_error_fields, #fingerprint, #unsafe_tag, #location, #timestamp, #measures, #exec_context, ::logcall::__private::ContextFields)
+        )
+    };
+    let kv_message = quote::quote!(
+        format_args!("call{}{}{}{}{}{}{}{}", #io_error_fields, #fingerprint, #unsafe_tag, #location, #timestamp, #measures, #exec_context, ::logcall::__private::ContextFields)
+    );
+    let message = if args.audit {
+        quote::quote!(format_args!("{}", ::logcall::audit::__Signed(#message)))
+    } else {
+        message
+    };
+    let enabled = match args.backend {
+        // The user macro is assumed to wrap `log`.
+        Backend::Log | Backend::Macro(_) => {
+            quote::quote!(::logcall::__private::log::log_enabled!(target: #target, #level_value))
+        }
+        Backend::Tracing => quote::quote!(::logcall::__event_enabled!(#target, #level_value)),
+        Backend::Slog(_) => quote::quote!(::logcall::__slog_enabled!(#__slog_logger, #level_value)),
+        Backend::Defmt => unreachable!("the records of `defmt` are generated by `gen_defmt_block`"),
+        // Printed regardless of the logger, which is likely not installed.
+        Backend::Stderr | Backend::Stdout | Backend::Console | Backend::Mobile => {
+            quote::quote!(true)
+        }
+    };
+    let emit = |message: proc_macro2::TokenStream| {
+        let message = quote::quote!(::logcall::__count_bytes!(#message));
+        if args.valuable() {
+            // The shared references are probed themselves, as `valuable` implements `Valuable`
+            // for `&[T]` and `&str`, but not for `[T]` and `str`.
+            let fields = args
+                .valuable_args
+                .iter()
+                .enumerate()
+                .map(|(i, (ident, _))| {
+                    let i = syn::Index::from(i);
+                    quote::quote!(#ident = ::logcall::__valuable!(&#__valuable_args.#i))
+                });
+            let ret = if args.outcome_only || !args.ret_redact.is_empty() || args.heapless.is_some()
+            {
+                quote::quote!(#return_value_ref)
+            } else {
+                quote::quote!(&#return_value)
+            };
+            quote::quote!(::logcall::__event!(#target, #level_value, #message, {
+                #(#fields,)*
+                ret = ::logcall::__valuable!(#ret)
+            }))
+        } else if let Backend::Tracing = args.backend {
+            quote::quote!(::logcall::__event!(#target, #level_value, #message))
+        } else if let Backend::Slog(_) = args.backend {
+            quote::quote!(::logcall::__slog!(#__slog_logger, #level_value, #message))
+        } else if let Backend::Stderr = args.backend {
+            quote::quote!(::std::eprintln!("[{}] {}", #level_value, #message))
+        } else if let Backend::Stdout = args.backend {
+            quote::quote!(::std::println!("[{}] {}", #level_value, #message))
+        } else if args.console() {
+            quote::quote!(::logcall::__console!(
+                #level_value,
+                #__fn_name,
+                &#__console_fields,
+                &#return_value_ref,
+                format_args!("{}{}{}{}{}{}{}{}", #io_error_fields, #fingerprint, #unsafe_tag, #location, #timestamp, #measures, #exec_context, ::logcall::__private::ContextFields)
+            ))
+        } else if let Backend::Console = args.backend {
+            quote::quote!(::logcall::__console!(#level_value, #message))
+        } else if let Backend::Mobile = args.backend {
+            quote::quote!(::logcall::__mobile!(#target, #level_value, #message))
+        } else if let Backend::Macro(path) = &args.backend {
+            quote::quote!(#path!(#level_value, "{}", #message))
+        } else if args.defer {
+            quote::quote!(
+                if ::logcall::__private::log::log_enabled!(target: #target, #level_value) {
+                    ::logcall::__private::defer(#level_value, #target, #message)
+                }
+            )
+        } else if args.journald {
+            quote::quote!(
+                if ::logcall::__private::log::log_enabled!(target: #target, #level_value) {
+                    ::logcall::__journald!(
+                        #level_value,
+                        #target,
+                        #__fn_name,
+                        &#__journal_fields,
+                        &#input_string,
+                        &#return_value_ref,
+                        {
+                            #[allow(unused_imports)]
+                            use ::logcall::__private::{NoOutcomeKind as _, ResultOutcomeKind as _};
+                            (&&::logcall::__private::OutcomeProbe(&#return_value)).outcome()
+                        },
+                        #message,
+                    )
+                }
+            )
+        } else if args.kv() {
+            let kv_message = quote::quote!(::logcall::__count_bytes!(#kv_message));
+            quote::quote!(
+                if ::logcall::__private::log::log_enabled!(target: #target, #level_value) {
+                    ::logcall::__private::log_kv(
+                        #level_value,
+                        #target,
+                        #__fn_name,
+                        &#__kv_fields,
+                        &#return_value_ref,
+                        {
+                            #[allow(unused_imports)]
+                            use ::logcall::__private::{NoOutcomeKind as _, ResultOutcomeKind as _};
+                            (&&::logcall::__private::OutcomeProbe(&#return_value)).outcome()
+                        },
+                        #__call_start,
+                        #kv_message,
+                    )
+                }
+            )
+        } else {
+            quote::quote!(
+                ::logcall::__private::log::log!(target: #target, #level_value, "{}", #message)
+            )
+        }
+    };
+    let log = match args.dedup {
+        Some(window) => {
+            let window = gen_duration(window);
+            let emit = emit(quote::quote!(format_args!("{}", #__message)));
+            // The summaries may be emitted by the flusher thread once the call is over, so they
+            // only carry the message, without the fields borrowed from the call.
+            let summary = quote::quote!(::logcall::__count_bytes!(format_args!("{}", #__message)));
+            let summary = match &args.backend {
+                Backend::Tracing => quote::quote!(::logcall::__event!(#target, #__level, #summary)),
+                Backend::Slog(_) => {
+                    quote::quote!(::logcall::__slog!(#__slog_logger, #__level, #summary))
+                }
+                Backend::Stderr => quote::quote!(::std::eprintln!("[{}] {}", #__level, #summary)),
+                Backend::Stdout => quote::quote!(::std::println!("[{}] {}", #__level, #summary)),
+                Backend::Console => quote::quote!(::logcall::__console!(#__level, #summary)),
+                Backend::Mobile => quote::quote!(::logcall::__mobile!(#target, #__level, #summary)),
+                Backend::Macro(path) => quote::quote!(#path!(#__level, "{}", #summary)),
+                Backend::Log => quote::quote!(
+                    ::logcall::__private::log::log!(target: #target, #__level, "{}", #summary)
+                ),
+                Backend::Defmt => {
+                    unreachable!("the records of `defmt` are generated by `gen_defmt_block`")
+                }
+            };
+            let slog_logger = match &args.backend {
+                Backend::Slog(_) => Some(
+                    quote::quote!(let #__slog_logger = ::std::clone::Clone::clone(&#__slog_logger);),
+                ),
+                _ => None,
+            };
+            quote::quote!(
+                if #enabled {
+                    static __LOGCALL_DEDUP: ::logcall::__private::Dedup = ::logcall::__private::Dedup::new(#window);
+                    let #__message = __LOGCALL_DEDUP.record(#message, || -> ::logcall::__private::DedupSummary {
+                        let #__level = #__level;
+                        #slog_logger
+                        ::std::boxed::Box::new(move |#__message: &str| #summary)
+                    });
+                    if let ::std::option::Option::Some(#__message) = #__message {
+                        #emit;
+                    }
+                }
+            )
+        }
+        None => emit(message),
+    };
+    let log = if args.record_span {
+        quote::quote!(
+            if !::logcall::__private::record_span(&#return_value_ref, #__call_start) {
+                #log
+            }
+        )
+    } else {
+        log
+    };
+    let log = match &args.sink {
+        None => log,
+        Some(Sink { sink, mode }) => {
+            let sink = quote::quote!(
+                ::logcall::__private::sink(
+                    &#sink,
+                    #level_value,
+                    #target,
+                    module_path!(),
+                    #__fn_name,
+                    &#input_string,
+                    &#return_value,
+                    #__call_start,
+                )
+            );
+            match mode {
+                Accumulate::Only => quote::quote!(#sink),
+                Accumulate::Both => quote::quote!({
+                    #sink;
+                    #log
+                }),
+            }
+        }
+    };
+    let push_record = quote::quote!(
+        ::logcall::__private::push_record(
+            #level_value,
+            #target,
+            module_path!(),
+            #__fn_name,
+            &#input_string,
+            &#return_value,
+            #__call_start,
+        )
+    );
+    let log = match args.accumulate {
+        None => log,
+        Some(Accumulate::Only) => quote::quote!(
+            if !#push_record {
+                #log
+            }
+        ),
+        Some(Accumulate::Both) => quote::quote!({
+            #push_record;
+            #log
+        }),
+    };
+    // Only the enabled records count towards the rate of `auto_mute`.
+    let log = match &args.auto_mute {
+        Some(AutoMute {
+            threshold,
+            window,
+            cooldown,
+            rate,
+            cooldown_str,
+        }) => {
+            let window = gen_duration(*window);
+            let cooldown = gen_duration(*cooldown);
+            quote::quote!(
+                if !#enabled
+                    || __LOGCALL_AUTO_MUTE.admit(
+                        #threshold,
+                        #window,
+                        #cooldown,
+                        #target,
+                        #__fn_name,
+                        #rate,
+                        #cooldown_str,
+                    )
+                {
+                    #log
+                }
+            )
+        }
+        None => log,
+    };
+    let log = match args.summarize {
+        Some(interval) => {
+            let interval = gen_duration(interval);
+            quote::quote!(
+                __LOGCALL_SUMMARY.record(
+                    #interval,
+                    #level_value,
+                    #target,
+                    #__fn_name,
+                    {
+                        #[allow(unused_imports)]
+                        use ::logcall::__private::{NoOutcomeKind as _, ResultOutcomeKind as _};
+                        (&&::logcall::__private::OutcomeProbe(&#return_value)).outcome()
+                    },
+                    #__call_start,
+                )
+            )
+        }
+        None => log,
+    };
+    // The overrides of `LOGCALL_LEVEL` and `set_level_override` replace the level of the
+    // attribute, except that of the `Err` records, and the escalations below still apply.
+    // `LOGCALL_LEVEL` is read when the function is compiled, so that cargo rebuilds it when the
+    // variable changes.
+    let level = if is_err {
+        quote::quote!(::logcall::__private::log::Level::#level_variant)
+    } else {
+        quote::quote!({
+            static __LOGCALL_ENV_LEVEL: ::logcall::__private::EnvLevel =
+                ::logcall::__private::EnvLevel::new();
+            ::logcall::__private::resolve_level(
+                ::logcall::__private::log::Level::#level_variant,
+                &__LOGCALL_ENV_LEVEL,
+                ::core::option_env!("LOGCALL_LEVEL"),
+                || #__fn_name,
+            )
+        })
+    };
+    let level = match boost {
+        Some(boost) => {
+            let boost = gen_level(boost);
+            quote::quote!(__LOGCALL_AFTER_ERROR.level(#level, #boost))
+        }
+        None => level,
+    };
+    let level = match &args.slow {
+        Some(slow) => {
+            let slow_level = gen_level(&slow.level);
+            let threshold = gen_duration(slow.threshold);
+            quote::quote!(::logcall::__private::slow_level(#level, #slow_level, #__call_start, #threshold))
+        }
+        None => level,
+    };
+    let level = match &args.elapsed_levels {
+        Some(elapsed_levels) => {
+            let rungs = elapsed_levels.rungs.iter().map(|(threshold, level)| {
+                let threshold = gen_duration(*threshold);
+                let level = gen_level(level);
+                quote::quote!((#threshold, #level))
+            });
+            quote::quote!(::logcall::__private::elapsed_level(#level, #__call_start, &[#(#rungs),*]))
+        }
+        None => level,
+    };
+    let log = quote::quote!({
+        let #__level = #level;
+        // With the `sentry` feature, the call is also recorded as a breadcrumb at that level.
+        ::logcall::__breadcrumb!(#__level, #__fn_name, &#input_string, &#return_value);
+        #log
+    });
+    // With the `fastrace` and `otel` features, the call is also recorded in the local parent
+    // span and in the active OpenTelemetry span respectively. The return value is rendered as in
+    // the record, so `format = "json"` doesn't require `Debug` either.
+    let event_ret = if args.json {
+        quote::quote!(::logcall::__json_ret!(#return_value, #redacted))
+    } else {
+        return_value_ref
+    };
+    let log = quote::quote!({
+        ::logcall::__local_span!(#__fn_name, &#input_string, &#event_ret);
+        ::logcall::__otel_event!(#__fn_name, &#input_string, &#event_ret, &#return_value);
+        #log
+    });
+    let log = match &args.slow {
+        Some(Slow {
+            threshold,
+            only: true,
+            ..
+        }) => {
+            let threshold = gen_duration(*threshold);
+            quote::quote!(
+                if #__call_start.elapsed() >= #threshold {
+                    #log
+                }
+            )
+        }
+        _ => log,
+    };
+    let log = match &args.elapsed_levels {
+        Some(ElapsedLevels { rungs, only: true }) => {
+            let threshold = gen_duration(rungs[0].0);
+            quote::quote!(
+                if #__call_start.elapsed() >= #threshold {
+                    #log
+                }
+            )
+        }
+        _ => log,
+    };
+    let log = if args.sample.is_some() {
+        quote::quote!(
+            if #__sampled {
+                #log
+            }
+        )
+    } else {
+        log
+    };
+    if args.catch_panic {
+        quote::quote!(::logcall::__private::catch_log(#target, #__fn_name, || #log))
+    } else {
+        log
+    }
+}
+
+// A local variable of the expansion. It resolves at the definition site of the macro, like the
+// variables of `macro_rules!`, so it neither shadows nor is shadowed by the variables of the
+// instrumented function, e.g. an argument of the same name.
+fn binding(name: &str) -> Ident {
+    Ident::new(name, Span::mixed_site())
+}
+
+// The label of the inlined body of a `#[track_caller]` function, see `binding`.
+fn body_label() -> Lifetime {
+    Lifetime::new("'__logcall_body", Span::mixed_site())
+}
+
+// The target of a record: `ok_target` or `err_target` if any, then `target`, and the module path
+// of the function otherwise.
+fn gen_target(args: &Args, target: Option<&LitStr>) -> proc_macro2::TokenStream {
+    match target.or(args.target.as_ref()) {
+        Some(target) => quote::quote!(#target),
+        None => quote::quote!(module_path!()),
+    }
+}
+
+// The level of the progress records of `progress_every`: the least severe of the levels, as
+// the call may still succeed.
+fn progress_level(levels: &Levels) -> &str {
+    let (first, second) = match levels {
+        Levels::Simple(level) => return level,
+        Levels::Result {
+            ok_level,
+            err_level,
+        } => (ok_level, err_level),
+        Levels::Either {
+            left_level,
+            right_level,
+        } => (left_level, right_level),
+    };
+    first
+        .iter()
+        .chain(second)
+        .max_by_key(|level| severity(&check_level(level)))
+        .unwrap()
+}
+
+// 0 for `error`, the most severe level, up to 4 for `trace`.
+fn severity(level: &str) -> usize {
+    ["error", "warn", "info", "debug", "trace"]
+        .iter()
+        .position(|severity| *severity == level)
+        .unwrap()
+}
+
+fn check_level(level: &str) -> String {
+    let level = level.to_lowercase();
+    if !["error", "warn", "info", "debug", "trace"].contains(&level.as_str()) {
+        abort_call_site!("unknown log level");
+    }
+    level
+}
+
+// ::logcall::__private::log::Level::Info
+fn gen_level(level: &str) -> proc_macro2::TokenStream {
+    let level_variant: Ident = Ident::new(&capitalize(&check_level(level)), Span::call_site());
+    quote::quote!(::logcall::__private::log::Level::#level_variant)
+}
+
+// The path of the enclosing function, evaluated at runtime, once per call into `__fn_name`,
+// which the guards and the records refer to. With `low_overhead`, the name is derived once and
+// cached in `__LOGCALL_FN_NAME`. With `fn_id`, the function is named by its ID in the registry
+// instead.
+fn gen_fn_name(args: &Args) -> proc_macro2::TokenStream {
+    bindings!(__fn_name);
+    if args.fn_id {
+        return quote::quote!(let #__fn_name: &'static str = ::logcall::__fn_id!(););
+    }
+    let fn_name = quote::quote! {
+        {
+            fn f() {}
+            fn type_name_of<T>(_: T) -> &'static str {
+                ::core::any::type_name::<T>()
+            }
+            let name = type_name_of(f);
+            let name = &name[..name.len() - 3];
+            name.trim_end_matches("::{{closure}}")
+        }
+    };
+    if args.low_overhead {
+        quote::quote!(
+            let #__fn_name: &'static str = {
+                static __LOGCALL_FN_NAME: ::std::sync::OnceLock<&'static str> =
+                    ::std::sync::OnceLock::new();
+                __LOGCALL_FN_NAME.get_or_init(|| #fn_name)
+            };
+        )
+    } else {
+        quote::quote!(let #__fn_name: &'static str = #fn_name;)
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+// With `catch_panic`, a panic while rendering the arguments, e.g. in a `Debug` implementation,
+// is caught and the arguments are rendered as a placeholder instead.
+fn gen_render(args: &Args, render: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if args.catch_panic {
+        quote::quote!(::logcall::__private::catch_render(|| #render))
+    } else {
+        render
+    }
+}
+
+// Use the user provided format string if any, otherwise generate one from the signature.
+//
+// The user provided literal keeps its original span so that the identifiers captured by the
+// format string resolve with the same hygiene as the literal itself.
+//
+// In `heapless` mode, the input is rendered into a fixed-capacity stack buffer instead.
+// With `diff_by`, only the changes since the previous call with the same key are rendered.
+fn gen_input_string(args: &Args, sig: &Signature) -> proc_macro2::TokenStream {
+    // With `summarize` and `only = "timing"`, the calls are only counted and timed.
+    if args.summarize.is_some() || args.timing {
+        return quote::quote!(::std::string::String::new());
+    }
+    // The structured fields are generated for every parameter.
+    if (args.json || args.journald || args.kv() || args.valuable())
+        && sig.inputs.iter().any(|input| !cfg_attrs(input).is_empty())
+    {
+        abort_call_site!(
+            "`#[cfg]` on parameters cannot be used with `format = \"json\"`, `journald`, or the `kv` and `valuable` features"
+        );
+    }
+    let format_args = match &args.input_format {
+        Some(input_format) => quote::quote!(#input_format),
+        None => gen_input_format(args, sig),
+    };
+    let input_string = if args.json {
+        gen_input_json(args, sig)
+    } else if args.diff_by.is_some() {
+        gen_input_diff(args, sig)
+    } else if let Some(size) = args.heapless {
+        quote::quote!({
+            let mut buf = ::logcall::__private::StackBuf::<#size>::new();
+            let _ = ::std::fmt::Write::write_fmt(&mut buf, format_args!(#format_args));
+            buf
+        })
+    } else {
+        gen_render(args, quote::quote!(format!(#format_args)))
+    };
+    // With `low_overhead`, the inputs are not rendered when the most verbose level the function
+    // may log at is filtered out by `log::max_level()`. The accumulator and the `tracing`
+    // and `slog` backends don't go through that filter.
+    if args.low_overhead && args.accumulate.is_none() && matches!(args.backend, Backend::Log) {
+        let levels = args
+            .levels
+            .checked()
+            .into_iter()
+            .chain(args.test_level.clone())
+            .chain(
+                args.after_error
+                    .as_ref()
+                    .map(|after_error| after_error.level.clone()),
+            );
+        let level = gen_level(&levels.max_by_key(|level| severity(level)).unwrap());
+        quote::quote!(
+            if #level <= ::logcall::__private::log::max_level() {
+                #input_string
+            } else {
+                ::std::default::Default::default()
+            }
+        )
+    } else {
+        input_string
+    }
+}
+
+// fn(a: usize, b: usize) => "a = {:?}, b = {:?}", a, b
+//
+// The arguments are passed explicitly instead of being captured by the format string, so that
+// the parameter identifiers keep their own spans. This matters for functions generated by
+// `macro_rules!`, where the parameter names may come from a different hygiene context than
+// the attribute.
+fn gen_input_format(args: &Args, sig: &Signature) -> proc_macro2::TokenStream {
+    let head_args = args.head.iter().flat_map(|head| &head.args);
+    for ident in args.tokenize.iter().chain(head_args) {
+        if !has_arg(sig, ident) {
+            abort!(ident.span(), "unknown argument `{}`", ident);
+        }
+    }
+    if sig.inputs.iter().any(|input| !cfg_attrs(input).is_empty()) {
+        return gen_input_cfg_args(args, sig);
+    }
+    let mut input_format = String::new();
+    let mut input_args = Vec::new();
+    for (i, input) in sig.inputs.iter().enumerate() {
+        if i > 0 {
+            input_format.push_str(", ");
+        }
+        match input {
+            FnArg::Typed(PatType { pat, ty, .. }) => {
+                if let Pat::Ident(pat_ident) = &**pat {
+                    let ident = &pat_ident.ident;
+                    input_format.push_str(&format!("{ident} = {{:?}}"));
+                    input_args.push(gen_arg_value(args, sig, ident, ty));
+                }
+            }
+            FnArg::Receiver(_) => {
+                input_format.push_str("self");
+            }
+        }
+    }
+    quote::quote!(#input_format, #(#input_args),*)
+}
+
+// With `#[cfg]`-gated parameters, the arguments are rendered at runtime, as the disabled ones
+// can't be left out of a format string:
+//
+// "{}", ::logcall::__private::CfgArgs(&[("self", None), #[cfg(feature = "x")] ("a", Some(&a))])
+fn gen_input_cfg_args(args: &Args, sig: &Signature) -> proc_macro2::TokenStream {
+    let inputs = sig.inputs.iter().filter_map(|input| {
+        let cfg_attrs = cfg_attrs(input);
+        match input {
+            FnArg::Typed(PatType { pat, ty, .. }) => match &**pat {
+                Pat::Ident(pat_ident) => {
+                    let ident = &pat_ident.ident;
+                    let name = ident.to_string();
+                    let value = gen_arg_value(args, sig, ident, ty);
+                    Some(quote::quote!(
+                        #(#cfg_attrs)*
+                        (#name, Some(&#value as &dyn ::std::fmt::Debug))
+                    ))
+                }
+                _ => None,
+            },
+            FnArg::Receiver(_) => Some(quote::quote!(("self", None))),
+        }
+    });
+    quote::quote!("{}", ::logcall::__private::CfgArgs(&[#(#inputs),*]))
+}
+
+// The `#[cfg(..)]` attributes of a parameter.
+fn cfg_attrs(input: &FnArg) -> Vec<&Attribute> {
+    let attrs = match input {
+        FnArg::Typed(PatType { attrs, .. }) => attrs,
+        FnArg::Receiver(receiver) => &receiver.attrs,
+    };
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("cfg"))
+        .collect()
+}
+
+// With `diff_by`, the input is rendered against the previous call with the same key:
+//
+// static __LOGCALL_DIFF: ArgsDiff = ArgsDiff::new();
+// __LOGCALL_DIFF.render("key", &[("self", None), ("key", Some(&key)), ("a", Some(&a))])
+fn gen_input_diff(args: &Args, sig: &Signature) -> proc_macro2::TokenStream {
+    let key = args.diff_by.as_ref().unwrap();
+    if !has_arg(sig, key) {
+        abort!(key.span(), "unknown argument `{}`", key);
+    }
+    let key = key.to_string();
+    let inputs = sig.inputs.iter().filter_map(|input| {
+        let cfg_attrs = cfg_attrs(input);
+        match input {
+            FnArg::Typed(PatType { pat, ty, .. }) => match &**pat {
+                Pat::Ident(pat_ident) => {
+                    let ident = &pat_ident.ident;
+                    let name = ident.to_string();
+                    let value = gen_arg_value(args, sig, ident, ty);
+                    Some(quote::quote!(
+                        #(#cfg_attrs)*
+                        (#name, Some(&#value as &dyn ::std::fmt::Debug))
+                    ))
+                }
+                _ => None,
+            },
+            FnArg::Receiver(_) => Some(quote::quote!(("self", None))),
+        }
+    });
+    quote::quote!({
+        static __LOGCALL_DIFF: ::logcall::__private::ArgsDiff = ::logcall::__private::ArgsDiff::new();
+        __LOGCALL_DIFF.render(#key, &[#(#inputs),*])
+    })
+}
+
+// With `format = "json"`, the input is rendered as a JSON object, with a nested object for each
+// `group`:
+//
+// ::logcall::__json_args!(("a", a), ("meta", [("b", b), ("c", c)]))
+fn gen_input_json(args: &Args, sig: &Signature) -> proc_macro2::TokenStream {
+    let gen_field = |ident: &Ident, ty: &Type| {
+        let name = arg_name(ident);
+        let value =
+            gen_wrapped_arg_value(args, sig, ident, ty).unwrap_or_else(|| quote::quote!(#ident));
+        quote::quote!((#name, #value))
+    };
+    let fields = structured_fields(args, sig)
+        .into_iter()
+        .map(|field| match field {
+            StructuredField::Arg(ident, ty) => gen_field(ident, ty),
+            StructuredField::Group(name, members) => {
+                let members = members.into_iter().map(|(ident, ty)| gen_field(ident, ty));
+                quote::quote!((#name, [#(#members),*]))
+            }
+        })
+        .collect::<Vec<_>>();
+    quote::quote!(::logcall::__json_args!(#(#fields),*))
+}
+
+// The number of arguments of a callable parameter, i.e. an `fn` pointer, an `impl Fn*`, a
+// `dyn Fn*` behind a reference or a smart pointer, or a type parameter bounded by `Fn*`.
+//
+// Callables don't implement `Debug`, so they are rendered as their type name instead.
+fn fn_arity(ty: &Type, generics: &Generics) -> Option<usize> {
+    match ty {
+        Type::BareFn(bare_fn) => Some(bare_fn.inputs.len()),
+        Type::ImplTrait(TypeImplTrait { bounds, .. })
+        | Type::TraitObject(TypeTraitObject { bounds, .. }) => bounds_arity(bounds.iter()),
+        Type::Reference(TypeReference { elem, .. })
+        | Type::Paren(TypeParen { elem, .. })
+        | Type::Group(TypeGroup { elem, .. }) => fn_arity(elem, generics),
+        Type::Path(TypePath { qself: None, path }) => {
+            if let Some(ident) = path.get_ident() {
+                return generic_arity(ident, generics);
+            }
+            // `Box<dyn Fn(..)>`, `Arc<dyn Fn(..)>`, `Rc<dyn Fn(..)>`
+            let segment = path.segments.last()?;
+            if !["Box", "Arc", "Rc"].contains(&segment.ident.to_string().as_str()) {
+                return None;
+            }
+            match &segment.arguments {
+                PathArguments::AngleBracketed(arguments) => match arguments.args.first()? {
+                    GenericArgument::Type(ty) => fn_arity(ty, generics),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// The arity of a type parameter bounded by `Fn*`, inline or in the `where` clause.
+fn generic_arity(ident: &Ident, generics: &Generics) -> Option<usize> {
+    let inline = generics.type_params().filter(|param| param.ident == *ident);
+    let inline = inline.flat_map(|param| param.bounds.iter());
+    let predicates = generics.where_clause.iter().flat_map(|where_clause| {
+        where_clause
+            .predicates
+            .iter()
+            .filter_map(|predicate| match predicate {
+                WherePredicate::Type(PredicateType {
+                    bounded_ty: Type::Path(TypePath { qself: None, path }),
+                    bounds,
+                    ..
+                }) if path.is_ident(ident) => Some(bounds.iter()),
+                _ => None,
+            })
+    });
+    bounds_arity(inline.chain(predicates.flatten()))
+}
+
+fn bounds_arity<'a>(mut bounds: impl Iterator<Item = &'a TypeParamBound>) -> Option<usize> {
+    bounds.find_map(|bound| match bound {
+        TypeParamBound::Trait(TraitBound { path, .. }) => {
+            let segment = path.segments.last()?;
+            if !["Fn", "FnMut", "FnOnce"].contains(&segment.ident.to_string().as_str()) {
+                return None;
+            }
+            match &segment.arguments {
+                PathArguments::Parenthesized(arguments) => Some(arguments.inputs.len()),
+                _ => None,
+            }
+        }
+        _ => None,
+    })
+}
+
+// The `Debug` rendering of an argument: through the tokenizer for `tokenize` arguments, as
+// its type name for callables, with its collections cut for `head` arguments, and as is
+// otherwise.
+fn gen_arg_value(
+    args: &Args,
+    sig: &Signature,
+    ident: &Ident,
+    ty: &Type,
+) -> proc_macro2::TokenStream {
+    gen_wrapped_arg_value(args, sig, ident, ty).unwrap_or_else(|| {
+        quote::quote!({
+            #[allow(unused_imports)]
+            use ::logcall::__private::{NoRenderKind as _, RenderKind as _};
+            (&&::logcall::__private::RenderProbe(&#ident)).render_as()
+        })
+    })
+}
+
+// The argument wrapped for `tokenize`, `head` and the like, if any. Otherwise, the argument is
+// rendered with its `logcall::Render` implementation, if any, and with `Debug` otherwise, but
+// serialized as is with `format = "json"`.
+fn gen_wrapped_arg_value(
+    args: &Args,
+    sig: &Signature,
+    ident: &Ident,
+    ty: &Type,
+) -> Option<proc_macro2::TokenStream> {
+    let value = if args.tokenize.contains(ident) {
+        quote::quote!(::logcall::__private::Tokenize(&#ident))
+    } else if let Some(arity) = fn_arity(ty, &sig.generics) {
+        quote::quote!(::logcall::__private::FnName::<_, #arity>(&#ident))
+    } else if let Some(Head { items, .. }) = args
+        .head
+        .as_ref()
+        .filter(|head| head.args.is_empty() || head.args.contains(ident))
+    {
+        quote::quote!(::logcall::__private::Head(&#ident, #items))
+    } else if is_array(ty) {
+        let limit = args.array_summary.unwrap_or(DEFAULT_ARRAY_SUMMARY);
+        quote::quote!(::logcall::__private::ArraySummary(&#ident[..], #limit))
+    } else {
+        return None;
+    };
+    Some(value)
+}
+
+// The number of elements above which the array arguments are summarized by default.
+const DEFAULT_ARRAY_SUMMARY: usize = 64;
+
+// `[T; N]`, or a reference to it.
+fn is_array(ty: &Type) -> bool {
+    match ty {
+        Type::Array(_) => true,
+        Type::Reference(TypeReference { elem, .. })
+        | Type::Paren(TypeParen { elem, .. })
+        | Type::Group(TypeGroup { elem, .. }) => is_array(elem),
+        _ => false,
+    }
+}
+
+// The values that the record needs from the arguments, which are captured before the call,
+// since the arguments may be moved into the body.
+fn gen_captures(args: &Args, sig: &Signature) -> proc_macro2::TokenStream {
+    bindings!(__slog_logger, __sampled, __valuable_args);
+    let journal_fields = gen_journal_fields(args, sig);
+    // The shared references are copied, and the other arguments rendered, before the call.
+    let valuable_args = args.valuable().then(|| {
+        let values = args.valuable_args.iter().map(|(ident, shared_ref)| {
+            if *shared_ref {
+                quote::quote!(#ident)
+            } else {
+                let ty = typed_args(sig).find(|(arg, _)| *arg == ident).unwrap().1;
+                let value = gen_arg_value(args, sig, ident, ty);
+                gen_render(args, quote::quote!(format!("{:?}", #value)))
+            }
+        });
+        quote::quote!(let #__valuable_args = (#(#values,)*);)
+    });
+    let sampled = args.sample.as_ref().map(|Sample { key, rate }| {
+        if !has_arg(sig, key) {
+            abort!(key.span(), "unknown argument `{}`", key);
+        }
+        quote::quote!(let #__sampled = ::logcall::__private::sampled(&#key, #rate);)
+    });
+    // The logger is evaluated on entry, as the body may move out of it, e.g. out of `self`.
+    let slog_logger = match &args.backend {
+        Backend::Slog(logger) => {
+            Some(quote::quote!(let #__slog_logger = ::std::clone::Clone::clone(&#logger);))
+        }
+        _ => None,
+    };
+    // Shared by all the records of the function.
+    let auto_mute = args.auto_mute.is_some().then(|| {
+        quote::quote!(
+            static __LOGCALL_AUTO_MUTE: ::logcall::__private::AutoMute =
+                ::logcall::__private::AutoMute::new();
+        )
+    });
+    let summary = args.summarize.is_some().then(|| {
+        quote::quote!(
+            static __LOGCALL_SUMMARY: ::logcall::__private::Summary =
+                ::logcall::__private::Summary::new();
+        )
+    });
+    quote::quote!(
+        #journal_fields
+        #valuable_args
+        #sampled
+        #slog_logger
+        #auto_mute
+        #summary
+    )
+}
+
+// [("A", format!("{:?}", a)), ("B", format!("{:?}", b))]
+//
+// With the `kv` feature, the arguments are captured the same way as `__kv_fields`, under their
+// own names.
+fn gen_journal_fields(args: &Args, sig: &Signature) -> proc_macro2::TokenStream {
+    bindings!(__journal_fields, __kv_fields, __console_fields);
+    let (fields, values) = if args.journald {
+        let values = typed_args(sig)
+            .map(|(ident, ty)| {
+                let name = arg_name(ident).to_uppercase();
+                let value = gen_arg_value(args, sig, ident, ty);
+                let value = gen_render(args, quote::quote!(format!("{:?}", #value)));
+                quote::quote!((#name, #value))
+            })
+            .collect::<Vec<_>>();
+        (quote::quote!(#__journal_fields), values)
+    } else if args.kv() {
+        let values = structured_fields(args, sig)
+            .into_iter()
+            .map(|field| match field {
+                StructuredField::Arg(ident, ty) => {
+                    let name = arg_name(ident);
+                    let value = gen_arg_value(args, sig, ident, ty);
+                    let value = gen_render(args, quote::quote!(format!("{:?}", #value)));
+                    quote::quote!((#name, #value))
+                }
+                // ("meta", format!("{{a: {:?}, b: {:?}}}", a, b))
+                StructuredField::Group(name, members) => {
+                    let format = members
+                        .iter()
+                        .map(|(ident, _)| format!("{}: {{:?}}", arg_name(ident)))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let format = format!("{{{{{format}}}}}");
+                    let values = members
+                        .iter()
+                        .map(|(ident, ty)| gen_arg_value(args, sig, ident, ty));
+                    let value = gen_render(args, quote::quote!(format!(#format, #(#values),*)));
+                    quote::quote!((#name, #value))
+                }
+            })
+            .collect::<Vec<_>>();
+        (quote::quote!(#__kv_fields), values)
+    } else if args.console() {
+        let values = typed_args(sig)
+            .map(|(ident, ty)| {
+                let name = arg_name(ident);
+                let value = gen_arg_value(args, sig, ident, ty);
+                let value = gen_render(args, quote::quote!(format!("{:?}", #value)));
+                quote::quote!((#name, #value))
+            })
+            .collect::<Vec<_>>();
+        (quote::quote!(#__console_fields), values)
+    } else {
+        return quote::quote!();
+    };
+    let len = values.len();
+    quote::quote!(
+        let #fields: [(&'static str, ::std::string::String); #len] = [#(#values),*];
+    )
+}
+
+// The arguments bound to an identifier, which are the ones that are logged.
+fn typed_args(sig: &Signature) -> impl Iterator<Item = (&Ident, &Type)> {
+    sig.inputs.iter().filter_map(|input| match input {
+        FnArg::Typed(PatType { pat, ty, .. }) => match &**pat {
+            Pat::Ident(pat_ident) => Some((&pat_ident.ident, &**ty)),
+            _ => None,
+        },
+        FnArg::Receiver(_) => None,
+    })
+}
+
+fn arg_name(ident: &Ident) -> String {
+    ident.to_string().trim_start_matches("r#").to_string()
+}
+
+enum StructuredField<'a> {
+    Arg(&'a Ident, &'a Type),
+    Group(String, Vec<(&'a Ident, &'a Type)>),
+}
+
+// The fields of the structured formats: the arguments in order, with the members of each
+// `group` gathered under its name at the position of its first member.
+fn structured_fields<'a>(args: &Args, sig: &'a Signature) -> Vec<StructuredField<'a>> {
+    let mut grouped = Vec::new();
+    for group in &args.groups {
+        for member in &group.members {
+            if !has_arg(sig, member) {
+                abort!(member.span(), "unknown argument `{}`", member);
+            }
+            if grouped.contains(&member) {
+                abort!(member.span(), "argument `{}` is already grouped", member);
+            }
+            grouped.push(member);
+        }
+    }
+    let mut fields = Vec::new();
+    for (ident, ty) in typed_args(sig) {
+        match args
+            .groups
+            .iter()
+            .find(|group| group.members.contains(ident))
+        {
+            None => fields.push(StructuredField::Arg(ident, ty)),
+            Some(group) => {
+                let name = group.name.value();
+                let member = (ident, ty);
+                match fields.iter_mut().find_map(|field| match field {
+                    StructuredField::Group(group_name, members) if *group_name == name => {
+                        Some(members)
+                    }
+                    _ => None,
+                }) {
+                    Some(members) => members.push(member),
+                    None => fields.push(StructuredField::Group(name, vec![member])),
+                }
+            }
+        }
+    }
+    fields
+}
+
+fn has_arg(sig: &Signature, ident: &Ident) -> bool {
+    sig.inputs.iter().any(|input| match input {
+        FnArg::Typed(PatType { pat, .. }) => {
+            matches!(&**pat, Pat::Ident(pat_ident) if pat_ident.ident == *ident)
+        }
+        FnArg::Receiver(_) => false,
+    })
+}
+
+enum AsyncTraitKind<'a> {
+    // old construction. Contains the function
+    Function,
+    // new construction. Contains a reference to the async block
+    Async(&'a ExprAsync),
+}
+
+struct AsyncTraitInfo<'a> {
+    // statement that must be patched
+    _source_stmt: &'a Stmt,
+    kind: AsyncTraitKind<'a>,
+}
+
+// Get the AST of the inner function we need to hook, if it was generated
+// by async-trait.
+// When we are given a function annotated by async-trait, that function
+// is only a placeholder that returns a pinned future containing the
+// user logic, and it is that pinned future that needs to be instrumented.
+// Were we to instrument its parent, we would only collect information
+// regarding the allocation of that future, and not its own span of execution.
+// Depending on the version of async-trait, we inspect the block of the function
+// to find if it matches the pattern
+// `async fn foo<...>(...) {...}; Box::pin(foo<...>(...))` (<=0.1.43), or if
+// it matches `Box::pin(async move { ... }) (>=0.1.44). We the return the
+// statement that must be instrumented, along with some other informations.
+// 'gen_body' will then be able to use that information to instrument the
+// proper function/future.
+// (this follows the approach suggested in
+// https://github.com/dtolnay/async-trait/issues/45#issuecomment-571245673)
+fn get_async_trait_info(block: &Block, block_is_async: bool) -> Option<AsyncTraitInfo<'_>> {
+    // are we in an async context? If yes, this isn't a async_trait-like pattern
+    if block_is_async {
+        return None;
+    }
+
+    // list of async functions declared inside the block
+    let inside_funs = block.stmts.iter().filter_map(|stmt| {
+        if let Stmt::Item(Item::Fn(fun)) = &stmt {
+            // If the function is async, this is a candidate
+            if fun.sig.asyncness.is_some() {
+                return Some((stmt, fun));
+            }
+        }
+        None
+    });
+
+    // last expression of the block (it determines the return value
+    // of the block, so that if we are working on a function whose
+    // `trait` or `impl` declaration is annotated by async_trait,
+    // this is quite likely the point where the future is pinned)
+    let (last_expr_stmt, last_expr) = block.stmts.iter().rev().find_map(|stmt| {
+        if let Stmt::Expr(expr) = stmt {
+            Some((stmt, expr))
+        } else {
+            None
+        }
+    })?;
+
+    // is the last expression a function call?
+    let (outside_func, outside_args) = match last_expr {
+        Expr::Call(ExprCall { func, args, .. }) => (func, args),
+        _ => return None,
+    };
+
+    // is it a call to `Box::pin()`?
+    let path = match outside_func.as_ref() {
+        Expr::Path(path) => &path.path,
+        _ => return None,
+    };
+    if !path_to_string(path).ends_with("Box::pin") {
+        return None;
+    }
+
+    // Does the call take an argument? If it doesn't,
+    // it's not gonna compile anyway, but that's no reason
+    // to (try to) perform an out of bounds access
+    if outside_args.is_empty() {
+        return None;
+    }
+
+    // Is the argument to Box::pin an async block that
+    // captures its arguments?
+    if let Expr::Async(async_expr) = &outside_args[0] {
+        // check that the move 'keyword' is present
+        async_expr.capture?;
+
+        return Some(AsyncTraitInfo {
+            _source_stmt: last_expr_stmt,
+            kind: AsyncTraitKind::Async(async_expr),
+        });
+    }
+
+    // Is the argument to Box::pin a function call itself?
+    let func = match &outside_args[0] {
+        Expr::Call(ExprCall { func, .. }) => func,
+        _ => return None,
+    };
+
+    // "stringify" the path of the function called
+    let func_name = match **func {
+        Expr::Path(ref func_path) => path_to_string(&func_path.path),
+        _ => return None,
+    };
+
+    // Was that function defined inside of the current block?
+    // If so, retrieve the statement where it was declared and the function itself
+    let (stmt_func_declaration, _) = inside_funs
+        .into_iter()
+        .find(|(_, fun)| fun.sig.ident == func_name)?;
+
+    Some(AsyncTraitInfo {
+        _source_stmt: stmt_func_declaration,
+        kind: AsyncTraitKind::Function,
+    })
+}
+
+// Return a path as a String
+fn path_to_string(path: &Path) -> String {
+    use std::fmt::Write;
+    // some heuristic to prevent too many allocations
+    let mut res = String::with_capacity(path.segments.len() * 5);
+    for i in 0..path.segments.len() {
+        write!(res, "{}", path.segments[i].ident).expect("writing to a String should never fail");
+        if i < path.segments.len() - 1 {
+            res.push_str("::");
+        }
+    }
+    res
+}
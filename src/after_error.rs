@@ -0,0 +1,46 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use log::Level;
+
+// Per-function state of `#[logcall(after_error(...))]`.
+//
+// An `Err` opens a window covering the next `count` invocations of the function, which are
+// logged at the boosted level if it is more severe than their own level.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct AfterError {
+    remaining: AtomicUsize,
+}
+
+impl AfterError {
+    pub const fn new() -> Self {
+        AfterError {
+            remaining: AtomicUsize::new(0),
+        }
+    }
+
+    // Opens a new window, or restarts the current one.
+    pub fn trigger(&self, count: usize) {
+        self.remaining.store(count, Ordering::Relaxed);
+    }
+
+    // Consumes an invocation of the window, returning whether there was one left.
+    pub fn boost(&self) -> bool {
+        self.remaining
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |remaining| {
+                remaining.checked_sub(1)
+            })
+            .is_ok()
+    }
+
+    // The level of the record of a successful invocation.
+    pub fn level(&self, level: Level, boost: Level) -> Level {
+        if self.boost() {
+            // `Error` is the smallest level.
+            level.min(boost)
+        } else {
+            level
+        }
+    }
+}
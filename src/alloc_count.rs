@@ -0,0 +1,152 @@
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::cell::Cell;
+use std::fmt;
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::task::Context as TaskContext;
+use std::task::Poll;
+
+thread_local! {
+    // The number of allocations and of bytes allocated on this thread. Const-initialized
+    // without a destructor, so that accessing it never allocates.
+    static COUNTS: Cell<(u64, u64)> = const { Cell::new((0, 0)) };
+}
+
+/// A global allocator counting the allocations of each thread, which `#[logcall(allocations)]`
+/// reports for every call. It wraps another allocator, [`System`] by default.
+///
+/// Without it, the calls are reported with no allocations.
+///
+/// # Examples
+///
+/// ```
+/// use std::alloc::System;
+///
+/// use logcall::CountingAllocator;
+///
+/// #[global_allocator]
+/// static GLOBAL: CountingAllocator = CountingAllocator::new(System);
+/// ```
+pub struct CountingAllocator<A = System>(A);
+
+impl<A> CountingAllocator<A> {
+    /// Wraps `allocator`.
+    pub const fn new(allocator: A) -> Self {
+        CountingAllocator(allocator)
+    }
+}
+
+fn record(bytes: usize) {
+    // The counters are gone while the thread is torn down.
+    let _ = COUNTS.try_with(|counts| {
+        let (allocs, total) = counts.get();
+        counts.set((allocs + 1, total + bytes as u64));
+    });
+}
+
+// SAFETY: every method forwards to the wrapped allocator.
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        self.0.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        self.0.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.0.dealloc(ptr, layout)
+    }
+
+    // A reallocation counts as an allocation of the bytes it adds.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size.saturating_sub(layout.size()));
+        self.0.realloc(ptr, layout, new_size)
+    }
+}
+
+// Renders ` allocs=12 alloc_bytes=3456` for `#[logcall(allocations)]`: the allocations made
+// by the body of the call, as counted by `CountingAllocator`. The allocations of an async call
+// are counted during the polls of its body, on whichever threads they run.
+#[doc(hidden)]
+pub struct AllocCount {
+    allocs: AtomicU64,
+    bytes: AtomicU64,
+}
+
+impl AllocCount {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        AllocCount {
+            allocs: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+        }
+    }
+
+    // The counters of the thread, to be passed to `add_since`.
+    pub fn snapshot(&self) -> (u64, u64) {
+        COUNTS.try_with(Cell::get).unwrap_or_default()
+    }
+
+    // Adds the allocations of the thread since `start`.
+    pub fn add_since(&self, start: (u64, u64)) {
+        let (allocs, bytes) = self.snapshot();
+        self.allocs.fetch_add(allocs - start.0, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes - start.1, Ordering::Relaxed);
+    }
+
+    // Counts the allocations of the polls of `future`.
+    pub fn instrument<F: Future>(&self, future: F) -> AllocCounted<'_, F> {
+        AllocCounted {
+            future,
+            alloc_count: self,
+        }
+    }
+}
+
+impl Display for AllocCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            " allocs={} alloc_bytes={}",
+            self.allocs.load(Ordering::Relaxed),
+            self.bytes.load(Ordering::Relaxed)
+        )
+    }
+}
+
+#[doc(hidden)]
+pub struct AllocCounted<'a, F> {
+    future: F,
+    alloc_count: &'a AllocCount,
+}
+
+impl<F: Future> Future for AllocCounted<'_, F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<F::Output> {
+        let alloc_count = self.alloc_count;
+        // SAFETY: `future` is structurally pinned and never moved out of `self`.
+        let future = unsafe { self.map_unchecked_mut(|this| &mut this.future) };
+        let start = alloc_count.snapshot();
+        let poll = future.poll(cx);
+        alloc_count.add_since(start);
+        poll
+    }
+}
+
+// Counts the allocations of the call with the `alloc-count` feature.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __alloc_count {
+    () => {
+        $crate::__private::AllocCount::new()
+    };
+}
@@ -0,0 +1,24 @@
+use std::fmt;
+use std::fmt::Debug;
+
+// The number of elements rendered at the front of a summarized array.
+const PREVIEW: usize = 8;
+
+// Renders an array argument, e.g. `[u8; 4096]`, in full up to `limit` elements, and above it as
+// its type followed by its first elements, e.g. `[u8; 4096] [0, 1, 2, 3, 4, 5, 6, 7, …]`.
+#[doc(hidden)]
+pub struct ArraySummary<'a, T>(pub &'a [T], pub usize);
+
+impl<T: Debug> Debug for ArraySummary<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ArraySummary(array, limit) = *self;
+        if array.len() <= limit {
+            return array.fmt(f);
+        }
+        write!(f, "[{}; {}] [", std::any::type_name::<T>(), array.len())?;
+        for item in array.iter().take(limit.min(PREVIEW)) {
+            write!(f, "{item:?}, ")?;
+        }
+        f.write_str("…]")
+    }
+}
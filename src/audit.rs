@@ -0,0 +1,76 @@
+//! Tamper-evident audit records.
+//!
+//! Records of functions annotated with `#[logcall(audit)]` end with ` sig=<hex>`, the
+//! HMAC-SHA256 of the rest of the line computed with the key installed by [`set_key`].
+//! Downstream systems holding the key can detect modified lines with [`verify`]. Records
+//! logged before a key is installed end with ` sig=unsigned`.
+
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Write;
+use std::sync::RwLock;
+
+use hmac::Hmac;
+use hmac::Mac;
+use sha2::Sha256;
+
+static KEY: RwLock<Option<Vec<u8>>> = RwLock::new(None);
+
+const SEPARATOR: &str = " sig=";
+
+/// Installs the key used to sign audit records.
+pub fn set_key(key: impl Into<Vec<u8>>) {
+    *KEY.write().unwrap_or_else(|err| err.into_inner()) = Some(key.into());
+}
+
+/// Checks the signature of an audit record with the given key.
+///
+/// Returns `false` if the line is unsigned, or if it was modified after being signed.
+pub fn verify(key: &[u8], line: &str) -> bool {
+    let Some((message, signature)) = line.rsplit_once(SEPARATOR) else {
+        return false;
+    };
+    let Some(signature) = decode_hex(signature) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(key) else {
+        return false;
+    };
+    mac.update(message.as_bytes());
+    mac.verify_slice(&signature).is_ok()
+}
+
+fn sign(message: &str) -> Option<String> {
+    let key = KEY.read().unwrap_or_else(|err| err.into_inner());
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.as_deref()?).ok()?;
+    mac.update(message.as_bytes());
+    let mut signature = String::with_capacity(64);
+    for byte in mac.finalize().into_bytes() {
+        let _ = write!(signature, "{byte:02x}");
+    }
+    Some(signature)
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+// Renders the message followed by its signature.
+#[doc(hidden)]
+pub struct __Signed<'a>(pub fmt::Arguments<'a>);
+
+impl Display for __Signed<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = self.0.to_string();
+        match sign(&message) {
+            Some(signature) => write!(f, "{message}{SEPARATOR}{signature}"),
+            None => write!(f, "{message}{SEPARATOR}unsigned"),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+// Per-function state of `#[logcall(auto_mute(threshold = "1000/min", for = "5m"))]`.
+//
+// The records are counted in fixed windows of the unit of the threshold. A function logging
+// more records than the threshold within a window is muted for the cooldown, which is announced
+// by a single notice, and its records are counted again from scratch when it ends.
+#[doc(hidden)]
+pub struct AutoMute {
+    state: Mutex<State>,
+}
+
+struct State {
+    window_start: Option<Instant>,
+    count: u64,
+    muted_until: Option<Instant>,
+}
+
+impl AutoMute {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        AutoMute {
+            state: Mutex::new(State {
+                window_start: None,
+                count: 0,
+                muted_until: None,
+            }),
+        }
+    }
+
+    // Whether the record is emitted.
+    #[allow(clippy::too_many_arguments)]
+    pub fn admit(
+        &self,
+        threshold: u64,
+        window: Duration,
+        cooldown: Duration,
+        target: &str,
+        fn_name: &str,
+        rate: &str,
+        cooldown_str: &str,
+    ) -> bool {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(muted_until) = state.muted_until {
+            if now < muted_until {
+                return false;
+            }
+            state.muted_until = None;
+            state.window_start = None;
+        }
+        match state.window_start {
+            Some(start) if now.duration_since(start) < window => state.count += 1,
+            _ => {
+                state.window_start = Some(now);
+                state.count = 1;
+            }
+        }
+        if state.count <= threshold {
+            return true;
+        }
+        state.muted_until = Some(now + cooldown);
+        drop(state);
+        log::warn!(
+            target: target,
+            "{fn_name} logged more than {rate} and is muted for {cooldown_str}"
+        );
+        false
+    }
+}
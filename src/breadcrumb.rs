@@ -0,0 +1,83 @@
+use std::fmt::Debug;
+use std::fmt::Display;
+
+use log::Level;
+use sentry_core::protocol::Breadcrumb;
+use sentry_core::protocol::Map;
+
+// Records the call as a breadcrumb of the current Sentry hub, named after the function and
+// carrying the rendered arguments, and the `Debug` of the error if it failed, so that the
+// error reports contain the trail of the recent calls. The breadcrumb is only rendered if a
+// Sentry client is bound.
+#[doc(hidden)]
+pub fn breadcrumb(level: Level, fn_name: &'static str, args: &dyn Display, error: Option<&dyn Debug>) {
+    sentry_core::add_breadcrumb(|| {
+        let mut data = Map::new();
+        data.insert("args".to_string(), args.to_string().into());
+        if let Some(error) = error {
+            data.insert("error".to_string(), format!("{error:?}").into());
+        }
+        Breadcrumb {
+            ty: "debug".to_string(),
+            category: Some("logcall".to_string()),
+            level: sentry_level(level),
+            message: Some(fn_name.to_string()),
+            data,
+            ..Default::default()
+        }
+    });
+}
+
+fn sentry_level(level: Level) -> sentry_core::Level {
+    match level {
+        Level::Error => sentry_core::Level::Error,
+        Level::Warn => sentry_core::Level::Warning,
+        Level::Info => sentry_core::Level::Info,
+        Level::Debug | Level::Trace => sentry_core::Level::Debug,
+    }
+}
+
+// Selects the error of the return value, using autoref specialization:
+// `(&&ErrorProbe(&value)).error()` resolves to `ErrorKind`, which returns the error of an `Err`,
+// when the value is a `Result` with a `Debug` error, and to `NoErrorKind`, which has no error,
+// otherwise.
+#[doc(hidden)]
+pub struct ErrorProbe<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait ErrorKind<'a> {
+    fn error(&self) -> Option<&'a dyn Debug>;
+}
+
+#[doc(hidden)]
+pub trait NoErrorKind<'a> {
+    fn error(&self) -> Option<&'a dyn Debug>;
+}
+
+impl<'a, T: ?Sized> NoErrorKind<'a> for ErrorProbe<'a, T> {
+    fn error(&self) -> Option<&'a dyn Debug> {
+        None
+    }
+}
+
+impl<'a, T, E: Debug> ErrorKind<'a> for &ErrorProbe<'a, Result<T, E>> {
+    fn error(&self) -> Option<&'a dyn Debug> {
+        self.0.as_ref().err().map(|err| err as &dyn Debug)
+    }
+}
+
+// Records the call as a Sentry breadcrumb with the `sentry` feature.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __breadcrumb {
+    ($level:expr, $fn_name:expr, $args:expr, $value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__private::{ErrorKind as _, NoErrorKind as _};
+        $crate::__private::breadcrumb(
+            $level,
+            $fn_name,
+            $args,
+            (&&$crate::__private::ErrorProbe($value)).error(),
+        )
+    }};
+}
@@ -0,0 +1,49 @@
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Display;
+
+use crate::fingerprint::fold;
+use crate::fingerprint::Fnv1a;
+use crate::hasher::hash;
+
+// Renders `(a = 1, b = 2) => 3` for `#[logcall(budget = "1KiB")]` when the rendered inputs and
+// return value fit in the budget, and a summary of the call otherwise, rather than truncating a
+// value midway:
+//
+// (data: Vec<u8>) => usize budget_exceeded=true args_len=1532 ret_len=4 hash=3f2a9c1e
+//
+// The summary has the parameters and the return type of the function in place of the values,
+// their lengths, and the FNV-1a hash of the values, unless `set_hasher` installs another
+// hasher, so identical calls can still be matched.
+#[doc(hidden)]
+pub struct Budget<'a> {
+    pub args: &'a dyn Display,
+    pub ret: &'a dyn Debug,
+    pub size: usize,
+    pub params: &'static str,
+    pub ret_type: &'static str,
+}
+
+impl Display for Budget<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let args = self.args.to_string();
+        let ret = format!("{:?}", self.ret);
+        if args.len() + ret.len() <= self.size {
+            return write!(f, "({args}) => {ret}");
+        }
+        let hash = hash(Fnv1a::new(), |hasher| {
+            hasher.write(args.as_bytes());
+            hasher.write(&[0]);
+            hasher.write(ret.as_bytes());
+        });
+        write!(
+            f,
+            "({}) => {} budget_exceeded=true args_len={} ret_len={} hash={:08x}",
+            self.params,
+            self.ret_type,
+            args.len(),
+            ret.len(),
+            fold(hash)
+        )
+    }
+}
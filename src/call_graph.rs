@@ -0,0 +1,154 @@
+//! Caller → callee graph of instrumented functions.
+//!
+//! With the `call-graph` feature, every call of an instrumented function made while recording is
+//! attributed to the instrumented function it was called from, directly or through functions
+//! that aren't instrumented. Calls made from the body of an instrumented async function are
+//! attributed to it on whichever thread it's polled.
+//!
+//! The graph can be dumped in the DOT format, to be rendered with Graphviz:
+//!
+//! ```no_run
+//! logcall::call_graph::start();
+//! // ... run the workload ...
+//! logcall::call_graph::save("calls.dot").unwrap();
+//! ```
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::future::Future;
+use std::io;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::task::Context as TaskContext;
+use std::task::Poll;
+
+// The name of the root node, for calls made outside of any instrumented function.
+const ROOT: &str = "<root>";
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static EDGES: Mutex<BTreeMap<(&'static str, &'static str), u64>> = Mutex::new(BTreeMap::new());
+
+thread_local! {
+    // The instrumented functions being called, or polled, on this thread.
+    static STACK: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Starts recording the calls of instrumented functions.
+pub fn start() {
+    RECORDING.store(true, Ordering::Relaxed);
+}
+
+/// Stops recording. The graph recorded so far is kept until it's cleared.
+pub fn stop() {
+    RECORDING.store(false, Ordering::Relaxed);
+}
+
+/// Discards the recorded graph.
+pub fn clear() {
+    EDGES.lock().unwrap_or_else(|err| err.into_inner()).clear();
+}
+
+/// Returns the recorded `(caller, callee, calls)` edges, sorted by caller and callee.
+///
+/// Calls made outside of any instrumented function have `<root>` as caller.
+pub fn edges() -> Vec<(&'static str, &'static str, u64)> {
+    EDGES
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .iter()
+        .map(|(&(caller, callee), &calls)| (caller, callee, calls))
+        .collect()
+}
+
+/// Writes the recorded graph in the DOT format, with the number of calls as edge labels.
+pub fn write_dot(mut writer: impl Write) -> io::Result<()> {
+    writeln!(writer, "digraph logcall {{")?;
+    for (caller, callee, calls) in edges() {
+        writeln!(writer, "    {caller:?} -> {callee:?} [label=\"{calls}\"];")?;
+    }
+    writeln!(writer, "}}")?;
+    writer.flush()
+}
+
+/// Writes the recorded graph in the DOT format to the file at `path`.
+pub fn save(path: impl AsRef<Path>) -> io::Result<()> {
+    write_dot(BufWriter::new(File::create(path)?))
+}
+
+fn record(callee: &'static str) {
+    if !RECORDING.load(Ordering::Relaxed) {
+        return;
+    }
+    let caller = STACK.with(|stack| stack.borrow().last().copied().unwrap_or(ROOT));
+    *EDGES
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .entry((caller, callee))
+        .or_default() += 1;
+}
+
+// Records the call and makes the function the caller of the calls made until it's dropped.
+#[doc(hidden)]
+pub struct CallGuard;
+
+impl CallGuard {
+    pub fn enter(name: &'static str) -> Self {
+        record(name);
+        CallGuard::push(name)
+    }
+
+    fn push(name: &'static str) -> Self {
+        STACK.with(|stack| stack.borrow_mut().push(name));
+        CallGuard
+    }
+}
+
+impl Drop for CallGuard {
+    fn drop(&mut self) {
+        STACK.with(|stack| stack.borrow_mut().pop());
+    }
+}
+
+// Records the call of an async function and makes it the caller of the calls made while its
+// body is polled.
+#[doc(hidden)]
+pub fn call_scope<F: Future>(name: &'static str, future: F) -> CallScope<F> {
+    record(name);
+    CallScope { name, future }
+}
+
+#[doc(hidden)]
+pub struct CallScope<F> {
+    name: &'static str,
+    future: F,
+}
+
+impl<F: Future> Future for CallScope<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<F::Output> {
+        // SAFETY: `future` is structurally pinned and never moved out of `self`.
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+        let _guard = CallGuard::push(this.name);
+        future.poll(cx)
+    }
+}
+
+// Records the call with the `call-graph` feature.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __call_guard {
+    (enter $guard:ident, $fn_name:expr) => {
+        let $guard = $crate::__private::CallGuard::enter($fn_name);
+    };
+    (scope $fn_name:expr, $future:expr) => {
+        $crate::__private::call_scope($fn_name, $future)
+    };
+}
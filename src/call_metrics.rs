@@ -0,0 +1,108 @@
+use std::borrow::Cow;
+use std::time::Instant;
+
+// Records the call with the `metrics` recorder: `fn_calls` counts the calls, `fn_errors` the
+// calls returning an `Err`, and the `fn_duration_seconds` histogram their durations, all
+// labelled with the name of the function. `fn_errors` is also labelled with the error as
+// `error`, if the function returns a `Result`.
+#[doc(hidden)]
+pub fn record_call(
+    fn_name: &'static str,
+    outcome: Option<&'static str>,
+    error_label: Option<Cow<'static, str>>,
+    start: Instant,
+) {
+    ::metrics::counter!("fn_calls", "fn" => fn_name).increment(1);
+    ::metrics::histogram!("fn_duration_seconds", "fn" => fn_name).record(start.elapsed());
+    match error_label {
+        Some(error) => {
+            ::metrics::counter!("fn_errors", "fn" => fn_name, "error" => error).increment(1);
+        }
+        None if outcome == Some("err") => {
+            ::metrics::counter!("fn_errors", "fn" => fn_name).increment(1);
+        }
+        None => {}
+    }
+}
+
+// Selects the `error` label of the return value, using autoref specialization:
+// `(&&&ErrorLabelProbe(&value)).error_label()` resolves to `StaticErrorLabelKind`, which takes
+// the name from `&E: Into<&'static str>`, as derived by `strum::IntoStaticStr`, when the value
+// is a `Result<_, E>`, to `TypeErrorLabelKind`, which takes the type name of `E`, for any other
+// `Result<_, E>`, and to `NoErrorLabelKind`, which has no label, otherwise. The values of the
+// errors are only used by the types opting in, so that the labels are bounded.
+#[doc(hidden)]
+pub struct ErrorLabelProbe<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait StaticErrorLabelKind {
+    fn error_label(&self) -> Option<Cow<'static, str>>;
+}
+
+#[doc(hidden)]
+pub trait TypeErrorLabelKind {
+    fn error_label(&self) -> Option<Cow<'static, str>>;
+}
+
+#[doc(hidden)]
+pub trait NoErrorLabelKind {
+    fn error_label(&self) -> Option<Cow<'static, str>>;
+}
+
+impl<T: ?Sized> NoErrorLabelKind for ErrorLabelProbe<'_, T> {
+    fn error_label(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+}
+
+impl<T, E> TypeErrorLabelKind for &ErrorLabelProbe<'_, Result<T, E>> {
+    fn error_label(&self) -> Option<Cow<'static, str>> {
+        self.0
+            .as_ref()
+            .err()
+            .map(|_| Cow::Borrowed(std::any::type_name::<E>()))
+    }
+}
+
+impl<T, E> StaticErrorLabelKind for &&ErrorLabelProbe<'_, Result<T, E>>
+where
+    for<'e> &'e E: Into<&'static str>,
+{
+    fn error_label(&self) -> Option<Cow<'static, str>> {
+        self.0.as_ref().err().map(|err| Cow::Borrowed(err.into()))
+    }
+}
+
+// Records the call with the `metrics` feature, labelling `fn_errors` with the error mapped by
+// `$error_label` if specified.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __metrics {
+    ($fn_name:expr, $value:expr, $start:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__private::{
+            NoErrorLabelKind as _, StaticErrorLabelKind as _, TypeErrorLabelKind as _,
+        };
+        #[allow(unused_imports)]
+        use $crate::__private::{NoOutcomeKind as _, ResultOutcomeKind as _};
+        $crate::__private::record_call(
+            $fn_name,
+            (&&$crate::__private::OutcomeProbe($value)).outcome(),
+            (&&&$crate::__private::ErrorLabelProbe($value)).error_label(),
+            $start,
+        )
+    }};
+    ($fn_name:expr, $value:expr, $start:expr, $error_label:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__private::{NoOutcomeKind as _, ResultOutcomeKind as _};
+        let __value = $value;
+        $crate::__private::record_call(
+            $fn_name,
+            (&&$crate::__private::OutcomeProbe(__value)).outcome(),
+            ::core::result::Result::as_ref(__value)
+                .err()
+                .map(|err| ::std::borrow::Cow::from($error_label(err))),
+            $start,
+        )
+    }};
+}
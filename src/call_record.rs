@@ -0,0 +1,51 @@
+use std::fmt;
+use std::fmt::Display;
+
+use crate::context::ContextFields;
+
+/// The call of an instrumented function as rendered in its record, available to the body of
+/// the function as `__logcall_record`.
+///
+/// It renders as the function name, the arguments and the fields of the current context, e.g.
+/// `app::charge(id = 7) request_id=42`, so that the custom log lines and error messages of the
+/// body can be joined with the record of the call:
+///
+/// ```
+/// #[logcall::logcall("info")]
+/// fn charge(id: u32, amount: u64) -> Result<(), String> {
+///     if amount == 0 {
+///         return Err(format!("empty charge in {__logcall_record}"));
+///     }
+///     log::debug!("charging {amount} in {}", __logcall_record);
+///     Ok(())
+/// }
+/// ```
+///
+/// The binding is only declared in the functions that mention it.
+pub struct CallRecord<'a> {
+    fn_name: &'static str,
+    input: &'a dyn Display,
+}
+
+impl<'a> CallRecord<'a> {
+    #[doc(hidden)]
+    pub fn new(fn_name: &'static str, input: &'a dyn Display) -> Self {
+        CallRecord { fn_name, input }
+    }
+
+    /// Returns the name of the function, e.g. `app::charge`.
+    pub fn fn_name(&self) -> &'static str {
+        self.fn_name
+    }
+
+    /// Returns the rendered arguments, e.g. `id = 7`.
+    pub fn input(&self) -> &dyn Display {
+        self.input
+    }
+}
+
+impl Display for CallRecord<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({}){}", self.fn_name, self.input, ContextFields)
+    }
+}
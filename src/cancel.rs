@@ -0,0 +1,50 @@
+use std::fmt::Display;
+
+use log::Level;
+
+// Logs `fn(args) => <cancelled>` when dropped while armed, i.e. when the future of an async
+// function is dropped after its first poll and before it completes, e.g. by the losing branch
+// of a `select!`. Unwinding from a panic of the body is not a cancellation.
+#[doc(hidden)]
+pub struct CancelGuard<'a> {
+    armed: bool,
+    level: Level,
+    target: &'static str,
+    fn_name: &'static str,
+    args: &'a dyn Display,
+}
+
+impl<'a> CancelGuard<'a> {
+    pub fn arm(
+        level: Level,
+        target: &'static str,
+        fn_name: &'static str,
+        args: &'a dyn Display,
+    ) -> Self {
+        CancelGuard {
+            armed: true,
+            level,
+            target,
+            fn_name,
+            args,
+        }
+    }
+
+    pub fn disarm(mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for CancelGuard<'_> {
+    fn drop(&mut self) {
+        if self.armed && !std::thread::panicking() {
+            log::log!(
+                target: self.target,
+                self.level,
+                "{}({}) => <cancelled>",
+                self.fn_name,
+                self.args
+            );
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use std::any::Any;
+use std::panic::catch_unwind;
+use std::panic::AssertUnwindSafe;
+
+const FAILED: &str = "<failed to render arguments>";
+
+// Renders the arguments for `#[logcall(catch_panic)]`, falling back to a placeholder if a
+// `Debug` implementation panics, so that the function still runs.
+#[doc(hidden)]
+pub fn catch_render<T: From<&'static str>>(render: impl FnOnce() -> T) -> T {
+    catch_unwind(AssertUnwindSafe(render)).unwrap_or_else(|_| T::from(FAILED))
+}
+
+// Emits the record for `#[logcall(catch_panic)]`. If rendering the return value or the logger
+// panics, a fallback record is emitted through `log` instead, and the function returns
+// normally.
+#[doc(hidden)]
+pub fn catch_log(target: &str, fn_name: &str, log: impl FnOnce()) {
+    if let Err(panic) = catch_unwind(AssertUnwindSafe(log)) {
+        // The fallback may reach the same panicking logger.
+        let _ = catch_unwind(AssertUnwindSafe(|| {
+            log::error!(target: target, "{fn_name}: {FAILED}: {}", panic_message(&*panic));
+        }));
+    }
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    match panic.downcast_ref::<&'static str>() {
+        Some(message) => message,
+        None => match panic.downcast_ref::<String>() {
+            Some(message) => message,
+            None => "Box<dyn Any>",
+        },
+    }
+}
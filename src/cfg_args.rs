@@ -0,0 +1,25 @@
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Display;
+
+// Renders the arguments of a function with `#[cfg]`-gated parameters as the format string of
+// the other functions does, e.g. `self, a = 1`, leaving out the disabled parameters, which a
+// format string can't. `args` holds the name and the value of every enabled argument, in
+// order, or no value for `self`.
+#[doc(hidden)]
+pub struct CfgArgs<'a>(pub &'a [(&'static str, Option<&'a dyn Debug>)]);
+
+impl Display for CfgArgs<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, value)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            match value {
+                Some(value) => write!(f, "{name} = {value:?}")?,
+                None => f.write_str(name)?,
+            }
+        }
+        Ok(())
+    }
+}
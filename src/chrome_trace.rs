@@ -0,0 +1,169 @@
+//! Export of instrumented calls in the Chrome trace event format.
+//!
+//! With the `chrome-trace` feature, every call of an instrumented function made while recording
+//! is captured as a complete event, with its start time, duration and thread. The trace can be
+//! opened in `about:tracing` or [Perfetto](https://ui.perfetto.dev) to visualize the run as a
+//! timeline:
+//!
+//! ```no_run
+//! logcall::chrome_trace::start();
+//! // ... run the workload ...
+//! logcall::chrome_trace::save("trace.json").unwrap();
+//! ```
+
+use std::cell::Cell;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static EVENTS: Mutex<Vec<Event>> = Mutex::new(Vec::new());
+static EPOCH: OnceLock<Instant> = OnceLock::new();
+
+thread_local! {
+    static THREAD_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+struct Event {
+    name: &'static str,
+    start: Duration,
+    duration: Duration,
+    thread_id: u64,
+}
+
+/// Starts recording the calls of instrumented functions.
+pub fn start() {
+    EPOCH.get_or_init(Instant::now);
+    RECORDING.store(true, Ordering::Relaxed);
+}
+
+/// Stops recording. The events recorded so far are kept until they are written or cleared.
+pub fn stop() {
+    RECORDING.store(false, Ordering::Relaxed);
+}
+
+/// Discards the recorded events.
+pub fn clear() {
+    EVENTS.lock().unwrap_or_else(|err| err.into_inner()).clear();
+}
+
+/// Writes the recorded events as a JSON trace.
+pub fn write(mut writer: impl Write) -> io::Result<()> {
+    let pid = std::process::id();
+    let events = EVENTS.lock().unwrap_or_else(|err| err.into_inner());
+    writer.write_all(b"{\"traceEvents\":[")?;
+    for (i, event) in events.iter().enumerate() {
+        if i > 0 {
+            writer.write_all(b",")?;
+        }
+        write!(
+            writer,
+            "{{\"name\":\"{}\",\"cat\":\"logcall\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":{},\"tid\":{}}}",
+            escape(event.name),
+            micros(event.start),
+            micros(event.duration),
+            pid,
+            event.thread_id
+        )?;
+    }
+    writer.write_all(b"],\"displayTimeUnit\":\"ms\"}")?;
+    writer.flush()
+}
+
+/// Writes the recorded events as a JSON trace to the file at `path`.
+pub fn save(path: impl AsRef<Path>) -> io::Result<()> {
+    write(BufWriter::new(File::create(path)?))
+}
+
+fn micros(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000_000.0
+}
+
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// A small sequential ID per thread, as expected by trace viewers.
+fn thread_id() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    THREAD_ID.with(|id| {
+        if id.get() == 0 {
+            id.set(NEXT.fetch_add(1, Ordering::Relaxed));
+        }
+        id.get()
+    })
+}
+
+// Records a complete event for the call when dropped, if recording was on when it started.
+#[doc(hidden)]
+pub struct TraceGuard {
+    name: &'static str,
+    start: Option<Instant>,
+    thread_id: u64,
+}
+
+impl TraceGuard {
+    pub fn begin(name: &'static str) -> Self {
+        let recording = RECORDING.load(Ordering::Relaxed);
+        TraceGuard {
+            name,
+            start: recording.then(Instant::now),
+            thread_id: if recording { thread_id() } else { 0 },
+        }
+    }
+
+    pub fn end(self) {}
+}
+
+impl Drop for TraceGuard {
+    fn drop(&mut self) {
+        let Some(start) = self.start else {
+            return;
+        };
+        let end = Instant::now();
+        let epoch = *EPOCH.get_or_init(|| start);
+        let event = Event {
+            name: self.name,
+            start: start.saturating_duration_since(epoch),
+            duration: end - start,
+            thread_id: self.thread_id,
+        };
+        EVENTS
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(event);
+    }
+}
+
+// Records the call with the `chrome-trace` feature.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace_guard {
+    (begin $guard:ident, $fn_name:expr) => {
+        let $guard = $crate::__private::TraceGuard::begin($fn_name);
+    };
+    (end $guard:ident) => {
+        $guard.end();
+    };
+}
@@ -0,0 +1,69 @@
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Display;
+
+use log::Level;
+
+// Emits the record of `#[logcall(emit = "console")]` with `console.error`, `console.warn`,
+// `console.info` or `console.debug`, passing the function name, every argument, the return
+// value and the appended fields, if any, as separate console arguments:
+//
+// console.info("app::add", "a = 1", "b = 2", "=> 3")
+//
+// On targets other than `wasm32`, the arguments are printed to stderr, separated by spaces.
+#[doc(hidden)]
+pub fn console(
+    level: Level,
+    fn_name: &'static str,
+    fields: &[(&'static str, String)],
+    ret: &dyn Debug,
+    extra: fmt::Arguments<'_>,
+) {
+    let mut parts = Vec::with_capacity(fields.len() + 3);
+    parts.push(fn_name.to_string());
+    parts.extend(fields.iter().map(|(name, value)| format!("{name} = {value}")));
+    parts.push(format!("=> {ret:?}"));
+    let extra = extra.to_string();
+    let extra = extra.trim_start();
+    if !extra.is_empty() {
+        parts.push(extra.to_string());
+    }
+    write_console(level, &parts);
+}
+
+// Emits a message, e.g. the `schema` record, as a single console argument.
+#[doc(hidden)]
+pub fn console_message(level: Level, message: &dyn Display) {
+    write_console(level, &[message.to_string()]);
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_console(level: Level, parts: &[String]) {
+    let data = js_sys::Array::new();
+    for part in parts {
+        data.push(&wasm_bindgen::JsValue::from_str(part));
+    }
+    match level {
+        Level::Error => web_sys::console::error(&data),
+        Level::Warn => web_sys::console::warn(&data),
+        Level::Info => web_sys::console::info(&data),
+        Level::Debug | Level::Trace => web_sys::console::debug(&data),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_console(level: Level, parts: &[String]) {
+    eprintln!("[{}] {}", level, parts.join(" "));
+}
+
+// Emits the record to the browser console with the `wasm-console` feature.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __console {
+    ($level:expr, $message:expr) => {
+        $crate::__private::console_message($level, &$message)
+    };
+    ($level:expr, $fn_name:expr, $fields:expr, $ret:expr, $extra:expr) => {
+        $crate::__private::console($level, $fn_name, $fields, $ret, $extra)
+    };
+}
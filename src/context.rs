@@ -0,0 +1,303 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::RwLock;
+use std::task::Context as TaskContext;
+use std::task::Poll;
+use std::time::Instant;
+
+use log::Level;
+
+use crate::Record;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Context>> = const { RefCell::new(None) };
+}
+
+type TraceIdHook = Box<dyn Fn() -> Option<String> + Send + Sync>;
+
+static TRACE_ID_HOOK: RwLock<Option<TraceIdHook>> = RwLock::new(None);
+
+/// Per-task state consulted by instrumented functions.
+///
+/// A context is installed for the duration of a future with [`scope`](Context::scope), or of a
+/// closure with [`scope_sync`](Context::scope_sync). Instrumented functions called while the
+/// context is installed, directly or from nested calls, see the same context.
+///
+/// ```
+/// # async fn handle_request() {}
+/// # async fn example() {
+/// logcall::Context::new()
+///     .with_accumulator()
+///     .scope(async {
+///         handle_request().await;
+///         let records = logcall::drain();
+///         log::info!("handled request: {records:?}");
+///     })
+///     .await;
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct Context {
+    records: Option<Vec<Record>>,
+    request_id: Option<String>,
+    trace_id: Option<String>,
+    span_id: Option<String>,
+}
+
+impl Context {
+    /// Creates an empty context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collects the records of functions annotated with `#[logcall(accumulate)]`, to be
+    /// retrieved with [`drain`].
+    pub fn with_accumulator(mut self) -> Self {
+        self.records = Some(Vec::new());
+        self
+    }
+
+    /// Tags every record logged in this context with `request_id=...`.
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Tags every record logged in this context with `trace_id=...`, to join the records with
+    /// a distributed trace.
+    pub fn with_trace_id(mut self, trace_id: impl Into<String>) -> Self {
+        self.trace_id = Some(trace_id.into());
+        self
+    }
+
+    /// Tags every record logged in this context with the `trace_id=...` and `span_id=...` of
+    /// a W3C `traceparent` header, such as
+    /// `00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01`.
+    ///
+    /// Invalid headers are ignored.
+    pub fn with_traceparent(mut self, traceparent: &str) -> Self {
+        if let Some((trace_id, span_id)) = parse_traceparent(traceparent) {
+            self.trace_id = Some(trace_id.to_string());
+            self.span_id = Some(span_id.to_string());
+        }
+        self
+    }
+
+    /// Installs the context while the future is polled.
+    pub fn scope<F: Future>(self, future: F) -> Scoped<F> {
+        Scoped {
+            future,
+            context: Some(self),
+        }
+    }
+
+    /// Installs the context while the closure runs.
+    pub fn scope_sync<R>(self, f: impl FnOnce() -> R) -> R {
+        let mut context = Some(self);
+        let _guard = Guard::enter(&mut context);
+        f()
+    }
+}
+
+/// A future with a [`Context`] installed while it is polled.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Scoped<F> {
+    future: F,
+    context: Option<Context>,
+}
+
+impl<F: Future> Future for Scoped<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<F::Output> {
+        // SAFETY: `future` is structurally pinned and never moved out of `self`.
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+        let _guard = Guard::enter(&mut this.context);
+        future.poll(cx)
+    }
+}
+
+// Swaps the context into the thread-local slot, and back out when dropped, so that the
+// previous context is restored even if the scoped code panics.
+struct Guard<'a> {
+    slot: &'a mut Option<Context>,
+}
+
+impl<'a> Guard<'a> {
+    fn enter(slot: &'a mut Option<Context>) -> Self {
+        CURRENT.with(|current| std::mem::swap(slot, &mut *current.borrow_mut()));
+        Guard { slot }
+    }
+}
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        CURRENT.with(|current| std::mem::swap(self.slot, &mut *current.borrow_mut()));
+    }
+}
+
+/// Takes the records accumulated so far in the current [`Context`].
+///
+/// Returns an empty list if there is no context, or if it has no accumulator.
+pub fn drain() -> Vec<Record> {
+    CURRENT.with(|current| {
+        current
+            .borrow_mut()
+            .as_mut()
+            .and_then(|context| context.records.as_mut())
+            .map(std::mem::take)
+            .unwrap_or_default()
+    })
+}
+
+/// Returns the request ID of the current [`Context`], if any.
+pub fn request_id() -> Option<String> {
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .and_then(|context| context.request_id.clone())
+    })
+}
+
+/// Returns the trace ID of the current [`Context`], or the one returned by the hook installed
+/// with [`set_trace_id_hook`] if the context has none.
+pub fn trace_id() -> Option<String> {
+    CURRENT
+        .with(|current| {
+            current
+                .borrow()
+                .as_ref()
+                .and_then(|context| context.trace_id.clone())
+        })
+        .or_else(hooked_trace_id)
+}
+
+/// Installs a callback returning the trace ID to tag the records with, when the current
+/// [`Context`] has none.
+///
+/// This bridges tracing systems that keep track of the current trace on their own, e.g. an
+/// OpenTelemetry context.
+///
+/// ```
+/// logcall::set_trace_id_hook(|| Some("4bf92f3577b34da6a3ce929d0e0e4736".to_string()));
+/// ```
+pub fn set_trace_id_hook(hook: impl Fn() -> Option<String> + Send + Sync + 'static) {
+    *TRACE_ID_HOOK.write().unwrap_or_else(|err| err.into_inner()) = Some(Box::new(hook));
+}
+
+fn hooked_trace_id() -> Option<String> {
+    let hook = TRACE_ID_HOOK.read().unwrap_or_else(|err| err.into_inner());
+    hook.as_ref().and_then(|hook| hook())
+}
+
+// version "-" trace-id "-" parent-id "-" trace-flags, in lowercase hex.
+fn parse_traceparent(traceparent: &str) -> Option<(&str, &str)> {
+    let is_hex = |field: &str, len: usize| {
+        field.len() == len
+            && field
+                .bytes()
+                .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+    };
+    let is_zero = |field: &str| field.bytes().all(|b| b == b'0');
+
+    let mut fields = traceparent.trim().split('-');
+    let version = fields.next()?;
+    let trace_id = fields.next()?;
+    let span_id = fields.next()?;
+    let flags = fields.next()?;
+    // Later versions may append fields, but version 00 has exactly four.
+    if !is_hex(version, 2) || version == "ff" || (version == "00" && fields.next().is_some()) {
+        return None;
+    }
+    if !is_hex(trace_id, 32) || is_zero(trace_id) || !is_hex(span_id, 16) || is_zero(span_id) {
+        return None;
+    }
+    if !is_hex(flags, 2) {
+        return None;
+    }
+    Some((trace_id, span_id))
+}
+
+// Renders the fields of the current context that are appended to every log line.
+#[doc(hidden)]
+pub struct ContextFields;
+
+impl Display for ContextFields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let has_trace_id = CURRENT.with(|current| {
+            let current = current.borrow();
+            let Some(context) = &*current else {
+                return Ok(false);
+            };
+            if let Some(request_id) = &context.request_id {
+                write!(f, " request_id={request_id}")?;
+            }
+            if let Some(trace_id) = &context.trace_id {
+                write!(f, " trace_id={trace_id}")?;
+            }
+            if let Some(span_id) = &context.span_id {
+                write!(f, " span_id={span_id}")?;
+            }
+            Ok(context.trace_id.is_some())
+        })?;
+        // The hook is called outside of the borrow, in case it calls into instrumented
+        // functions.
+        if !has_trace_id {
+            if let Some(trace_id) = hooked_trace_id() {
+                write!(f, " trace_id={trace_id}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Appends a record to the accumulator of the current context, returning `false` if there is
+// no accumulator.
+#[doc(hidden)]
+pub fn push_record(
+    level: Level,
+    target: &'static str,
+    module: &'static str,
+    fn_name: &'static str,
+    args: &dyn Display,
+    ret: &dyn Debug,
+    start: Instant,
+) -> bool {
+    let accumulating = CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .is_some_and(|context| context.records.is_some())
+    });
+    if !accumulating {
+        return false;
+    }
+
+    // Render outside of the borrow, in case `Debug` calls into other instrumented functions.
+    let record = Record {
+        level,
+        target,
+        module,
+        fn_name,
+        args: args.to_string(),
+        ret: format!("{ret:?}"),
+        elapsed: start.elapsed(),
+    };
+    CURRENT.with(|current| {
+        if let Some(records) = current
+            .borrow_mut()
+            .as_mut()
+            .and_then(|context| context.records.as_mut())
+        {
+            records.push(record);
+        }
+    });
+    true
+}
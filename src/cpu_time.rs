@@ -0,0 +1,88 @@
+use std::fmt;
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::task::Context as TaskContext;
+use std::task::Poll;
+use std::time::Duration;
+
+use cpu_time::ThreadTime;
+
+// Renders ` cpu=0.8ms` for `#[logcall(cpu_time)]`: the CPU time spent by the call, next to its
+// wall time, so that blocking work can be told from waiting.
+//
+// A synchronous call is measured on its thread from its start until the record is built. An
+// async call only counts the polls of its body, on whichever threads they run, as the thread is
+// free to run other tasks while the body waits.
+#[doc(hidden)]
+pub enum CpuTime {
+    Thread(ThreadTime),
+    Polls(AtomicU64),
+}
+
+impl CpuTime {
+    pub fn thread() -> Self {
+        CpuTime::Thread(ThreadTime::now())
+    }
+
+    pub fn polls() -> Self {
+        CpuTime::Polls(AtomicU64::new(0))
+    }
+
+    // Counts the CPU time of the polls of `future`.
+    pub fn instrument<F: Future>(&self, future: F) -> CpuTimed<'_, F> {
+        CpuTimed {
+            future,
+            cpu_time: self,
+        }
+    }
+
+    fn elapsed(&self) -> Duration {
+        match self {
+            CpuTime::Thread(start) => start.elapsed(),
+            CpuTime::Polls(nanos) => Duration::from_nanos(nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl Display for CpuTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, " cpu={:?}", self.elapsed())
+    }
+}
+
+#[doc(hidden)]
+pub struct CpuTimed<'a, F> {
+    future: F,
+    cpu_time: &'a CpuTime,
+}
+
+impl<F: Future> Future for CpuTimed<'_, F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<F::Output> {
+        let cpu_time = self.cpu_time;
+        // SAFETY: `future` is structurally pinned and never moved out of `self`.
+        let future = unsafe { self.map_unchecked_mut(|this| &mut this.future) };
+        let start = ThreadTime::now();
+        let poll = future.poll(cx);
+        if let CpuTime::Polls(nanos) = cpu_time {
+            nanos.fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+        }
+        poll
+    }
+}
+
+// Measures the CPU time of the call with the `cpu-time` feature.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cpu_time {
+    (thread) => {
+        $crate::__private::CpuTime::thread()
+    };
+    (polls) => {
+        $crate::__private::CpuTime::polls()
+    };
+}
@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Condvar;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+
+// Emits a summary line of `#[logcall(dedup = "...")]` through the backend of the callsite.
+#[doc(hidden)]
+pub type DedupSummary = Box<dyn Fn(&str) + Send>;
+
+// Per-callsite state of `#[logcall(dedup = "...")]`.
+//
+// The first occurrence of a record in a window is emitted right away, and identical records
+// that follow within the window are only counted. When the window is over, a summary line
+// with `repeated=N` is emitted for every record that was suppressed, by the shared flusher
+// thread, or by the next record of the callsite if it comes first.
+#[doc(hidden)]
+pub struct Dedup {
+    window: Duration,
+    state: Mutex<Option<State>>,
+}
+
+struct State {
+    start: Instant,
+    // The records of the current window, with their suppressed occurrences, if any.
+    records: HashMap<String, Option<Suppressed>>,
+    // Whether the flusher is due to close the current window.
+    scheduled: bool,
+}
+
+struct Suppressed {
+    repeated: u64,
+    summary: DedupSummary,
+}
+
+impl Dedup {
+    pub const fn new(window: Duration) -> Self {
+        Dedup {
+            window,
+            state: Mutex::new(None),
+        }
+    }
+
+    // Returns the message to emit for this record, unless it is a repetition. The summaries
+    // of the previous window are emitted first, if it is over. `summary` is only called for
+    // the first repetition of a record in a window.
+    pub fn record(
+        &'static self,
+        args: fmt::Arguments<'_>,
+        summary: impl FnOnce() -> DedupSummary,
+    ) -> Option<String> {
+        let message = args.to_string();
+        let now = Instant::now();
+
+        let mut guard = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        let closed = match &mut *guard {
+            Some(state) if now.duration_since(state.start) >= self.window => {
+                state.start = now;
+                state.scheduled = false;
+                std::mem::take(&mut state.records)
+            }
+            _ => HashMap::new(),
+        };
+        let state = guard.get_or_insert_with(|| State {
+            start: now,
+            records: HashMap::new(),
+            scheduled: false,
+        });
+        let message = match state.records.get_mut(&message) {
+            Some(Some(suppressed)) => {
+                suppressed.repeated += 1;
+                None
+            }
+            Some(suppressed) => {
+                *suppressed = Some(Suppressed {
+                    repeated: 1,
+                    summary: summary(),
+                });
+                if !std::mem::replace(&mut state.scheduled, true) {
+                    if let Some(flusher) = flusher() {
+                        flusher.schedule(state.start + self.window, self);
+                    }
+                }
+                None
+            }
+            None => {
+                state.records.insert(message.clone(), None);
+                Some(message)
+            }
+        };
+        // Emitted outside of the lock, as the logger may call into instrumented functions.
+        drop(guard);
+        emit_summaries(closed);
+
+        message
+    }
+
+    // Closes the current window if it is over.
+    fn flush(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        let closed = match &*state {
+            Some(open) if open.start.elapsed() >= self.window => state.take().unwrap().records,
+            _ => return,
+        };
+        drop(state);
+        emit_summaries(closed);
+    }
+}
+
+fn emit_summaries(records: HashMap<String, Option<Suppressed>>) {
+    for (message, suppressed) in records {
+        if let Some(Suppressed { repeated, summary }) = suppressed {
+            summary(&format!("{message} repeated={repeated}"));
+        }
+    }
+}
+
+// The windows with suppressed records, by end, and the thread closing them. A single thread
+// serves all the callsites.
+struct Flusher {
+    windows: Mutex<BTreeMap<(Instant, u64), &'static Dedup>>,
+    changed: Condvar,
+}
+
+static FLUSHER: OnceLock<Option<Flusher>> = OnceLock::new();
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+fn flusher() -> Option<&'static Flusher> {
+    FLUSHER
+        .get_or_init(|| {
+            let spawned = std::thread::Builder::new()
+                .name("logcall-dedup".to_string())
+                .spawn(|| run(flusher().unwrap()));
+            match spawned {
+                Ok(_) => Some(Flusher {
+                    windows: Mutex::new(BTreeMap::new()),
+                    changed: Condvar::new(),
+                }),
+                Err(err) => {
+                    log::warn!("failed to spawn the logcall dedup thread: {err}");
+                    None
+                }
+            }
+        })
+        .as_ref()
+}
+
+impl Flusher {
+    fn schedule(&self, end: Instant, dedup: &'static Dedup) {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let mut windows = self.windows.lock().unwrap_or_else(|err| err.into_inner());
+        windows.insert((end, id), dedup);
+        // The thread only needs to wake up early for a new earliest window.
+        if windows.keys().next() == Some(&(end, id)) {
+            self.changed.notify_all();
+        }
+    }
+}
+
+fn run(flusher: &Flusher) {
+    let mut windows = flusher.windows.lock().unwrap_or_else(|err| err.into_inner());
+    loop {
+        let now = Instant::now();
+        let Some((&(end, id), &dedup)) = windows.iter().next() else {
+            windows = flusher
+                .changed
+                .wait(windows)
+                .unwrap_or_else(|err| err.into_inner());
+            continue;
+        };
+        if end > now {
+            windows = flusher
+                .changed
+                .wait_timeout(windows, end - now)
+                .unwrap_or_else(|err| err.into_inner())
+                .0;
+            continue;
+        }
+        windows.remove(&(end, id));
+        drop(windows);
+        // A window closed in the meantime by a record is left alone.
+        dedup.flush();
+        windows = flusher.windows.lock().unwrap_or_else(|err| err.into_inner());
+    }
+}
@@ -0,0 +1,68 @@
+use std::cell::RefCell;
+use std::fmt;
+
+use log::Level;
+
+thread_local! {
+    static QUEUE: Queue = const { Queue(RefCell::new(Vec::new())) };
+}
+
+struct Deferred {
+    level: Level,
+    target: &'static str,
+    message: String,
+}
+
+struct Queue(RefCell<Vec<Deferred>>);
+
+// Records that were never flushed are emitted when the thread exits.
+impl Drop for Queue {
+    fn drop(&mut self) {
+        emit(std::mem::take(self.0.get_mut()));
+    }
+}
+
+/// Emits the records deferred on the current thread by functions annotated with
+/// `#[logcall(defer)]`.
+///
+/// Records are queued per thread, so this only flushes the records of the calling thread;
+/// it is typically called once per frame, tick or simulation step. Records that are never
+/// flushed are emitted when the thread exits.
+pub fn flush() {
+    let records = QUEUE
+        .try_with(|queue| std::mem::take(&mut *queue.0.borrow_mut()))
+        .unwrap_or_default();
+    emit(records);
+}
+
+fn emit(records: Vec<Deferred>) {
+    if records.is_empty() {
+        return;
+    }
+
+    let logger = log::logger();
+    for record in &records {
+        logger.log(
+            &log::Record::builder()
+                .level(record.level)
+                .target(record.target)
+                .args(format_args!("{}", record.message))
+                .build(),
+        );
+    }
+    logger.flush();
+}
+
+// Renders the record now, and queues it until the next `flush`.
+#[doc(hidden)]
+pub fn defer(level: Level, target: &'static str, args: fmt::Arguments<'_>) {
+    let record = Deferred {
+        level,
+        target,
+        message: args.to_string(),
+    };
+    let mut record = Some(record);
+    let _ = QUEUE.try_with(|queue| queue.0.borrow_mut().extend(record.take()));
+    // If the thread is being torn down, emit the record right away.
+    emit(record.into_iter().collect());
+}
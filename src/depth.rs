@@ -0,0 +1,49 @@
+use std::cell::Cell;
+use std::fmt::Display;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::thread::LocalKey;
+
+// Increments the recursion depth of an instrumented function on the current thread, and
+// decrements it when dropped, even if the function panics.
+//
+// The first time the depth exceeds `max_depth` in the process, an error-level record is
+// logged, before the stack overflows and aborts the process without any diagnostic.
+#[doc(hidden)]
+pub struct DepthGuard {
+    depth: &'static LocalKey<Cell<usize>>,
+}
+
+impl DepthGuard {
+    pub fn enter(
+        depth: &'static LocalKey<Cell<usize>>,
+        reported: &'static AtomicBool,
+        max_depth: usize,
+        target: &'static str,
+        fn_name: &'static str,
+        args: &dyn Display,
+    ) -> Self {
+        let current = depth.with(|depth| {
+            let current = depth.get() + 1;
+            depth.set(current);
+            current
+        });
+        if current > max_depth && !reported.swap(true, Ordering::Relaxed) {
+            log::error!(
+                target: target,
+                "{}({}) exceeded max_depth={} on thread {:?}",
+                fn_name,
+                args,
+                max_depth,
+                std::thread::current().name().unwrap_or("<unnamed>")
+            );
+        }
+        DepthGuard { depth }
+    }
+}
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        self.depth.with(|depth| depth.set(depth.get() - 1));
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+use std::fmt::Display;
+
+use log::Level;
+
+use crate::redact::skip_literal;
+use crate::redact::skip_value;
+
+// Renders the arguments of `#[logcall(detail(trace = "full", info = "summary"))]` in full or
+// summarized, depending on the level of the record. A level takes the detail of the most
+// severe level specified at or below it, e.g. `debug` the one of `trace` and `warn` the one of
+// `info`, and is rendered in full if there is none.
+#[doc(hidden)]
+pub struct Detail<'a, T: ?Sized>(pub &'a T, pub Level, pub &'static [(Level, bool)]);
+
+impl<T: Display + ?Sized> Display for Detail<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let summary = self
+            .2
+            .iter()
+            .filter(|(level, _)| *level >= self.1)
+            .min_by_key(|(level, _)| *level)
+            .is_some_and(|(_, summary)| *summary);
+        if summary {
+            f.write_str(&summarize_args(&self.0.to_string()))
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+// `a = 1, data = [1, 2, 3], name = "alice"` => `a = 1, data = [3 items], name = "…" (5 chars)`
+fn summarize_args(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut summary = String::with_capacity(input.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let end = skip_value(bytes, i);
+        let arg = &input[i..end];
+        match arg.split_once(" = ") {
+            Some((name, value)) => {
+                summary.push_str(name);
+                summary.push_str(" = ");
+                summary.push_str(&summarize_value(value));
+            }
+            None => summary.push_str(arg),
+        }
+        summary.push_str(&input[end..(end + 1).min(bytes.len())]);
+        i = end + 1;
+    }
+    summary
+}
+
+// The length of strings and collections, and the name of structs, enum variants with fields
+// and tuples, e.g. `Batch { .. }` or `Some(..)`. Other values, such as numbers, are kept.
+fn summarize_value(value: &str) -> String {
+    let bytes = value.as_bytes();
+    match bytes.first() {
+        Some(b'"') => format!("\"…\" ({} chars)", literal_chars(value)),
+        Some(&open @ (b'[' | b'{')) => {
+            let close = if open == b'[' { ']' } else { '}' };
+            format!("{}{} items{}", open as char, count_items(bytes), close)
+        }
+        _ => match value.find(['{', '(']) {
+            Some(pos) if bytes[pos] == b'{' => format!("{}{{ .. }}", &value[..pos]),
+            Some(pos) => format!("{}(..)", &value[..pos]),
+            None => value.to_string(),
+        },
+    }
+}
+
+// The number of items in the brackets starting the value.
+fn count_items(bytes: &[u8]) -> usize {
+    let mut count = 0;
+    let mut i = 1;
+    loop {
+        while bytes.get(i) == Some(&b' ') {
+            i += 1;
+        }
+        if matches!(bytes.get(i), None | Some(b']' | b'}')) {
+            return count;
+        }
+        count += 1;
+        i = skip_value(bytes, i);
+        if bytes.get(i) != Some(&b',') {
+            return count;
+        }
+        i += 1;
+    }
+}
+
+// The number of chars of the string literal, with its escapes unescaped.
+fn literal_chars(literal: &str) -> usize {
+    let end = skip_literal(literal.as_bytes(), 0);
+    let mut chars = literal[1..end.saturating_sub(1).max(1)].chars();
+    let mut count = 0;
+    while let Some(c) = chars.next() {
+        if c == '\\' && chars.next() == Some('u') {
+            chars.by_ref().find(|&c| c == '}');
+        }
+        count += 1;
+    }
+    count
+}
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Mutex;
+
+// Per-function state of `#[logcall(diff_by = "...")]`.
+//
+// The arguments of the previous call are kept for every value of the key argument, and the
+// next call with the same key renders only the arguments that changed, as `name: old -> new`,
+// or `name.field: old -> new` for the changed fields of a struct. The key argument is always
+// rendered as is.
+#[doc(hidden)]
+pub struct ArgsDiff {
+    // The `{:#?}` renderings of the arguments of the previous call, by key.
+    previous: Mutex<Option<HashMap<String, Vec<String>>>>,
+}
+
+impl ArgsDiff {
+    pub const fn new() -> Self {
+        ArgsDiff {
+            previous: Mutex::new(None),
+        }
+    }
+
+    // Renders the input of the record. `args` holds the name and the value of every argument,
+    // in order, or no value for `self`.
+    pub fn render(&self, key: &str, args: &[(&str, Option<&dyn Debug>)]) -> String {
+        let current: Vec<String> = args
+            .iter()
+            .map(|(_, value)| value.map_or_else(String::new, |value| format!("{value:#?}")))
+            .collect();
+        let key_value = args
+            .iter()
+            .position(|(name, _)| *name == key)
+            .map(|i| current[i].clone())
+            .unwrap_or_default();
+
+        let previous = self
+            .previous
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .get_or_insert_with(HashMap::new)
+            .insert(key_value, current.clone());
+
+        let mut changes = Vec::new();
+        let mut changed = false;
+        for (i, (name, value)) in args.iter().enumerate() {
+            match (value, &previous) {
+                (None, _) => changes.push(name.to_string()),
+                (Some(_), Some(previous)) if *name != key => {
+                    let diff = diff(name, &previous[i], &current[i]);
+                    changed |= !diff.is_empty();
+                    changes.extend(diff);
+                }
+                (Some(_), _) => changes.push(format!("{name} = {}", collapse(&current[i]))),
+            }
+        }
+        if previous.is_some() && !changed {
+            changes.push("unchanged".to_string());
+        }
+        changes.join(", ")
+    }
+}
+
+impl Default for ArgsDiff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The changes of an argument, field by field if both renderings are the same struct.
+fn diff(name: &str, old: &str, new: &str) -> Vec<String> {
+    if old == new {
+        return Vec::new();
+    }
+    if let (Some((old_type, old_fields)), Some((new_type, new_fields))) =
+        (fields(old), fields(new))
+    {
+        if old_type == new_type
+            && old_fields.len() == new_fields.len()
+            && old_fields
+                .iter()
+                .zip(&new_fields)
+                .all(|((old_field, _), (new_field, _))| old_field == new_field)
+        {
+            return old_fields
+                .iter()
+                .zip(&new_fields)
+                .filter(|((_, old), (_, new))| old != new)
+                .map(|((field, old), (_, new))| format!("{name}.{field}: {old} -> {new}"))
+                .collect();
+        }
+    }
+    vec![format!("{name}: {} -> {}", collapse(old), collapse(new))]
+}
+
+// Splits the `{:#?}` rendering of a struct with named fields into its name and its fields, the
+// values of which are collapsed to a single line.
+fn fields(pretty: &str) -> Option<(&str, Vec<(&str, String)>)> {
+    let mut lines = pretty.lines();
+    let type_name = lines.next()?.strip_suffix(" {")?;
+    let mut fields: Vec<(&str, Vec<&str>)> = Vec::new();
+    for line in lines {
+        match line.strip_prefix("    ") {
+            // The continuation of a multi-line value.
+            Some(rest) if rest.starts_with(' ') || rest.starts_with(['}', ']', ')']) => {
+                fields.last_mut()?.1.push(line);
+            }
+            Some(rest) => {
+                let (field, value) = rest.split_once(": ")?;
+                fields.push((field, vec![value]));
+            }
+            None if line == "}" => {}
+            None => return None,
+        }
+    }
+    let fields = fields
+        .into_iter()
+        .map(|(field, lines)| {
+            let value = collapse(&lines.join("\n"));
+            (field, value.strip_suffix(',').map(str::to_string).unwrap_or(value))
+        })
+        .collect();
+    Some((type_name, fields))
+}
+
+// `Foo {\n    a: 1,\n}` => `Foo { a: 1 }`
+fn collapse(pretty: &str) -> String {
+    let mut collapsed = String::new();
+    for line in pretty.lines().map(str::trim) {
+        if line.starts_with(['}', ']', ')']) {
+            if collapsed.ends_with(',') {
+                collapsed.pop();
+            }
+            if line.starts_with('}') {
+                collapsed.push(' ');
+            }
+        } else if !collapsed.is_empty() && !collapsed.ends_with(['[', '(']) {
+            collapsed.push(' ');
+        }
+        collapsed.push_str(line);
+    }
+    collapsed
+}
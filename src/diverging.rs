@@ -0,0 +1,46 @@
+use std::fmt;
+use std::fmt::Debug;
+use std::time::Duration;
+use std::time::Instant;
+
+use log::Level;
+
+// Stands in for the return value of functions returning `!`.
+#[doc(hidden)]
+pub struct Diverging;
+
+impl Debug for Diverging {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("!")
+    }
+}
+
+// Spawns a thread that logs `fn(args) still running (..s elapsed)` at every interval.
+//
+// The instrumented function never returns, so the thread lives as long as the process.
+#[doc(hidden)]
+pub fn heartbeat(
+    interval: Duration,
+    level: Level,
+    target: &'static str,
+    fn_name: &'static str,
+    args: String,
+) {
+    let start = Instant::now();
+    let spawned = std::thread::Builder::new()
+        .name("logcall-heartbeat".to_string())
+        .spawn(move || loop {
+            std::thread::sleep(interval);
+            log::log!(
+                target: target,
+                level,
+                "{}({}) still running ({:?} elapsed)",
+                fn_name,
+                args,
+                start.elapsed()
+            );
+        });
+    if let Err(err) = spawned {
+        log::warn!("failed to spawn the heartbeat thread of {fn_name}: {err}");
+    }
+}
@@ -0,0 +1,43 @@
+// Emits the record as a `tracing` event for `#[logcall(backend = "tracing")]`.
+//
+// The level of an event has to be known at compile time, as it is part of the metadata of its
+// callsite, so the `log` level, which is dynamic when boosted by `after_error`, is matched
+// against each of the `tracing` levels.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __event {
+    ($target:expr, $level:expr, $message:expr) => {
+        match $level {
+            $crate::__private::log::Level::Error => $crate::__private::tracing::event!(target: $target, $crate::__private::tracing::Level::ERROR, "{}", $message),
+            $crate::__private::log::Level::Warn => $crate::__private::tracing::event!(target: $target, $crate::__private::tracing::Level::WARN, "{}", $message),
+            $crate::__private::log::Level::Info => $crate::__private::tracing::event!(target: $target, $crate::__private::tracing::Level::INFO, "{}", $message),
+            $crate::__private::log::Level::Debug => $crate::__private::tracing::event!(target: $target, $crate::__private::tracing::Level::DEBUG, "{}", $message),
+            $crate::__private::log::Level::Trace => $crate::__private::tracing::event!(target: $target, $crate::__private::tracing::Level::TRACE, "{}", $message),
+        }
+    };
+    // With the fields of the `valuable` feature.
+    ($target:expr, $level:expr, $message:expr, { $($field:ident = $value:expr),* }) => {
+        match $level {
+            $crate::__private::log::Level::Error => $crate::__private::tracing::event!(target: $target, $crate::__private::tracing::Level::ERROR, $($field = $value,)* "{}", $message),
+            $crate::__private::log::Level::Warn => $crate::__private::tracing::event!(target: $target, $crate::__private::tracing::Level::WARN, $($field = $value,)* "{}", $message),
+            $crate::__private::log::Level::Info => $crate::__private::tracing::event!(target: $target, $crate::__private::tracing::Level::INFO, $($field = $value,)* "{}", $message),
+            $crate::__private::log::Level::Debug => $crate::__private::tracing::event!(target: $target, $crate::__private::tracing::Level::DEBUG, $($field = $value,)* "{}", $message),
+            $crate::__private::log::Level::Trace => $crate::__private::tracing::event!(target: $target, $crate::__private::tracing::Level::TRACE, $($field = $value,)* "{}", $message),
+        }
+    };
+}
+
+// Whether an event at the level would be recorded, like `log::log_enabled!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __event_enabled {
+    ($target:expr, $level:expr) => {
+        match $level {
+            $crate::__private::log::Level::Error => $crate::__private::tracing::enabled!(target: $target, $crate::__private::tracing::Level::ERROR),
+            $crate::__private::log::Level::Warn => $crate::__private::tracing::enabled!(target: $target, $crate::__private::tracing::Level::WARN),
+            $crate::__private::log::Level::Info => $crate::__private::tracing::enabled!(target: $target, $crate::__private::tracing::Level::INFO),
+            $crate::__private::log::Level::Debug => $crate::__private::tracing::enabled!(target: $target, $crate::__private::tracing::Level::DEBUG),
+            $crate::__private::log::Level::Trace => $crate::__private::tracing::enabled!(target: $target, $crate::__private::tracing::Level::TRACE),
+        }
+    };
+}
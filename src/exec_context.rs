@@ -0,0 +1,90 @@
+use std::cell::Cell;
+use std::fmt;
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context as TaskContext;
+use std::task::Poll;
+
+thread_local! {
+    // The number of instrumented async functions being polled on this thread.
+    static POLLING: Cell<usize> = const { Cell::new(0) };
+}
+
+// Marks the thread as running async code while the body of an instrumented async function is
+// polled, so that synchronous functions called from it can be attributed to an executor.
+#[doc(hidden)]
+pub struct Polling<F> {
+    future: F,
+}
+
+impl<F> Polling<F> {
+    pub fn new(future: F) -> Self {
+        Polling { future }
+    }
+}
+
+impl<F: Future> Future for Polling<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<F::Output> {
+        // SAFETY: `future` is structurally pinned and never moved out of `self`.
+        let future = unsafe { self.map_unchecked_mut(|this| &mut this.future) };
+        let _guard = PollingGuard::enter();
+        future.poll(cx)
+    }
+}
+
+// Marks the thread as running async code until dropped, so that the counter is restored even
+// if the poll panics. Also held by instrumented async functions while their record is logged.
+#[doc(hidden)]
+pub struct PollingGuard;
+
+impl PollingGuard {
+    pub fn enter() -> Self {
+        POLLING.with(|polling| polling.set(polling.get() + 1));
+        PollingGuard
+    }
+}
+
+impl Drop for PollingGuard {
+    fn drop(&mut self) {
+        POLLING.with(|polling| polling.set(polling.get() - 1));
+    }
+}
+
+// Renders ` exec=worker`, ` exec=blocking`, ` exec=main` or ` exec=thread`, depending on where
+// the instrumented function runs:
+//
+// - `worker`: inside an instrumented async function, i.e. on an executor thread.
+// - `blocking`: in a tokio runtime outside of async code, e.g. in `spawn_blocking`. Only detected
+//   with the `tokio` feature.
+// - `main`: on the main thread, outside of async code.
+// - `thread`: on any other thread.
+#[doc(hidden)]
+pub struct ExecContext;
+
+impl Display for ExecContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let exec = if POLLING.with(Cell::get) > 0 {
+            "worker"
+        } else if std::thread::current().name() == Some("main") {
+            "main"
+        } else if in_tokio_runtime() {
+            "blocking"
+        } else {
+            "thread"
+        };
+        write!(f, " exec={exec}")
+    }
+}
+
+#[cfg(feature = "tokio")]
+fn in_tokio_runtime() -> bool {
+    tokio::runtime::Handle::try_current().is_ok()
+}
+
+#[cfg(not(feature = "tokio"))]
+fn in_tokio_runtime() -> bool {
+    false
+}
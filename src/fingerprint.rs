@@ -0,0 +1,58 @@
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Write;
+use std::hash::Hasher;
+
+use crate::hasher::hash;
+use crate::hasher::HashWriter;
+
+// Renders ` fingerprint=3f2a9c1e` for an `Err` with `#[logcall(err_fingerprint)]`, and nothing
+// for an `Ok`.
+//
+// The fingerprint hashes the type name and the `Display` text of the error with FNV-1a, which,
+// unlike the `std` hashers, is specified, so identical failures get the same fingerprint on
+// every host running the same build. `set_hasher` replaces it, e.g. with a keyed hasher.
+#[doc(hidden)]
+pub struct Fingerprint<'a, T, E>(pub &'a Result<T, E>);
+
+impl<T, E: Display> Display for Fingerprint<'_, T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                let hash = hash(Fnv1a::new(), |hasher| {
+                    hasher.write(std::any::type_name::<E>().as_bytes());
+                    hasher.write(&[0]);
+                    let _ = write!(HashWriter(hasher), "{err}");
+                });
+                write!(f, " fingerprint={:08x}", fold(hash))
+            }
+        }
+    }
+}
+
+pub(crate) struct Fnv1a(u64);
+
+impl Fnv1a {
+    pub(crate) fn new() -> Self {
+        Fnv1a(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+// The hash folded into 32 bits, which is enough to tell records apart.
+pub(crate) fn fold(hash: u64) -> u32 {
+    (hash ^ (hash >> 32)) as u32
+}
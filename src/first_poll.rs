@@ -0,0 +1,22 @@
+use std::fmt;
+use std::fmt::Display;
+use std::time::Duration;
+
+// Renders ` first_poll_delay=1.234ms` for `#[logcall(first_poll_delay)]`: the time from the
+// creation of the future of an async function until its first poll, which the executor may
+// delay under load.
+#[doc(hidden)]
+pub struct FirstPollDelay(pub Duration);
+
+impl Display for FirstPollDelay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, " first_poll_delay={:?}", self.0)
+    }
+}
+
+// Bounds the future returned by an `async fn` desugared for `first_poll_delay`, so that it
+// captures the lifetimes of the inputs, as the future of the `async fn` does.
+#[doc(hidden)]
+pub trait Captures<T: ?Sized> {}
+
+impl<T: ?Sized, U: ?Sized> Captures<U> for T {}
@@ -0,0 +1,13 @@
+use std::fmt;
+use std::fmt::Debug;
+
+// Renders a callable argument, which doesn't implement `Debug`, as its type name and arity,
+// e.g. `<app::main::{{closure}} arity=1>` or `<fn(u32) -> u32 arity=1>`.
+#[doc(hidden)]
+pub struct FnName<'a, T: ?Sized, const ARITY: usize>(pub &'a T);
+
+impl<T: ?Sized, const ARITY: usize> Debug for FnName<'_, T, ARITY> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{} arity={}>", std::any::type_name::<T>(), ARITY)
+    }
+}
@@ -0,0 +1,58 @@
+use std::fmt;
+use std::hash::BuildHasher;
+use std::hash::Hasher;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::RwLock;
+
+type BuildDynHasher = Box<dyn Fn() -> Box<dyn Hasher> + Send + Sync>;
+
+static HASHER: RwLock<Option<BuildDynHasher>> = RwLock::new(None);
+// Whether a hasher is installed, so that the calls don't take the lock otherwise.
+static HAS_HASHER: AtomicBool = AtomicBool::new(false);
+
+/// Installs the hasher used for the values hashed by the records, i.e. the keys of
+/// `#[logcall(sample_by = "...")]`, the `err_fingerprint` fingerprints and the `budget` hashes,
+/// in place of the fixed hashers.
+///
+/// A keyed hasher, e.g. SipHash with a per-deployment key, keeps the hashes of low-cardinality
+/// values from being reversed by a dictionary attack. The hashes stay comparable across hosts
+/// as long as they share the key.
+///
+/// ```
+/// logcall::set_hasher(std::collections::hash_map::RandomState::new());
+/// ```
+pub fn set_hasher<S>(build_hasher: S)
+where
+    S: BuildHasher + Send + Sync + 'static,
+    S::Hasher: 'static,
+{
+    *HASHER.write().unwrap_or_else(|err| err.into_inner()) =
+        Some(Box::new(move || Box::new(build_hasher.build_hasher())));
+    HAS_HASHER.store(true, Ordering::Relaxed);
+}
+
+// Hashes the values written by `write` with the installed hasher, or `default` if there is none.
+pub(crate) fn hash(default: impl Hasher, write: impl FnOnce(&mut dyn Hasher)) -> u64 {
+    if HAS_HASHER.load(Ordering::Relaxed) {
+        let build_hasher = HASHER.read().unwrap_or_else(|err| err.into_inner());
+        if let Some(build_hasher) = &*build_hasher {
+            let mut hasher = build_hasher();
+            write(&mut *hasher);
+            return hasher.finish();
+        }
+    }
+    let mut hasher = default;
+    write(&mut hasher);
+    hasher.finish()
+}
+
+// Hashes the text written with `write!`.
+pub(crate) struct HashWriter<'a>(pub &'a mut dyn Hasher);
+
+impl fmt::Write for HashWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write(s.as_bytes());
+        Ok(())
+    }
+}
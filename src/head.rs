@@ -0,0 +1,104 @@
+use std::fmt;
+use std::fmt::Debug;
+
+use crate::redact::skip_literal;
+
+// Renders an argument listed in `#[logcall(head(items = 5))]` with the collections in it, i.e.
+// slices, `Vec`s, sets and maps, at any depth, cut after their first items, e.g.
+// `[1, 2, 3, 4, 5, … (+995 more)]`.
+#[doc(hidden)]
+pub struct Head<'a, T: ?Sized>(pub &'a T, pub usize);
+
+impl<T: Debug + ?Sized> Debug for Head<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&head_debug(&format!("{:?}", self.0), self.1))
+    }
+}
+
+// Cuts the collections of a `Debug` rendering after `items` items. The brackets of a struct,
+// which follow its name, and of a tuple are left as is.
+fn head_debug(debug: &str, items: usize) -> String {
+    let bytes = debug.as_bytes();
+    let mut head = Vec::with_capacity(debug.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        i = head_item(bytes, i, items, &mut head);
+        if i < bytes.len() {
+            head.push(bytes[i]);
+            i += 1;
+        }
+    }
+    // Only cut at ASCII bytes outside of literals.
+    String::from_utf8(head).unwrap_or_else(|_| debug.to_string())
+}
+
+// Copies the item at `start` to `head`, up to the next `,` or closing bracket outside of nested
+// brackets and literals, and returns its end.
+fn head_item(bytes: &[u8], start: usize, items: usize, head: &mut Vec<u8>) -> usize {
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' | b'\'' => {
+                let end = skip_literal(bytes, i);
+                head.extend_from_slice(&bytes[i..end]);
+                i = end;
+            }
+            b'[' | b'{' | b'(' => i = head_brackets(bytes, i, items, head),
+            b',' | b']' | b'}' | b')' => return i,
+            byte => {
+                head.push(byte);
+                i += 1;
+            }
+        }
+    }
+    i
+}
+
+// Copies the brackets at `open` to `head`, with the items past the first `items` replaced by
+// their count if they are a collection, and returns their end.
+fn head_brackets(bytes: &[u8], open: usize, items: usize, head: &mut Vec<u8>) -> usize {
+    let collection = match bytes[open] {
+        b'[' => true,
+        b'{' => !(open >= 2 && bytes[open - 1] == b' ' && is_ident_byte(bytes[open - 2])),
+        _ => false,
+    };
+    head.push(bytes[open]);
+    let mut i = open + 1;
+    let mut count = 0;
+    loop {
+        if i >= bytes.len() {
+            return i;
+        }
+        if matches!(bytes[i], b']' | b'}' | b')') {
+            if collection && count > items {
+                write_more(head, count - items);
+            }
+            head.push(bytes[i]);
+            return i + 1;
+        }
+        count += 1;
+        if collection && count > items {
+            i = head_item(bytes, i, items, &mut Vec::new());
+        } else {
+            i = head_item(bytes, i, items, head);
+        }
+        if bytes.get(i) == Some(&b',') {
+            if !collection || count < items {
+                head.push(b',');
+            }
+            i += 1;
+        }
+    }
+}
+
+// `, … (+995 more)`, or `… (+995 more)` if no item is kept.
+fn write_more(head: &mut Vec<u8>, more: usize) {
+    if !matches!(head.last(), Some(b'[' | b'{')) {
+        head.extend_from_slice(b", ");
+    }
+    head.extend_from_slice(format!("… (+{more} more)").as_bytes());
+}
+
+fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
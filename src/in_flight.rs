@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Condvar;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+
+// A call that is warned about unless it completes before its deadline.
+struct Watched {
+    threshold: Duration,
+    target: &'static str,
+    fn_name: &'static str,
+    args: String,
+}
+
+// The calls being watched by deadline, and the thread logging them once their deadline passes.
+// A single thread serves all the calls, however many are in flight.
+struct Watchdog {
+    calls: Mutex<BTreeMap<(Instant, u64), Watched>>,
+    changed: Condvar,
+}
+
+static WATCHDOG: OnceLock<Option<Watchdog>> = OnceLock::new();
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+fn watchdog() -> Option<&'static Watchdog> {
+    WATCHDOG
+        .get_or_init(|| {
+            let spawned = std::thread::Builder::new()
+                .name("logcall-watchdog".to_string())
+                .spawn(|| run(watchdog().unwrap()));
+            match spawned {
+                Ok(_) => Some(Watchdog {
+                    calls: Mutex::new(BTreeMap::new()),
+                    changed: Condvar::new(),
+                }),
+                Err(err) => {
+                    log::warn!("failed to spawn the logcall watchdog thread: {err}");
+                    None
+                }
+            }
+        })
+        .as_ref()
+}
+
+fn run(watchdog: &Watchdog) {
+    let mut calls = watchdog.calls.lock().unwrap_or_else(|err| err.into_inner());
+    loop {
+        let now = Instant::now();
+        let Some(&(deadline, id)) = calls.keys().next() else {
+            calls = watchdog
+                .changed
+                .wait(calls)
+                .unwrap_or_else(|err| err.into_inner());
+            continue;
+        };
+        if deadline > now {
+            calls = watchdog
+                .changed
+                .wait_timeout(calls, deadline - now)
+                .unwrap_or_else(|err| err.into_inner())
+                .0;
+            continue;
+        }
+        let call = calls.remove(&(deadline, id)).unwrap();
+        // Logged outside of the lock, as the logger may call into instrumented functions.
+        drop(calls);
+        log::warn!(
+            target: call.target,
+            "{}({}) still in flight after {:?}",
+            call.fn_name,
+            call.args,
+            call.threshold
+        );
+        calls = watchdog.calls.lock().unwrap_or_else(|err| err.into_inner());
+    }
+}
+
+// Logs `fn(args) still in flight after 30s` at the `warn` level if the call hasn't completed,
+// nor been dropped, when the threshold passes, to identify the hung calls, which never log
+// their record.
+//
+// Nothing is watched if the `warn` level is disabled for the target.
+#[doc(hidden)]
+pub struct InFlight {
+    key: Option<(Instant, u64)>,
+}
+
+impl InFlight {
+    pub fn watch(
+        threshold: Duration,
+        target: &'static str,
+        fn_name: &'static str,
+        args: &dyn Display,
+    ) -> Self {
+        if !log::log_enabled!(target: target, log::Level::Warn) {
+            return InFlight { key: None };
+        }
+        let Some(watchdog) = watchdog() else {
+            return InFlight { key: None };
+        };
+        let key = (
+            Instant::now() + threshold,
+            NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        );
+        let call = Watched {
+            threshold,
+            target,
+            fn_name,
+            args: args.to_string(),
+        };
+        let mut calls = watchdog.calls.lock().unwrap_or_else(|err| err.into_inner());
+        calls.insert(key, call);
+        // The thread only needs to wake up early for a new earliest deadline.
+        if calls.keys().next() == Some(&key) {
+            watchdog.changed.notify_one();
+        }
+        InFlight { key: Some(key) }
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        if let (Some(key), Some(watchdog)) = (self.key, watchdog()) {
+            let mut calls = watchdog.calls.lock().unwrap_or_else(|err| err.into_inner());
+            calls.remove(&key);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Request ID middleware for `actix-web`.
+//!
+//! ```ignore
+//! let app = actix_web::App::new()
+//!     .wrap(logcall::integration::actix::RequestId::new())
+//!     .route("/", actix_web::web::get().to(handler));
+//! ```
+
+use std::future::ready;
+use std::future::Ready;
+
+use actix_web::dev::forward_ready;
+use actix_web::dev::Service;
+use actix_web::dev::ServiceRequest;
+use actix_web::dev::ServiceResponse;
+use actix_web::dev::Transform;
+use actix_web::http::header::HeaderName;
+use actix_web::Error;
+
+use crate::Context;
+use crate::Scoped;
+
+use super::REQUEST_ID_HEADER;
+use super::TRACEPARENT_HEADER;
+
+/// A middleware that installs the request ID and the trace ID of every request into the logcall
+/// [`Context`].
+#[derive(Debug, Clone)]
+pub struct RequestId {
+    header: HeaderName,
+}
+
+impl RequestId {
+    /// Reads the request ID from the `x-request-id` header.
+    pub fn new() -> Self {
+        Self::with_header(HeaderName::from_static(REQUEST_ID_HEADER))
+    }
+
+    /// Reads the request ID from the given header.
+    pub fn with_header(header: HeaderName) -> Self {
+        RequestId { header }
+    }
+}
+
+impl Default for RequestId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequestId
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestIdMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestIdMiddleware {
+            service,
+            header: self.header.clone(),
+        }))
+    }
+}
+
+/// The service created by [`RequestId`].
+#[derive(Debug)]
+pub struct RequestIdMiddleware<S> {
+    service: S,
+    header: HeaderName,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestIdMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Scoped<S::Future>;
+
+    forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let mut context = Context::new();
+        if let Some(request_id) = request
+            .headers()
+            .get(&self.header)
+            .and_then(|value| value.to_str().ok())
+        {
+            context = context.with_request_id(request_id);
+        }
+        if let Some(traceparent) = request
+            .headers()
+            .get(TRACEPARENT_HEADER)
+            .and_then(|value| value.to_str().ok())
+        {
+            context = context.with_traceparent(traceparent);
+        }
+        context.scope(self.service.call(request))
+    }
+}
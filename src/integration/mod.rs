@@ -0,0 +1,17 @@
+//! Helpers that install a [`Context`](crate::Context) for every request handled by a web
+//! framework, so that the records logged while handling the request carry its request ID.
+//!
+//! The request ID is read from the `x-request-id` header by default. Requests without the
+//! header are handled without a request ID. The trace ID and the span ID of the W3C
+//! `traceparent` header are installed as well, if the request has a valid one.
+
+#[cfg(feature = "actix")]
+pub mod actix;
+#[cfg(feature = "tower")]
+pub mod tower;
+
+/// The header the request ID is read from by default.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// The W3C Trace Context header the trace ID is read from.
+pub const TRACEPARENT_HEADER: &str = "traceparent";
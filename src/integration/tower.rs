@@ -0,0 +1,96 @@
+//! Request ID middleware for `tower` based frameworks such as `axum`.
+//!
+//! ```ignore
+//! let app = axum::Router::new()
+//!     .route("/", axum::routing::get(handler))
+//!     .layer(logcall::integration::tower::RequestIdLayer::new());
+//! ```
+
+use std::task::Context as TaskContext;
+use std::task::Poll;
+
+use http::HeaderName;
+use http::Request;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::Context;
+use crate::Scoped;
+
+use super::REQUEST_ID_HEADER;
+use super::TRACEPARENT_HEADER;
+
+/// A [`Layer`] that installs the request ID and the trace ID of every request into the logcall
+/// [`Context`].
+#[derive(Debug, Clone)]
+pub struct RequestIdLayer {
+    header: HeaderName,
+}
+
+impl RequestIdLayer {
+    /// Reads the request ID from the `x-request-id` header.
+    pub fn new() -> Self {
+        Self::with_header(HeaderName::from_static(REQUEST_ID_HEADER))
+    }
+
+    /// Reads the request ID from the given header.
+    pub fn with_header(header: HeaderName) -> Self {
+        RequestIdLayer { header }
+    }
+}
+
+impl Default for RequestIdLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestId<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestId {
+            inner,
+            header: self.header.clone(),
+        }
+    }
+}
+
+/// The service created by [`RequestIdLayer`].
+#[derive(Debug, Clone)]
+pub struct RequestId<S> {
+    inner: S,
+    header: HeaderName,
+}
+
+impl<S, B> Service<Request<B>> for RequestId<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Scoped<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let mut context = Context::new();
+        if let Some(request_id) = request
+            .headers()
+            .get(&self.header)
+            .and_then(|value| value.to_str().ok())
+        {
+            context = context.with_request_id(request_id);
+        }
+        if let Some(traceparent) = request
+            .headers()
+            .get(TRACEPARENT_HEADER)
+            .and_then(|value| value.to_str().ok())
+        {
+            context = context.with_traceparent(traceparent);
+        }
+        context.scope(self.inner.call(request))
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+use std::fmt::Display;
+use std::io;
+
+// Extracts the `std::io::Error` of a return value, if any, using autoref specialization:
+// `(&&IoErrorProbe(&value)).io_error_fields()` resolves to `IoErrorFieldsKind` when the
+// value is a `Result<_, io::Error>`, and to `NoIoErrorFieldsKind` otherwise.
+#[doc(hidden)]
+pub struct IoErrorProbe<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait IoErrorFieldsKind<'a> {
+    fn io_error_fields(&self) -> IoErrorFields<'a>;
+}
+
+impl<'a, T> IoErrorFieldsKind<'a> for &IoErrorProbe<'a, Result<T, io::Error>> {
+    fn io_error_fields(&self) -> IoErrorFields<'a> {
+        IoErrorFields(self.0.as_ref().err())
+    }
+}
+
+impl<'a> IoErrorFieldsKind<'a> for &IoErrorProbe<'a, io::Error> {
+    fn io_error_fields(&self) -> IoErrorFields<'a> {
+        IoErrorFields(Some(self.0))
+    }
+}
+
+#[doc(hidden)]
+pub trait NoIoErrorFieldsKind<'a> {
+    fn io_error_fields(&self) -> IoErrorFields<'a>;
+}
+
+impl<'a, T: ?Sized> NoIoErrorFieldsKind<'a> for IoErrorProbe<'a, T> {
+    fn io_error_fields(&self) -> IoErrorFields<'a> {
+        IoErrorFields(None)
+    }
+}
+
+// Renders ` kind=NotFound errno=2 message="No such file or directory"` for an `io::Error`,
+// and nothing otherwise.
+#[doc(hidden)]
+pub struct IoErrorFields<'a>(Option<&'a io::Error>);
+
+impl Display for IoErrorFields<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(err) = self.0 else {
+            return Ok(());
+        };
+
+        write!(f, " kind={:?}", err.kind())?;
+        let message = err.to_string();
+        match err.raw_os_error() {
+            Some(errno) => {
+                // The `Display` of OS errors ends with a redundant ` (os error N)`.
+                let suffix = format!(" (os error {errno})");
+                let message = message.strip_suffix(&suffix).unwrap_or(&message);
+                write!(f, " errno={errno} message={message:?}")
+            }
+            None => write!(f, " message={message:?}"),
+        }
+    }
+}
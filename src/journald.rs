@@ -0,0 +1,88 @@
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Display;
+
+use log::Level;
+
+// Emits the record with the journal fields of `#[logcall(journald)]` attached as key-values.
+#[doc(hidden)]
+#[allow(clippy::too_many_arguments)]
+pub fn journald(
+    level: Level,
+    target: &'static str,
+    fn_name: &'static str,
+    fields: &[(&'static str, String)],
+    input: &dyn Display,
+    ret: &dyn Debug,
+    outcome: Option<&'static str>,
+    args: fmt::Arguments<'_>,
+) {
+    let source = Fields {
+        level,
+        fn_name,
+        fields,
+        input,
+        ret,
+        outcome,
+    };
+    log::logger().log(
+        &log::Record::builder()
+            .level(level)
+            .target(target)
+            .args(args)
+            .key_values(&source)
+            .build(),
+    );
+}
+
+// Maps a level to the syslog priority used by systemd-journald. `log` has no level above
+// `error`, so `emerg`, `alert` and `crit` are never produced.
+fn priority(level: Level) -> u8 {
+    match level {
+        Level::Error => 3,
+        Level::Warn => 4,
+        Level::Info => 6,
+        Level::Debug | Level::Trace => 7,
+    }
+}
+
+struct Fields<'a> {
+    level: Level,
+    fn_name: &'static str,
+    fields: &'a [(&'static str, String)],
+    input: &'a dyn Display,
+    ret: &'a dyn Debug,
+    outcome: Option<&'static str>,
+}
+
+impl log::kv::Source for Fields<'_> {
+    fn visit<'kvs>(
+        &'kvs self,
+        visitor: &mut dyn log::kv::VisitSource<'kvs>,
+    ) -> Result<(), log::kv::Error> {
+        use log::kv::Key;
+        use log::kv::Value;
+
+        visitor.visit_pair(Key::from_str("PRIORITY"), Value::from(priority(self.level)))?;
+        visitor.visit_pair(Key::from_str("CODE_FUNC"), Value::from(self.fn_name))?;
+        visitor.visit_pair(Key::from_str("FN_NAME"), Value::from(self.fn_name))?;
+        visitor.visit_pair(Key::from_str("ARGS"), Value::from_dyn_display(self.input))?;
+        for (name, value) in self.fields {
+            visitor.visit_pair(Key::from_str(name), Value::from(value.as_str()))?;
+        }
+        visitor.visit_pair(Key::from_str("RET"), Value::from_dyn_debug(self.ret))?;
+        match self.outcome {
+            Some(outcome) => visitor.visit_pair(Key::from_str("RESULT"), Value::from(outcome)),
+            None => Ok(()),
+        }
+    }
+}
+
+// Emits the record with the `journald` feature.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __journald {
+    ($($args:expr),* $(,)?) => {
+        $crate::__private::journald($($args),*)
+    };
+}
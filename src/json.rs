@@ -0,0 +1,198 @@
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::redact::redact_debug;
+use crate::redact::REDACTED;
+
+// Selects how a value is rendered by `#[logcall(format = "json")]`, using autoref
+// specialization: `(&&JsonProbe(&value)).to_json()` resolves to `SerializeKind`, which
+// serializes the value with `serde_json`, when it implements `Serialize`, and to `DebugKind`,
+// which renders its `Debug` as a JSON string, otherwise.
+#[doc(hidden)]
+pub struct JsonProbe<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait SerializeKind {
+    fn to_json(&self) -> String;
+}
+
+#[doc(hidden)]
+pub trait DebugKind {
+    fn to_json(&self) -> String;
+}
+
+impl<T: Serialize + ?Sized> SerializeKind for &JsonProbe<'_, T> {
+    fn to_json(&self) -> String {
+        serde_json::to_string(self.0).unwrap_or_else(|err| debug_string(&err))
+    }
+}
+
+impl<T: Debug + ?Sized> DebugKind for JsonProbe<'_, T> {
+    fn to_json(&self) -> String {
+        debug_string(&self.0)
+    }
+}
+
+fn debug_string(value: &dyn Debug) -> String {
+    serde_json::Value::String(format!("{value:?}")).to_string()
+}
+
+// Renders `{"a":1,"b":"x"}` from the names and the JSON renderings of the arguments.
+#[doc(hidden)]
+pub fn json_args(args: &[(&str, String)]) -> String {
+    let mut object = String::from("{");
+    for (i, (name, value)) in args.iter().enumerate() {
+        if i > 0 {
+            object.push(',');
+        }
+        object.push_str(&serde_json::Value::from(*name).to_string());
+        object.push(':');
+        object.push_str(value);
+    }
+    object.push('}');
+    object
+}
+
+// Masks the values of the fields of `#[logcall(ret_redact(...))]` in the JSON rendering of a
+// return value, at any depth. The values rendered by their `Debug` are masked as in the text
+// format.
+#[doc(hidden)]
+pub fn redact_json(json: String, fields: &[&str]) -> String {
+    if fields.is_empty() {
+        return json;
+    }
+    match serde_json::from_str(&json) {
+        Ok(serde_json::Value::String(debug)) => {
+            serde_json::Value::String(redact_debug(&debug, fields)).to_string()
+        }
+        Ok(mut value) => {
+            redact_value(&mut value, fields);
+            value.to_string()
+        }
+        Err(_) => json,
+    }
+}
+
+fn redact_value(value: &mut serde_json::Value, fields: &[&str]) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (key, value) in object {
+                if fields.contains(&key.as_str()) {
+                    *value = serde_json::Value::from(REDACTED);
+                } else {
+                    redact_value(value, fields);
+                }
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for value in values {
+                redact_value(value, fields);
+            }
+        }
+        _ => {}
+    }
+}
+
+// Renders the record of `#[logcall(format = "json")]`:
+// `{"fn":"app::add","args":{"a":1,"b":2},"ret":3}`
+//
+// The fields appended to the records in the text format, e.g. ` request_id=abc`, are kept as
+// a single string under `fields`, if any.
+#[doc(hidden)]
+pub struct JsonRecord<'a, F> {
+    pub fn_name: &'a str,
+    pub args: &'a str,
+    pub ret: String,
+    pub fields: F,
+}
+
+impl<F: Display> Display for JsonRecord<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{\"fn\":{},\"args\":{},\"ret\":{}",
+            serde_json::Value::from(self.fn_name),
+            self.args,
+            self.ret
+        )?;
+        let fields = self.fields.to_string();
+        let fields = fields.trim_start();
+        if !fields.is_empty() {
+            write!(f, ",\"fields\":{}", serde_json::Value::from(fields))?;
+        }
+        f.write_str("}")
+    }
+}
+
+// Renders the arguments of `#[logcall(format = "json")]` as a JSON object.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __json_args {
+    ($($field:tt),*) => {
+        $crate::__private::json_args(&[$($crate::__json_field! $field),*])
+    };
+}
+
+// A field of the arguments of `#[logcall(format = "json")]`, or a `group` of them.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __json_field {
+    ($name:expr, [$($field:tt),*]) => {
+        ($name, $crate::__json_args!($($field),*))
+    };
+    ($name:expr, $value:expr) => {
+        ($name, $crate::__json_value!($value))
+    };
+}
+
+// Renders the record of `#[logcall(format = "json")]`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __json_record {
+    ($fn_name:expr, $args:expr, $ret:expr, $redact:expr, $fields:expr) => {
+        $crate::__private::JsonRecord {
+            fn_name: $fn_name,
+            args: $args,
+            ret: $crate::__private::redact_json($crate::__json_value!($ret), $redact),
+            fields: $fields,
+        }
+    };
+}
+
+// The return value of `#[logcall(format = "json")]` as carried by the events of `otel` and
+// `fastrace`: its redacted JSON rendering, which doesn't require `Debug`. It is only rendered
+// if the event is.
+#[doc(hidden)]
+pub struct JsonRet<F>(pub F);
+
+impl<F: Fn() -> String> Debug for JsonRet<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&(self.0)())
+    }
+}
+
+// Renders the return value of `#[logcall(format = "json")]` for the events of `otel` and
+// `fastrace`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __json_ret {
+    ($ret:expr, $redact:expr) => {
+        $crate::__private::JsonRet(|| {
+            $crate::__private::redact_json($crate::__json_value!($ret), $redact)
+        })
+    };
+}
+
+// Renders a value as JSON for `#[logcall(format = "json")]`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __json_value {
+    ($value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__private::{DebugKind as _, SerializeKind as _};
+        (&&$crate::__private::JsonProbe(&$value)).to_json()
+    }};
+}
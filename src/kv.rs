@@ -0,0 +1,65 @@
+use std::fmt;
+use std::fmt::Debug;
+use std::time::Instant;
+
+use log::kv::Key;
+use log::kv::Source;
+use log::kv::Value;
+use log::kv::VisitSource;
+use log::Level;
+
+// Emits the record with the function name, the arguments and the return value as key-values,
+// with the `kv` feature. The message is reduced to `call`, followed by the extra fields, e.g.
+// the context, that have no key of their own.
+//
+// The `outcome`, `err` for the `Err` of a `Result` and `ok` otherwise, and the `duration_ms` of
+// the call are always recorded, so error rates and latencies can be queried without parsing
+// the values.
+#[doc(hidden)]
+#[allow(clippy::too_many_arguments)]
+pub fn log_kv(
+    level: Level,
+    target: &'static str,
+    fn_name: &'static str,
+    fields: &[(&'static str, String)],
+    ret: &dyn Debug,
+    outcome: Option<&'static str>,
+    start: Instant,
+    args: fmt::Arguments<'_>,
+) {
+    let source = Fields {
+        fn_name,
+        fields,
+        ret,
+        outcome: outcome.unwrap_or("ok"),
+        duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+    };
+    log::logger().log(
+        &log::Record::builder()
+            .level(level)
+            .target(target)
+            .args(args)
+            .key_values(&source)
+            .build(),
+    );
+}
+
+struct Fields<'a> {
+    fn_name: &'static str,
+    fields: &'a [(&'static str, String)],
+    ret: &'a dyn Debug,
+    outcome: &'static str,
+    duration_ms: f64,
+}
+
+impl Source for Fields<'_> {
+    fn visit<'kvs>(&'kvs self, visitor: &mut dyn VisitSource<'kvs>) -> Result<(), log::kv::Error> {
+        visitor.visit_pair(Key::from_str("fn"), Value::from(self.fn_name))?;
+        for (name, value) in self.fields {
+            visitor.visit_pair(Key::from_str(name), Value::from(value.as_str()))?;
+        }
+        visitor.visit_pair(Key::from_str("ret"), Value::from_dyn_debug(self.ret))?;
+        visitor.visit_pair(Key::from_str("outcome"), Value::from(self.outcome))?;
+        visitor.visit_pair(Key::from_str("duration_ms"), Value::from(self.duration_ms))
+    }
+}
@@ -0,0 +1,128 @@
+use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::OnceLock;
+use std::sync::RwLock;
+
+use log::Level;
+
+// The overrides set with `set_level_override`, by path.
+static OVERRIDES: RwLock<Vec<(String, Level)>> = RwLock::new(Vec::new());
+// Whether there is any override, so that the calls don't take the lock otherwise.
+static HAS_OVERRIDES: AtomicBool = AtomicBool::new(false);
+
+/// Overrides the level of the records of the instrumented functions under `path`, e.g.
+/// `my_app::db` for the functions of a module or `my_app::db::query` for a single function, or
+/// `""` for every function.
+///
+/// The override takes precedence over the attributes, `#[logcall::module_defaults]` and the
+/// `LOGCALL_LEVEL` environment variable. If several overrides apply to a function, the one with
+/// the longest path wins. The records of `Err` results keep their level, and the escalations of
+/// `slow`, `elapsed_levels` and `after_error` still apply to the overridden level.
+pub fn set_level_override(path: &str, level: Level) {
+    let mut overrides = OVERRIDES.write().unwrap_or_else(|err| err.into_inner());
+    overrides.retain(|(existing, _)| existing != path);
+    overrides.push((path.to_string(), level));
+    HAS_OVERRIDES.store(true, Ordering::Relaxed);
+}
+
+/// Removes the override set for `path` by [`set_level_override`], if any.
+pub fn remove_level_override(path: &str) {
+    let mut overrides = OVERRIDES.write().unwrap_or_else(|err| err.into_inner());
+    overrides.retain(|(existing, _)| existing != path);
+    HAS_OVERRIDES.store(!overrides.is_empty(), Ordering::Relaxed);
+}
+
+/// Removes every override set by [`set_level_override`].
+pub fn clear_level_overrides() {
+    let mut overrides = OVERRIDES.write().unwrap_or_else(|err| err.into_inner());
+    overrides.clear();
+    HAS_OVERRIDES.store(false, Ordering::Relaxed);
+}
+
+// The level of `LOGCALL_LEVEL` for a function, parsed on its first call.
+#[doc(hidden)]
+pub struct EnvLevel(OnceLock<Option<Level>>);
+
+impl EnvLevel {
+    pub const fn new() -> Self {
+        EnvLevel(OnceLock::new())
+    }
+}
+
+impl Default for EnvLevel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The level of a record, which is the level of the attribute, possibly overridden by
+// `#[logcall::module_defaults]` at compile time, unless `LOGCALL_LEVEL`, as set when the
+// function was compiled, or `set_level_override` overrides it.
+//
+// Called before the record is known to be enabled, so that it costs a single atomic load
+// unless there is an override.
+#[doc(hidden)]
+#[inline]
+pub fn resolve_level(
+    level: Level,
+    env_level: &EnvLevel,
+    env: Option<&'static str>,
+    fn_name: impl FnOnce() -> &'static str,
+) -> Level {
+    if env.is_none() && !HAS_OVERRIDES.load(Ordering::Relaxed) {
+        return level;
+    }
+    resolve_overridden_level(level, env_level, env, fn_name())
+}
+
+#[cold]
+fn resolve_overridden_level(
+    level: Level,
+    env_level: &EnvLevel,
+    env: Option<&'static str>,
+    fn_name: &str,
+) -> Level {
+    if HAS_OVERRIDES.load(Ordering::Relaxed) {
+        let overrides = OVERRIDES.read().unwrap_or_else(|err| err.into_inner());
+        let found = most_specific(
+            overrides
+                .iter()
+                .map(|(path, level)| (path.as_str(), *level)),
+            fn_name,
+        );
+        if let Some(level) = found {
+            return level;
+        }
+    }
+    env_level
+        .0
+        .get_or_init(|| env.and_then(|env| most_specific(parse_directives(env), fn_name)))
+        .unwrap_or(level)
+}
+
+// `info,my_app::db=debug` => [("", Info), ("my_app::db", Debug)]
+//
+// Invalid directives are ignored.
+fn parse_directives(env: &str) -> impl Iterator<Item = (&str, Level)> {
+    env.split(',').filter_map(|directive| {
+        let (path, level) = directive.trim().rsplit_once('=').unwrap_or(("", directive));
+        Some((path.trim(), Level::from_str(level.trim()).ok()?))
+    })
+}
+
+// The level of the longest path that is `fn_name` or one of its parents.
+fn most_specific<'a>(
+    levels: impl Iterator<Item = (&'a str, Level)>,
+    fn_name: &str,
+) -> Option<Level> {
+    levels
+        .filter(|(path, _)| {
+            path.is_empty()
+                || fn_name
+                    .strip_prefix(path)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+        })
+        .max_by_key(|(path, _)| path.len())
+        .map(|(_, level)| level)
+}
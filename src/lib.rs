@@ -1,471 +1,567 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Declares items requiring `std`, i.e. everything but the macros and the `log` facade, which
+// are all that the `defmt` backend needs on `no_std` targets.
+macro_rules! cfg_std {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "std")]
+            $item
+        )*
+    };
+}
 
-// Instrumenting the async fn is not as straight forward as expected because `async_trait` rewrites `async fn`
-// into a normal fn which returns `Box<impl Future>`, and this stops the macro from distinguishing `async fn` from `fn`.
-// The following code reused the `async_trait` probes from [tokio-tracing](https://github.com/tokio-rs/tracing/blob/6a61897a5e834988ad9ac709e28c93c4dbf29116/tracing-attributes/src/expand.rs).
-
-extern crate proc_macro;
-
-#[macro_use]
-extern crate proc_macro_error;
-
-use proc_macro2::Span;
-use syn::spanned::Spanned;
-use syn::Ident;
-use syn::*;
-
-enum Args {
-    Simple {
-        level: String,
-        input_format: Option<String>,
-    },
-    Result {
-        ok_level: Option<String>,
-        err_level: Option<String>,
-        input_format: Option<String>,
-    },
+cfg_std! {
+    mod after_error;
+    #[cfg(feature = "alloc-count")]
+    mod alloc_count;
+    mod array_summary;
+    #[cfg(feature = "audit")]
+    pub mod audit;
+    mod auto_mute;
+    #[cfg(feature = "sentry")]
+    mod breadcrumb;
+    mod budget;
+    #[cfg(feature = "call-graph")]
+    pub mod call_graph;
+    #[cfg(feature = "metrics")]
+    mod call_metrics;
+    mod call_record;
+    mod cancel;
+    mod catch_panic;
+    mod cfg_args;
+    #[cfg(feature = "chrome-trace")]
+    pub mod chrome_trace;
+    #[cfg(feature = "wasm-console")]
+    mod console;
+    mod context;
+    #[cfg(feature = "cpu-time")]
+    mod cpu_time;
+    mod dedup;
+    mod defer;
+    mod depth;
+    mod detail;
+    mod diff;
+    mod diverging;
+    #[cfg(feature = "tracing")]
+    mod event;
+    mod exec_context;
+    mod fingerprint;
+    mod first_poll;
+    mod fn_name;
+    mod hasher;
+    mod head;
+    mod in_flight;
+    pub mod integration;
+    mod io_error;
+    #[cfg(feature = "journald")]
+    mod journald;
+    #[cfg(feature = "serde")]
+    mod json;
+    #[cfg(feature = "kv")]
+    mod kv;
+    mod level_override;
+    #[cfg(feature = "fastrace")]
+    mod local_span;
+    mod logged_fn;
+    #[cfg(feature = "mobile")]
+    mod mobile;
+    #[cfg(feature = "otel")]
+    mod otel;
+    mod outcome;
+    mod poll_stats;
+    #[cfg(any(feature = "puffin", feature = "tracy"))]
+    mod profiler;
+    mod progress;
+    #[cfg(feature = "quick-logger")]
+    mod quick_logger;
+    mod record;
+    mod redact;
+    #[cfg(feature = "registry")]
+    pub mod registry;
+    mod render;
+    mod report;
+    mod sample;
+    mod sink;
+    #[cfg(feature = "slog")]
+    mod slog_backend;
+    mod slow;
+    #[cfg(feature = "tracing")]
+    mod span;
+    mod stack_buf;
+    #[cfg(feature = "stats")]
+    mod stats;
+    mod summary;
+    mod timestamp;
+    mod tokenize;
+    #[cfg(feature = "trace-markers")]
+    mod trace_markers;
+    mod try_iter;
+    #[cfg(feature = "valuable")]
+    mod valuable;
+
+    #[cfg(feature = "alloc-count")]
+    pub use alloc_count::CountingAllocator;
+    pub use call_record::CallRecord;
+    pub use context::drain;
+    pub use context::request_id;
+    pub use context::set_trace_id_hook;
+    pub use context::trace_id;
+    pub use context::Context;
+    pub use context::Scoped;
+    pub use defer::flush;
+    pub use hasher::set_hasher;
+    pub use level_override::clear_level_overrides;
+    pub use level_override::remove_level_override;
+    pub use level_override::set_level_override;
+    pub use logged_fn::Call;
+    pub use logged_fn::CallMut;
+    pub use logged_fn::CallOnce;
+    pub use logged_fn::FnArgs;
+    pub use logged_fn::LoggedFn;
+    #[cfg(feature = "mobile")]
+    pub use mobile::set_os_log_subsystem;
+    #[cfg(feature = "quick-logger")]
+    pub use quick_logger::quick_init;
+    #[cfg(feature = "quick-logger")]
+    pub use quick_logger::try_quick_init;
+    pub use record::Record;
+    pub use render::set_renderer;
+    pub use render::Render;
+    pub use sink::LogcallSink;
+    #[cfg(feature = "stats")]
+    pub use stats::stats;
+    #[cfg(feature = "stats")]
+    pub use stats::FunctionStats;
+    #[cfg(feature = "stats")]
+    pub use stats::Stats;
+    pub use tokenize::set_tokenizer;
 }
 
-impl Args {
-    fn parse(input: AttributeArgs) -> Args {
-        let mut simple_level = None;
-        let mut ok_level = None;
-        let mut err_level = None;
-        let mut input_format = None;
-
-        for arg in input {
-            match arg {
-                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
-                    path,
-                    lit: Lit::Str(lit_str),
-                    ..
-                })) => {
-                    let ident = path.get_ident().unwrap().to_string();
-                    match ident.as_str() {
-                        "ok" => {
-                            ok_level = Some(lit_str.value());
-                        }
-                        "err" => {
-                            err_level = Some(lit_str.value());
-                        }
-                        "input" => {
-                            input_format = Some(lit_str.value());
-                        }
-                        _ => {
-                            abort!(ident.span(), "unexpected argument");
-                        }
-                    }
-                }
-                NestedMeta::Lit(Lit::Str(lit_str)) => {
-                    if simple_level.is_some() {
-                        abort!(lit_str.span(), "level has already been specified");
-                    }
-                    simple_level = Some(lit_str.value());
-                }
-                _ => {
-                    abort!(arg.span(), "unexpected argument");
-                }
-            }
-        }
-
-        if ok_level.is_some() || err_level.is_some() {
-            if simple_level.is_some() {
-                abort_call_site!("plain level cannot be specified with `ok` or `err` levels");
-            }
-
-            Args::Result {
-                ok_level,
-                err_level,
-                input_format,
-            }
-        } else {
-            Args::Simple {
-                level: simple_level.unwrap_or_else(|| "debug".to_string()),
-                input_format,
-            }
-        }
-    }
+// Declared outside of `cfg_std!`, since the macros exported from a module declared by a macro
+// can't be re-exported.
+#[cfg(feature = "registry")]
+pub mod test;
+
+pub use logcall_macro::extern_shim;
+pub use logcall_macro::instrument_extern;
+pub use logcall_macro::logcall;
+pub use logcall_macro::module_defaults;
+pub use logcall_macro::render_as;
+pub use logcall_macro::skip;
+pub use logcall_macro::timeit;
+pub use logcall_macro::wrap_dyn;
+
+// Registers the instrumented function with the registry, which is a no-op unless the
+// `registry` feature is enabled.
+#[cfg(not(feature = "registry"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register {
+    ($name:literal) => {};
 }
 
-/// `logcall` attribute macro that logs the function inputs and return values.
-#[proc_macro_attribute]
-#[proc_macro_error]
-pub fn logcall(
-    args: proc_macro::TokenStream,
-    item: proc_macro::TokenStream,
-) -> proc_macro::TokenStream {
-    let input = syn::parse_macro_input!(item as ItemFn);
-    let args = Args::parse(syn::parse_macro_input!(args as AttributeArgs));
-
-    // check for async_trait-like patterns in the block, and instrument
-    // the future instead of the wrapper
-    let func_body = if let Some(internal_fun) =
-        get_async_trait_info(&input.block, input.sig.asyncness.is_some())
-    {
-        // let's rewrite some statements!
-        match internal_fun.kind {
-            // async-trait <= 0.1.43
-            AsyncTraitKind::Function => {
-                unimplemented!(
-                    "Please upgrade the crate `async-trait` to a version higher than 0.1.44"
-                )
-            }
-            // async-trait >= 0.1.44
-            AsyncTraitKind::Async(async_expr) => {
-                // fallback if we couldn't find the '__async_trait' binding, might be
-                // useful for crates exhibiting the same behaviors as async-trait
-                let instrumented_block =
-                    gen_block(&async_expr.block, true, false, &input.sig, args);
-                let async_attrs = &async_expr.attrs;
-                quote::quote_spanned! {async_expr.span()=>
-                    Box::pin(#(#async_attrs) * #instrumented_block )
-                }
-            }
-        }
-    } else {
-        gen_block(
-            &input.block,
-            input.sig.asyncness.is_some(),
-            input.sig.asyncness.is_some(),
-            &input.sig,
-            args,
-        )
+// `fn_id` requires the `registry` feature.
+#[cfg(not(feature = "registry"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fn_id {
+    () => {
+        ::core::compile_error!("`fn_id` requires the `registry` feature of `logcall`")
     };
+}
 
-    let ItemFn {
-        attrs, vis, sig, ..
-    } = input.clone();
-
-    let Signature {
-        output: return_type,
-        inputs: params,
-        unsafety,
-        constness,
-        abi,
-        ident,
-        asyncness,
-        generics:
-            Generics {
-                params: gen_params,
-                where_clause,
-                ..
-            },
-        ..
-    } = sig;
-
-    quote::quote_spanned!(input.span()=>
-        #(#attrs) *
-        #vis #constness #unsafety #asyncness #abi fn #ident<#gen_params>(#params) #return_type
-        #where_clause
-        {
-            #func_body
-        }
-    )
-    .into()
+// Counts the bytes of the logged message with the registry, which is a no-op unless the
+// `registry` feature is enabled.
+#[cfg(not(feature = "registry"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __count_bytes {
+    ($message:expr) => {
+        $message
+    };
 }
 
-/// Instrument a block
-fn gen_block(
-    block: &Block,
-    async_context: bool,
-    async_keyword: bool,
-    sig: &Signature,
-    args: Args,
-) -> proc_macro2::TokenStream {
-    match args {
-        Args::Simple {
-            level,
-            input_format,
-        } => {
-            // Generate the instrumented function body.
-            // If the function is an `async fn`, this will wrap it in an async block.
-            if async_context {
-                let input_format = input_format.unwrap_or_else(|| gen_input_format(sig));
-                let log = gen_log(&level, "__input_string", "__ret_value");
-                let block = quote::quote_spanned!(block.span()=>
-                    #[allow(unknown_lints)]
-                    #[allow(clippy::useless_format)]
-                    let __input_string = format!(#input_format);
-                    #[allow(unknown_lints)]
-                    let __ret_value = async { #block }.await;
-                    #log;
-                    __ret_value
-                );
-
-                if async_keyword {
-                    block
-                } else {
-                    quote::quote_spanned!(block.span()=>
-                        async move {
-                            #block
-                        }
-                    )
-                }
-            } else {
-                let input_format = input_format.unwrap_or_else(|| gen_input_format(sig));
-                let log = gen_log(&level, "__input_string", "__ret_value");
-                quote::quote_spanned!(block.span()=>
-                    #[allow(unknown_lints)]
-                    #[allow(clippy::useless_format)]
-                    let __input_string = format!(#input_format);
-                    #[allow(unknown_lints)]
-                    #[allow(clippy::redundant_closure_call)]
-                    #[allow(clippy::let_unit_value)]
-                    let __ret_value = (move || #block)();
-                    #log;
-                    __ret_value
-                )
-            }
-        }
-        Args::Result {
-            ok_level,
-            err_level,
-            input_format,
-        } => {
-            let ok_arm = if let Some(ok_level) = ok_level {
-                let log_ok = gen_log(&ok_level, "__input_string", "__ret_value");
-                quote::quote_spanned!(block.span()=>
-                    __ret_value@Ok(_) => {
-                        #log_ok;
-                        __ret_value
-                    }
-                )
-            } else {
-                quote::quote_spanned!(block.span()=>
-                    Ok(__ret_value) => Ok(__ret_value),
-                )
-            };
-            let err_arm = if let Some(err_level) = err_level {
-                let log_err = gen_log(&err_level, "__input_string", "__ret_value");
-                quote::quote_spanned!(block.span()=>
-                    __ret_value@Err(_) => {
-                        #log_err;
-                        __ret_value
-                    }
-                )
-            } else {
-                quote::quote_spanned!(block.span()=>
-                    Err(__ret_value) => Err(__ret_value),
-                )
-            };
-
-            // Generate the instrumented function body.
-            // If the function is an `async fn`, this will wrap it in an async block.
-            if async_context {
-                let input_format = input_format.unwrap_or_else(|| gen_input_format(sig));
-                let block = quote::quote_spanned!(block.span()=>
-                    #[allow(unknown_lints)]
-                    #[allow(clippy::useless_format)]
-                    let __input_string = format!(#input_format);
-                    #[allow(unknown_lints)]
-                    let __ret_value = async { #block }.await;
-                    match __ret_value {
-                        #ok_arm
-                        #err_arm
-                    }
-                );
-
-                if async_keyword {
-                    block
-                } else {
-                    quote::quote_spanned!(block.span()=>
-                        async move {
-                            #block
-                        }
-                    )
-                }
-            } else {
-                let input_format = input_format.unwrap_or_else(|| gen_input_format(sig));
-                quote::quote_spanned!(block.span()=>
-                    #[allow(unknown_lints)]
-                    #[allow(clippy::useless_format)]
-                    let __input_string = format!(#input_format);
-                    #[allow(unknown_lints)]
-                    #[allow(clippy::redundant_closure_call)]
-                    #[allow(clippy::let_unit_value)]
-                    let __ret_value = (move || #block)();
-                    match __ret_value {
-                        #ok_arm
-                        #err_arm
-                    }
-                )
-            }
-        }
-    }
+// Records the call in the local parent span of `fastrace`, which is a no-op unless the
+// `fastrace` feature is enabled.
+#[cfg(not(feature = "fastrace"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __local_span {
+    ($fn_name:expr, $input:expr, $output:expr) => {};
 }
 
-fn gen_log(level: &str, input_string: &str, return_value: &str) -> proc_macro2::TokenStream {
-    let level = level.to_lowercase();
-    if !["error", "warn", "info", "debug", "trace"].contains(&level.as_str()) {
-        abort_call_site!("unknown log level");
-    }
-    let level: Ident = Ident::new(&level, Span::call_site());
-    let input_string: Ident = Ident::new(input_string, Span::call_site());
-    let return_value: Ident = Ident::new(return_value, Span::call_site());
-    let fn_name = quote::quote! {
-        {
-            fn f() {}
-            fn type_name_of<T>(_: T) -> &'static str {
-                std::any::type_name::<T>()
-            }
-            let name = type_name_of(f);
-            let name = &name[..name.len() - 3];
-            name.trim_end_matches("::{{closure}}")
-        }
+// Records the call as an event of the active OpenTelemetry span, which is a no-op unless the
+// `otel` feature is enabled.
+#[cfg(not(feature = "otel"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __otel_event {
+    ($fn_name:expr, $args:expr, $ret:expr, $value:expr) => {};
+}
+
+// Records the call as a Sentry breadcrumb, which is a no-op unless the `sentry` feature is
+// enabled.
+#[cfg(not(feature = "sentry"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __breadcrumb {
+    ($level:expr, $fn_name:expr, $args:expr, $value:expr) => {};
+}
+
+// `metrics` requires the `metrics` feature.
+#[cfg(not(feature = "metrics"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __metrics {
+    ($($tt:tt)*) => {
+        ::core::compile_error!("`metrics` requires the `metrics` feature of `logcall`")
     };
-    quote::quote!(
-        log::#level! ("{}({}) => {:?}", #fn_name, #input_string, &#return_value)
-    )
 }
 
-// fn(a: usize, b: usize) => "a = {a:?}, b = {b:?}"
-fn gen_input_format(sig: &Signature) -> String {
-    let mut input_format = String::new();
-    for (i, input) in sig.inputs.iter().enumerate() {
-        if i > 0 {
-            input_format.push_str(", ");
-        }
-        match input {
-            FnArg::Typed(PatType { pat, .. }) => {
-                if let Pat::Ident(pat_ident) = &**pat {
-                    let ident = &pat_ident.ident;
-                    input_format.push_str(&format!("{ident} = {{{ident}:?}}"));
-                }
-            }
-            FnArg::Receiver(_) => {
-                input_format.push_str("self");
-            }
-        }
-    }
-    input_format
+// Aggregates the call in the statistics returned by `logcall::stats()`, which is a no-op unless
+// the `stats` feature is enabled.
+#[cfg(not(feature = "stats"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __stats_start {
+    ($start:ident) => {};
+}
+
+#[cfg(not(feature = "stats"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __stats {
+    ($fn_name:expr, $value:expr, $start:expr) => {};
 }
 
-enum AsyncTraitKind<'a> {
-    // old construction. Contains the function
-    Function,
-    // new construction. Contains a reference to the async block
-    Async(&'a ExprAsync),
+// Makes the function the caller of the calls made by its body, which is a no-op unless the
+// `call-graph` feature is enabled.
+#[cfg(not(feature = "call-graph"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __call_guard {
+    (enter $guard:ident, $fn_name:expr) => {};
+    (scope $fn_name:expr, $future:expr) => {
+        $future
+    };
 }
 
-struct AsyncTraitInfo<'a> {
-    // statement that must be patched
-    _source_stmt: &'a Stmt,
-    kind: AsyncTraitKind<'a>,
+// Records the call as a Chrome trace event, which is a no-op unless the `chrome-trace` feature
+// is enabled.
+#[cfg(not(feature = "chrome-trace"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace_guard {
+    ($($tt:tt)*) => {};
 }
 
-// Get the AST of the inner function we need to hook, if it was generated
-// by async-trait.
-// When we are given a function annotated by async-trait, that function
-// is only a placeholder that returns a pinned future containing the
-// user logic, and it is that pinned future that needs to be instrumented.
-// Were we to instrument its parent, we would only collect information
-// regarding the allocation of that future, and not its own span of execution.
-// Depending on the version of async-trait, we inspect the block of the function
-// to find if it matches the pattern
-// `async fn foo<...>(...) {...}; Box::pin(foo<...>(...))` (<=0.1.43), or if
-// it matches `Box::pin(async move { ... }) (>=0.1.44). We the return the
-// statement that must be instrumented, along with some other informations.
-// 'gen_body' will then be able to use that information to instrument the
-// proper function/future.
-// (this follows the approach suggested in
-// https://github.com/dtolnay/async-trait/issues/45#issuecomment-571245673)
-fn get_async_trait_info(block: &Block, block_is_async: bool) -> Option<AsyncTraitInfo<'_>> {
-    // are we in an async context? If yes, this isn't a async_trait-like pattern
-    if block_is_async {
-        return None;
-    }
+// Delimits the call with ftrace markers, which is a no-op unless the `trace-markers` feature is
+// enabled.
+#[cfg(not(feature = "trace-markers"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __marker_guard {
+    ($($tt:tt)*) => {};
+}
 
-    // list of async functions declared inside the block
-    let inside_funs = block.stmts.iter().filter_map(|stmt| {
-        if let Stmt::Item(Item::Fn(fun)) = &stmt {
-            // If the function is async, this is a candidate
-            if fun.sig.asyncness.is_some() {
-                return Some((stmt, fun));
-            }
-        }
-        None
-    });
-
-    // last expression of the block (it determines the return value
-    // of the block, so that if we are working on a function whose
-    // `trait` or `impl` declaration is annotated by async_trait,
-    // this is quite likely the point where the future is pinned)
-    let (last_expr_stmt, last_expr) = block.stmts.iter().rev().find_map(|stmt| {
-        if let Stmt::Expr(expr) = stmt {
-            Some((stmt, expr))
-        } else {
-            None
-        }
-    })?;
-
-    // is the last expression a function call?
-    let (outside_func, outside_args) = match last_expr {
-        Expr::Call(ExprCall { func, args, .. }) => (func, args),
-        _ => return None,
+// Opens a profiler scope for the call, which is a no-op unless the `puffin` or the `tracy`
+// feature is enabled.
+#[cfg(not(any(feature = "puffin", feature = "tracy")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __profile_scope {
+    (enter $guard:ident, $fn_name:expr) => {};
+    (end $guard:ident) => {};
+    (instrument $fn_name:expr, $future:expr) => {
+        $future
     };
+}
 
-    // is it a call to `Box::pin()`?
-    let path = match outside_func.as_ref() {
-        Expr::Path(path) => &path.path,
-        _ => return None,
+// `backend = "tracing"` requires the `tracing` feature.
+#[cfg(not(feature = "tracing"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __event {
+    ($target:expr, $level:expr, $message:expr) => {
+        ::core::compile_error!(
+            "`backend = \"tracing\"` requires the `tracing` feature of `logcall`"
+        )
     };
-    if !path_to_string(path).ends_with("Box::pin") {
-        return None;
-    }
+}
 
-    // Does the call take an argument? If it doesn't,
-    // it's not gonna compile anyway, but that's no reason
-    // to (try to) perform an out of bounds access
-    if outside_args.is_empty() {
-        return None;
-    }
+#[cfg(not(feature = "tracing"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __event_enabled {
+    ($target:expr, $level:expr) => {
+        ::core::compile_error!(
+            "`backend = \"tracing\"` requires the `tracing` feature of `logcall`"
+        )
+    };
+}
 
-    // Is the argument to Box::pin an async block that
-    // captures its arguments?
-    if let Expr::Async(async_expr) = &outside_args[0] {
-        // check that the move 'keyword' is present
-        async_expr.capture?;
+// `journald` requires the `journald` feature.
+#[cfg(not(feature = "journald"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __journald {
+    ($($args:expr),* $(,)?) => {
+        ::core::compile_error!("`journald` requires the `journald` feature of `logcall`")
+    };
+}
 
-        return Some(AsyncTraitInfo {
-            _source_stmt: last_expr_stmt,
-            kind: AsyncTraitKind::Async(async_expr),
-        });
-    }
+// `emit = "mobile"` requires the `mobile` feature.
+#[cfg(not(feature = "mobile"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __mobile {
+    ($($args:expr),*) => {
+        ::core::compile_error!("`emit = \"mobile\"` requires the `mobile` feature of `logcall`")
+    };
+}
+
+// `allocations` requires the `alloc-count` feature.
+#[cfg(not(feature = "alloc-count"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __alloc_count {
+    () => {
+        ::core::compile_error!("`allocations` requires the `alloc-count` feature of `logcall`")
+    };
+}
+
+// `cpu_time` requires the `cpu-time` feature.
+#[cfg(not(feature = "cpu-time"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cpu_time {
+    ($mode:ident) => {
+        ::core::compile_error!("`cpu_time` requires the `cpu-time` feature of `logcall`")
+    };
+}
 
-    // Is the argument to Box::pin a function call itself?
-    let func = match &outside_args[0] {
-        Expr::Call(ExprCall { func, .. }) => func,
-        _ => return None,
+// `emit = "console"` requires the `wasm-console` feature.
+#[cfg(not(feature = "wasm-console"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __console {
+    ($($args:expr),*) => {
+        ::core::compile_error!(
+            "`emit = \"console\"` requires the `wasm-console` feature of `logcall`"
+        )
     };
+}
 
-    // "stringify" the path of the function called
-    let func_name = match **func {
-        Expr::Path(ref func_path) => path_to_string(&func_path.path),
-        _ => return None,
+// `slog` requires the `slog` feature.
+#[cfg(not(feature = "slog"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __slog {
+    ($logger:expr, $level:expr, $message:expr) => {
+        ::core::compile_error!("`slog` requires the `slog` feature of `logcall`")
     };
+}
 
-    // Was that function defined inside of the current block?
-    // If so, retrieve the statement where it was declared and the function itself
-    let (stmt_func_declaration, _) = inside_funs
-        .into_iter()
-        .find(|(_, fun)| fun.sig.ident == func_name)?;
+#[cfg(not(feature = "slog"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __slog_enabled {
+    ($logger:expr, $level:expr) => {
+        ::core::compile_error!("`slog` requires the `slog` feature of `logcall`")
+    };
+}
 
-    Some(AsyncTraitInfo {
-        _source_stmt: stmt_func_declaration,
-        kind: AsyncTraitKind::Function,
-    })
+// `format = "json"` requires the `serde` feature.
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __json_value {
+    ($value:expr) => {
+        ::core::compile_error!("`format = \"json\"` requires the `serde` feature of `logcall`")
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __json_args {
+    ($($field:tt),*) => {
+        ::core::compile_error!("`format = \"json\"` requires the `serde` feature of `logcall`")
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __json_record {
+    ($fn_name:expr, $args:expr, $ret:expr, $redact:expr, $fields:expr) => {
+        ::core::compile_error!("`format = \"json\"` requires the `serde` feature of `logcall`")
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __json_ret {
+    ($ret:expr, $redact:expr) => {
+        ::core::compile_error!("`format = \"json\"` requires the `serde` feature of `logcall`")
+    };
+}
+
+// `span` requires the `tracing` feature.
+#[cfg(not(feature = "tracing"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __span {
+    ($level:ident, $name:literal) => {
+        ::core::compile_error!("`span` requires the `tracing` feature of `logcall`")
+    };
 }
 
-// Return a path as a String
-fn path_to_string(path: &Path) -> String {
-    use std::fmt::Write;
-    // some heuristic to prevent too many allocations
-    let mut res = String::with_capacity(path.segments.len() * 5);
-    for i in 0..path.segments.len() {
-        write!(res, "{}", path.segments[i].ident).expect("writing to a String should never fail");
-        if i < path.segments.len() - 1 {
-            res.push_str("::");
-        }
+#[doc(hidden)]
+pub mod __private {
+    cfg_std! {
+        pub use crate::after_error::AfterError;
+        #[cfg(feature = "alloc-count")]
+        pub use crate::alloc_count::AllocCount;
+        pub use crate::array_summary::ArraySummary;
+        pub use crate::auto_mute::AutoMute;
+        #[cfg(feature = "sentry")]
+        pub use crate::breadcrumb::breadcrumb;
+        #[cfg(feature = "sentry")]
+        pub use crate::breadcrumb::ErrorKind;
+        #[cfg(feature = "sentry")]
+        pub use crate::breadcrumb::ErrorProbe;
+        #[cfg(feature = "sentry")]
+        pub use crate::breadcrumb::NoErrorKind;
+        pub use crate::budget::Budget;
+        #[cfg(feature = "call-graph")]
+        pub use crate::call_graph::call_scope;
+        #[cfg(feature = "call-graph")]
+        pub use crate::call_graph::CallGuard;
+        #[cfg(feature = "metrics")]
+        pub use crate::call_metrics::record_call;
+        #[cfg(feature = "metrics")]
+        pub use crate::call_metrics::ErrorLabelProbe;
+        #[cfg(feature = "metrics")]
+        pub use crate::call_metrics::NoErrorLabelKind;
+        #[cfg(feature = "metrics")]
+        pub use crate::call_metrics::StaticErrorLabelKind;
+        #[cfg(feature = "metrics")]
+        pub use crate::call_metrics::TypeErrorLabelKind;
+        pub use crate::cancel::CancelGuard;
+        pub use crate::catch_panic::catch_log;
+        pub use crate::catch_panic::catch_render;
+        pub use crate::cfg_args::CfgArgs;
+        #[cfg(feature = "chrome-trace")]
+        pub use crate::chrome_trace::TraceGuard;
+        #[cfg(feature = "wasm-console")]
+        pub use crate::console::console;
+        #[cfg(feature = "wasm-console")]
+        pub use crate::console::console_message;
+        pub use crate::context::push_record;
+        pub use crate::context::ContextFields;
+        #[cfg(feature = "cpu-time")]
+        pub use crate::cpu_time::CpuTime;
+        pub use crate::dedup::Dedup;
+        pub use crate::dedup::DedupSummary;
+        pub use crate::defer::defer;
+        pub use crate::depth::DepthGuard;
+        pub use crate::detail::Detail;
+        pub use crate::diff::ArgsDiff;
+        pub use crate::diverging::heartbeat;
+        pub use crate::diverging::Diverging;
+        pub use crate::exec_context::ExecContext;
+        pub use crate::exec_context::Polling;
+        pub use crate::exec_context::PollingGuard;
+        pub use crate::fingerprint::Fingerprint;
+        pub use crate::first_poll::Captures;
+        pub use crate::first_poll::FirstPollDelay;
+        pub use crate::fn_name::FnName;
+        pub use crate::head::Head;
+        pub use crate::in_flight::InFlight;
+        pub use crate::io_error::IoErrorFieldsKind;
+        pub use crate::io_error::IoErrorProbe;
+        pub use crate::io_error::NoIoErrorFieldsKind;
+        #[cfg(feature = "journald")]
+        pub use crate::journald::journald;
+        #[cfg(feature = "serde")]
+        pub use crate::json::json_args;
+        #[cfg(feature = "serde")]
+        pub use crate::json::redact_json;
+        #[cfg(feature = "serde")]
+        pub use crate::json::DebugKind;
+        #[cfg(feature = "serde")]
+        pub use crate::json::JsonProbe;
+        #[cfg(feature = "serde")]
+        pub use crate::json::JsonRecord;
+        #[cfg(feature = "serde")]
+        pub use crate::json::JsonRet;
+        #[cfg(feature = "serde")]
+        pub use crate::json::SerializeKind;
+        #[cfg(feature = "kv")]
+        pub use crate::kv::log_kv;
+        pub use crate::level_override::resolve_level;
+        pub use crate::level_override::EnvLevel;
+        #[cfg(feature = "fastrace")]
+        pub use crate::local_span::local_span_event;
+        #[cfg(feature = "mobile")]
+        pub use crate::mobile::mobile;
+        #[cfg(feature = "otel")]
+        pub use crate::otel::otel_event;
+        pub use crate::outcome::NoOutcomeKind;
+        pub use crate::outcome::Outcome;
+        pub use crate::outcome::OutcomeProbe;
+        pub use crate::outcome::ResultOutcomeKind;
+        pub use crate::poll_stats::PollStats;
+        #[cfg(any(feature = "puffin", feature = "tracy"))]
+        pub use crate::profiler::ProfileScope;
+        #[cfg(any(feature = "puffin", feature = "tracy"))]
+        pub use crate::profiler::ScopeSite;
+        pub use crate::progress::Progress;
+        pub use crate::redact::Redact;
+        pub use crate::render::render_registered;
+        pub use crate::render::NoRenderKind;
+        pub use crate::render::RenderKind;
+        pub use crate::render::RenderProbe;
+        pub use crate::report::NoReportKind;
+        pub use crate::report::ReportKind;
+        pub use crate::report::ReportProbe;
+        pub use crate::sample::sampled;
+        pub use crate::sink::sink;
+        #[cfg(feature = "slog")]
+        pub use crate::slog_backend::slog_level;
+        pub use crate::slow::elapsed_level;
+        pub use crate::slow::slow_level;
+        #[cfg(feature = "tracing")]
+        pub use crate::span::record_span;
+        pub use crate::stack_buf::StackBuf;
+        pub use crate::stack_buf::Truncate;
+        #[cfg(feature = "stats")]
+        pub use crate::stats::StatsCell;
+        pub use crate::summary::Summary;
+        pub use crate::timestamp::Elapsed;
+        pub use crate::timestamp::Timestamp;
+        pub use crate::tokenize::Tokenize;
+        #[cfg(feature = "trace-markers")]
+        pub use crate::trace_markers::MarkerGuard;
+        pub use crate::try_iter::TryIter;
+        #[cfg(feature = "valuable")]
+        pub use crate::valuable::DebugFieldKind;
+        #[cfg(feature = "valuable")]
+        pub use crate::valuable::ValuableKind;
+        #[cfg(feature = "valuable")]
+        pub use crate::valuable::ValuableProbe;
+        #[cfg(feature = "registry")]
+        pub use inventory;
+        #[cfg(feature = "slog")]
+        pub use slog;
+        #[cfg(feature = "tracing")]
+        pub use tracing;
+        #[cfg(feature = "tracing")]
+        pub use tracing::Instrument;
     }
-    res
+    pub use log;
 }
@@ -0,0 +1,28 @@
+use std::fmt::Debug;
+use std::fmt::Display;
+
+use fastrace::local::LocalSpan;
+use fastrace::Event;
+
+// Adds the call as an event of the local parent span, if any, named after the function and
+// carrying the rendered arguments and return value as the `input` and `output` properties, so
+// the trace carries the same data as the record. The properties are only rendered if the
+// event is collected.
+#[doc(hidden)]
+pub fn local_span_event(fn_name: &'static str, input: &dyn Display, output: &dyn Debug) {
+    LocalSpan::add_event(Event::new(fn_name).with_properties(|| {
+        [
+            ("input", input.to_string()),
+            ("output", format!("{output:?}")),
+        ]
+    }));
+}
+
+// Records the call in the local parent span with the `fastrace` feature.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __local_span {
+    ($fn_name:expr, $input:expr, $output:expr) => {
+        $crate::__private::local_span_event($fn_name, $input, $output)
+    };
+}
@@ -0,0 +1,211 @@
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Write;
+
+use log::Level;
+
+/// A closure wrapper that logs the arguments and the return value of every invocation.
+///
+/// This is the runtime counterpart of `#[logcall]` for callbacks that are stored in structs or
+/// passed to frameworks, where there is no function to annotate.
+///
+/// The `Fn*` traits can't be implemented outside of the standard library on stable Rust, so the
+/// wrapper is invoked through [`call`](LoggedFn::call), [`call_mut`](LoggedFn::call_mut) or
+/// [`call_once`](LoggedFn::call_once), which take the arguments as a tuple:
+///
+/// ```
+/// use logcall::LoggedFn;
+///
+/// let add = LoggedFn::new("add", log::Level::Info, |a: i32, b: i32| a + b);
+/// assert_eq!(add.call((2, 3)), 5);
+/// // logs "add(2, 3) => 5" at the `info` level
+/// ```
+#[derive(Clone)]
+pub struct LoggedFn<F> {
+    name: &'static str,
+    level: Level,
+    f: F,
+}
+
+impl<F> LoggedFn<F> {
+    /// Wraps the closure so that its invocations are logged as `name(args) => ret` at `level`.
+    pub fn new(name: &'static str, level: Level, f: F) -> Self {
+        LoggedFn { name, level, f }
+    }
+
+    /// Returns the name used in the log records.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the level at which invocations are logged.
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    /// Unwraps the logger, returning the inner closure.
+    pub fn into_inner(self) -> F {
+        self.f
+    }
+
+    /// Invokes the closure by reference.
+    pub fn call<Args>(&self, args: Args) -> F::Output
+    where
+        F: Call<Args>,
+        Args: FnArgs,
+        F::Output: Debug,
+    {
+        let input = render(self.level, &args);
+        let ret = self.f.call(args);
+        log(self.name, self.level, input, &ret);
+        ret
+    }
+
+    /// Invokes the closure by mutable reference.
+    pub fn call_mut<Args>(&mut self, args: Args) -> F::Output
+    where
+        F: CallMut<Args>,
+        Args: FnArgs,
+        F::Output: Debug,
+    {
+        let input = render(self.level, &args);
+        let ret = self.f.call_mut(args);
+        log(self.name, self.level, input, &ret);
+        ret
+    }
+
+    /// Invokes the closure by value.
+    pub fn call_once<Args>(self, args: Args) -> F::Output
+    where
+        F: CallOnce<Args>,
+        Args: FnArgs,
+        F::Output: Debug,
+    {
+        let input = render(self.level, &args);
+        let ret = self.f.call_once(args);
+        log(self.name, self.level, input, &ret);
+        ret
+    }
+}
+
+// Only render the arguments if the record is going to be emitted.
+fn render<Args: FnArgs>(level: Level, args: &Args) -> Option<String> {
+    if log::log_enabled!(level) {
+        let mut input = String::new();
+        args.render(&mut input).ok()?;
+        Some(input)
+    } else {
+        None
+    }
+}
+
+fn log<R: Debug>(name: &str, level: Level, input: Option<String>, ret: &R) {
+    if let Some(input) = input {
+        log::log!(level, "{}({}) => {:?}", name, input, ret);
+    }
+}
+
+impl<F> Debug for LoggedFn<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoggedFn")
+            .field("name", &self.name)
+            .field("level", &self.level)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Argument tuples that can be rendered into a log record.
+///
+/// This trait is sealed and implemented for tuples of up to 8 [`Debug`] elements.
+pub trait FnArgs: sealed::Sealed {
+    #[doc(hidden)]
+    fn render(&self, f: &mut String) -> fmt::Result;
+}
+
+/// Stable counterpart of [`FnOnce`], taking the arguments as a tuple.
+pub trait CallOnce<Args> {
+    /// The return type of the closure.
+    type Output;
+
+    /// Calls the closure by value.
+    fn call_once(self, args: Args) -> Self::Output;
+}
+
+/// Stable counterpart of [`FnMut`], taking the arguments as a tuple.
+pub trait CallMut<Args>: CallOnce<Args> {
+    /// Calls the closure by mutable reference.
+    fn call_mut(&mut self, args: Args) -> Self::Output;
+}
+
+/// Stable counterpart of [`Fn`], taking the arguments as a tuple.
+pub trait Call<Args>: CallMut<Args> {
+    /// Calls the closure by reference.
+    fn call(&self, args: Args) -> Self::Output;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_call {
+    ($($arg:ident),*) => {
+        impl<$($arg: Debug),*> sealed::Sealed for ($($arg,)*) {}
+
+        impl<$($arg: Debug),*> FnArgs for ($($arg,)*) {
+            #[allow(non_snake_case)]
+            fn render(&self, f: &mut String) -> fmt::Result {
+                let ($($arg,)*) = self;
+                let args: &[&dyn Debug] = &[$($arg),*];
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        f.push_str(", ");
+                    }
+                    write!(f, "{:?}", arg)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl<Func, Ret, $($arg),*> CallOnce<($($arg,)*)> for Func
+        where
+            Func: FnOnce($($arg),*) -> Ret,
+        {
+            type Output = Ret;
+
+            #[allow(non_snake_case)]
+            fn call_once(self, ($($arg,)*): ($($arg,)*)) -> Ret {
+                self($($arg),*)
+            }
+        }
+
+        impl<Func, Ret, $($arg),*> CallMut<($($arg,)*)> for Func
+        where
+            Func: FnMut($($arg),*) -> Ret,
+        {
+            #[allow(non_snake_case)]
+            fn call_mut(&mut self, ($($arg,)*): ($($arg,)*)) -> Ret {
+                self($($arg),*)
+            }
+        }
+
+        impl<Func, Ret, $($arg),*> Call<($($arg,)*)> for Func
+        where
+            Func: Fn($($arg),*) -> Ret,
+        {
+            #[allow(non_snake_case)]
+            fn call(&self, ($($arg,)*): ($($arg,)*)) -> Ret {
+                self($($arg),*)
+            }
+        }
+    };
+}
+
+impl_call!();
+impl_call!(A);
+impl_call!(A, B);
+impl_call!(A, B, C);
+impl_call!(A, B, C, D);
+impl_call!(A, B, C, D, E);
+impl_call!(A, B, C, D, E, G);
+impl_call!(A, B, C, D, E, G, H);
+impl_call!(A, B, C, D, E, G, H, I);
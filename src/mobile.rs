@@ -0,0 +1,134 @@
+use std::fmt;
+
+use log::Level;
+
+// The longest message written at once: logcat drops the bytes of an entry beyond about 4 KiB,
+// and `os_log` truncates the dynamic strings beyond 1 KiB. Longer messages are split into
+// several lines, the following ones starting with `… `.
+#[cfg(target_os = "android")]
+const MAX_LINE: usize = 4000;
+#[cfg(target_vendor = "apple")]
+const MAX_LINE: usize = 1000;
+
+// Emits the record of `#[logcall(emit = "mobile")]` to logcat on Android, tagged with the
+// target, and to `os_log` on Apple platforms, in the category of the target. The record is
+// printed to stderr on the other platforms.
+#[doc(hidden)]
+pub fn mobile(target: &str, level: Level, message: fmt::Arguments<'_>) {
+    #[cfg(any(target_os = "android", target_vendor = "apple"))]
+    {
+        // NULs would end the C strings early.
+        let message = message.to_string().replace('\0', "\u{fffd}");
+        for line in lines(&message) {
+            write_line(target, level, &line);
+        }
+    }
+    #[cfg(not(any(target_os = "android", target_vendor = "apple")))]
+    eprintln!("[{}] {}: {}", level, target, message);
+}
+
+// Splits the message at char boundaries into lines of at most `MAX_LINE` bytes.
+#[cfg(any(target_os = "android", target_vendor = "apple"))]
+fn lines(message: &str) -> impl Iterator<Item = String> + '_ {
+    const CONTINUATION: &str = "… ";
+    let mut rest = message;
+    let mut first = true;
+    std::iter::from_fn(move || {
+        if rest.is_empty() && !first {
+            return None;
+        }
+        let prefix = if first { "" } else { CONTINUATION };
+        let mut end = rest.len().min(MAX_LINE - prefix.len());
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        let line = format!("{prefix}{}", &rest[..end]);
+        rest = &rest[end..];
+        first = false;
+        Some(line)
+    })
+}
+
+#[cfg(target_os = "android")]
+fn write_line(target: &str, level: Level, line: &str) {
+    use android_log_sys::LogPriority;
+
+    let priority = match level {
+        Level::Error => LogPriority::ERROR,
+        Level::Warn => LogPriority::WARN,
+        Level::Info => LogPriority::INFO,
+        Level::Debug => LogPriority::DEBUG,
+        Level::Trace => LogPriority::VERBOSE,
+    };
+    let tag = std::ffi::CString::new(target.replace('\0', "\u{fffd}")).unwrap();
+    let line = std::ffi::CString::new(line).unwrap();
+    unsafe {
+        android_log_sys::__android_log_write(
+            priority as std::os::raw::c_int,
+            tag.as_ptr(),
+            line.as_ptr(),
+        );
+    }
+}
+
+#[cfg(target_vendor = "apple")]
+static SUBSYSTEM: std::sync::RwLock<Option<String>> = std::sync::RwLock::new(None);
+
+// The logs of the categories, which are kept, as Apple recommends, instead of being created
+// for every record.
+#[cfg(target_vendor = "apple")]
+static LOGS: std::sync::Mutex<Vec<(String, oslog::OsLog)>> = std::sync::Mutex::new(Vec::new());
+
+/// Sets the subsystem of the `os_log` records of `#[logcall(emit = "mobile")]` on Apple
+/// platforms, usually the bundle identifier of the app, e.g. `com.example.app`.
+///
+/// The subsystem defaults to the crate of the target of the record, e.g. `my_app` for the
+/// `my_app::network` target. The target is the category in either case. This has no effect on
+/// other platforms.
+///
+/// ```
+/// logcall::set_os_log_subsystem("com.example.app");
+/// ```
+pub fn set_os_log_subsystem(subsystem: &str) {
+    #[cfg(target_vendor = "apple")]
+    {
+        *SUBSYSTEM.write().unwrap_or_else(|err| err.into_inner()) = Some(subsystem.to_string());
+        LOGS.lock().unwrap_or_else(|err| err.into_inner()).clear();
+    }
+    #[cfg(not(target_vendor = "apple"))]
+    let _ = subsystem;
+}
+
+// `os_log` has no warning level, so warnings are logged at the default level, which, unlike
+// `info` and `debug`, is persisted.
+#[cfg(target_vendor = "apple")]
+fn write_line(target: &str, level: Level, line: &str) {
+    let level = match level {
+        Level::Error => oslog::Level::Error,
+        Level::Warn => oslog::Level::Default,
+        Level::Info => oslog::Level::Info,
+        Level::Debug | Level::Trace => oslog::Level::Debug,
+    };
+    let mut logs = LOGS.lock().unwrap_or_else(|err| err.into_inner());
+    let index = match logs.iter().position(|(category, _)| category == target) {
+        Some(index) => index,
+        None => {
+            let subsystem = SUBSYSTEM.read().unwrap_or_else(|err| err.into_inner());
+            let subsystem = subsystem
+                .as_deref()
+                .unwrap_or_else(|| target.split("::").next().unwrap_or(target));
+            logs.push((target.to_string(), oslog::OsLog::new(subsystem, target)));
+            logs.len() - 1
+        }
+    };
+    logs[index].1.with_level(level, line);
+}
+
+// Emits the record to logcat or `os_log` with the `mobile` feature.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __mobile {
+    ($target:expr, $level:expr, $message:expr) => {
+        $crate::__private::mobile($target, $level, $message)
+    };
+}
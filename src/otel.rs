@@ -0,0 +1,49 @@
+use std::fmt::Debug;
+use std::fmt::Display;
+
+use opentelemetry::trace::TraceContextExt;
+use opentelemetry::Context;
+use opentelemetry::KeyValue;
+
+// Adds the call as an event of the active OpenTelemetry span, if it's recording, named after
+// the function and carrying its name, the rendered arguments and return value, and whether a
+// `Result` is `ok` or `err`, so distributed traces show the same call detail as the record.
+#[doc(hidden)]
+pub fn otel_event(
+    fn_name: &'static str,
+    args: &dyn Display,
+    ret: &dyn Debug,
+    outcome: Option<&'static str>,
+) {
+    Context::map_current(|cx| {
+        let span = cx.span();
+        if !span.is_recording() {
+            return;
+        }
+        let mut attributes = vec![
+            KeyValue::new("code.function", fn_name),
+            KeyValue::new("args", args.to_string()),
+            KeyValue::new("ret", format!("{ret:?}")),
+        ];
+        if let Some(outcome) = outcome {
+            attributes.push(KeyValue::new("outcome", outcome));
+        }
+        span.add_event(fn_name, attributes);
+    });
+}
+
+// Records the call as an event of the active OpenTelemetry span with the `otel` feature.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __otel_event {
+    ($fn_name:expr, $args:expr, $ret:expr, $value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__private::{NoOutcomeKind as _, ResultOutcomeKind as _};
+        $crate::__private::otel_event(
+            $fn_name,
+            $args,
+            $ret,
+            (&&$crate::__private::OutcomeProbe($value)).outcome(),
+        )
+    }};
+}
@@ -0,0 +1,48 @@
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Display;
+
+// Renders a `Result` as `ok` or `err: <Display>` for `#[logcall(outcome_only)]`, without
+// requiring, or paying for, the `Debug` of the `Ok` payload.
+#[doc(hidden)]
+pub struct Outcome<'a, T, E>(pub &'a Result<T, E>);
+
+impl<T, E: Display> Debug for Outcome<'_, T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Ok(_) => f.write_str("ok"),
+            Err(err) => write!(f, "err: {err}"),
+        }
+    }
+}
+
+// Selects the outcome of the return value, using autoref specialization:
+// `(&&OutcomeProbe(&value)).outcome()` resolves to `ResultOutcomeKind`, which tells `ok` from
+// `err`, when the value is a `Result`, and to `NoOutcomeKind`, which has no outcome, otherwise.
+#[doc(hidden)]
+pub struct OutcomeProbe<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait ResultOutcomeKind {
+    fn outcome(&self) -> Option<&'static str>;
+}
+
+#[doc(hidden)]
+pub trait NoOutcomeKind {
+    fn outcome(&self) -> Option<&'static str>;
+}
+
+impl<T: ?Sized> NoOutcomeKind for OutcomeProbe<'_, T> {
+    fn outcome(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+impl<T, E> ResultOutcomeKind for &OutcomeProbe<'_, Result<T, E>> {
+    fn outcome(&self) -> Option<&'static str> {
+        match self.0 {
+            Ok(_) => Some("ok"),
+            Err(_) => Some("err"),
+        }
+    }
+}
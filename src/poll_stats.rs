@@ -0,0 +1,83 @@
+use std::fmt;
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::task::Context as TaskContext;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+// Renders ` polls=3 busy=1.2ms idle=10.5ms` for `#[logcall(poll_stats)]`: the number of polls of
+// the body of an async call, the time spent inside them, and the time spent between them, from
+// the first poll to the completion. A long idle time points to slow awaited IO, and a long busy
+// time to work starving the executor.
+#[doc(hidden)]
+pub struct PollStats {
+    start: Instant,
+    polls: AtomicU64,
+    // Nanoseconds.
+    busy: AtomicU64,
+    // The end of the last poll, in nanoseconds since `start`.
+    end: AtomicU64,
+}
+
+impl PollStats {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        PollStats {
+            start: Instant::now(),
+            polls: AtomicU64::new(0),
+            busy: AtomicU64::new(0),
+            end: AtomicU64::new(0),
+        }
+    }
+
+    // Measures the polls of `future`.
+    pub fn instrument<F: Future>(&self, future: F) -> PollStatsFuture<'_, F> {
+        PollStatsFuture {
+            future,
+            poll_stats: self,
+        }
+    }
+}
+
+impl Display for PollStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let busy = self.busy.load(Ordering::Relaxed);
+        let end = self.end.load(Ordering::Relaxed);
+        write!(
+            f,
+            " polls={} busy={:?} idle={:?}",
+            self.polls.load(Ordering::Relaxed),
+            Duration::from_nanos(busy),
+            Duration::from_nanos(end.saturating_sub(busy))
+        )
+    }
+}
+
+#[doc(hidden)]
+pub struct PollStatsFuture<'a, F> {
+    future: F,
+    poll_stats: &'a PollStats,
+}
+
+impl<F: Future> Future for PollStatsFuture<'_, F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<F::Output> {
+        let stats = self.poll_stats;
+        // SAFETY: `future` is structurally pinned and never moved out of `self`.
+        let future = unsafe { self.map_unchecked_mut(|this| &mut this.future) };
+        let start = Instant::now();
+        let poll = future.poll(cx);
+        let end = Instant::now();
+        stats.polls.fetch_add(1, Ordering::Relaxed);
+        let busy = end.duration_since(start).as_nanos() as u64;
+        stats.busy.fetch_add(busy, Ordering::Relaxed);
+        let end = end.duration_since(stats.start).as_nanos() as u64;
+        stats.end.store(end, Ordering::Relaxed);
+        poll
+    }
+}
@@ -0,0 +1,124 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context as TaskContext;
+use std::task::Poll;
+
+// The profiler scope of an instrumented function, registered with `puffin` on its first call.
+#[doc(hidden)]
+pub struct ScopeSite {
+    #[cfg(feature = "puffin")]
+    puffin: std::sync::OnceLock<puffin::ScopeId>,
+}
+
+impl ScopeSite {
+    pub const fn new() -> Self {
+        ScopeSite {
+            #[cfg(feature = "puffin")]
+            puffin: std::sync::OnceLock::new(),
+        }
+    }
+}
+
+// Opens a `puffin` scope and a Tracy zone, named after the function, until dropped. Nothing is
+// recorded unless `puffin` scopes are on, or the Tracy client is running.
+#[doc(hidden)]
+pub struct ProfileScope {
+    #[cfg(feature = "puffin")]
+    _puffin: Option<puffin::ProfilerScope>,
+    #[cfg(feature = "tracy")]
+    _tracy: Option<tracy_client::Span>,
+}
+
+impl Default for ScopeSite {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProfileScope {
+    #[allow(unused_variables)]
+    pub fn enter(site: &ScopeSite, name: &'static str, file: &'static str, line: u32) -> Self {
+        ProfileScope {
+            #[cfg(feature = "puffin")]
+            _puffin: puffin::are_scopes_on().then(|| {
+                let id = site.puffin.get_or_init(|| {
+                    puffin::ThreadProfiler::call(|profiler| {
+                        profiler.register_function_scope(name, puffin::short_file_name(file), line)
+                    })
+                });
+                puffin::ProfilerScope::new(*id, "")
+            }),
+            #[cfg(feature = "tracy")]
+            _tracy: tracy_client::Client::running()
+                .map(|client| client.span_alloc(Some(name), name, file, line, 0)),
+        }
+    }
+
+    // Opens the scope at every poll of the future of an async function, since the scopes of
+    // frame profilers belong to a thread, while the future may move between threads at its
+    // awaits.
+    pub fn instrument<F>(
+        site: &'static ScopeSite,
+        name: &'static str,
+        file: &'static str,
+        line: u32,
+        future: F,
+    ) -> Profiled<F> {
+        Profiled {
+            site,
+            name,
+            file,
+            line,
+            future,
+        }
+    }
+
+    pub fn end(self) {}
+}
+
+#[doc(hidden)]
+pub struct Profiled<F> {
+    site: &'static ScopeSite,
+    name: &'static str,
+    file: &'static str,
+    line: u32,
+    future: F,
+}
+
+impl<F: Future> Future for Profiled<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<F::Output> {
+        let _scope = ProfileScope::enter(self.site, self.name, self.file, self.line);
+        // SAFETY: `future` is structurally pinned and never moved out of `self`.
+        let future = unsafe { self.map_unchecked_mut(|this| &mut this.future) };
+        future.poll(cx)
+    }
+}
+
+// Opens the scope of the call with the `puffin` or the `tracy` feature. The scope site of the
+// function is registered once, on its first call.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __profile_scope {
+    (enter $guard:ident, $fn_name:expr) => {
+        let $guard = {
+            static __LOGCALL_SCOPE: $crate::__private::ScopeSite =
+                $crate::__private::ScopeSite::new();
+            $crate::__private::ProfileScope::enter(&__LOGCALL_SCOPE, $fn_name, file!(), line!())
+        };
+    };
+    (end $guard:ident) => {
+        $guard.end();
+    };
+    (instrument $fn_name:expr, $future:expr) => {{
+        static __LOGCALL_SCOPE: $crate::__private::ScopeSite = $crate::__private::ScopeSite::new();
+        $crate::__private::ProfileScope::instrument(
+            &__LOGCALL_SCOPE,
+            $fn_name,
+            file!(),
+            line!(),
+            $future,
+        )
+    }};
+}
@@ -0,0 +1,173 @@
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Condvar;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+
+use log::Level;
+
+// A call whose progress is logged at every interval until it completes.
+struct Running {
+    interval: Duration,
+    level: Level,
+    target: &'static str,
+    fn_name: &'static str,
+    args: String,
+    start: Instant,
+    next: Instant,
+}
+
+#[derive(Default)]
+struct Calls {
+    running: HashMap<u64, Running>,
+    // The next progress record of each call, by time.
+    schedule: BTreeSet<(Instant, u64)>,
+    // The call whose progress record is being logged, out of `running` in the meantime, and
+    // whether it completed since.
+    logging: Option<u64>,
+    completed: bool,
+}
+
+// The calls in progress, and the thread logging their progress records. A single thread serves
+// all the calls, however many are running.
+struct Ticker {
+    calls: Mutex<Calls>,
+    changed: Condvar,
+}
+
+static TICKER: OnceLock<Option<Ticker>> = OnceLock::new();
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+fn ticker() -> Option<&'static Ticker> {
+    TICKER
+        .get_or_init(|| {
+            let spawned = std::thread::Builder::new()
+                .name("logcall-progress".to_string())
+                .spawn(|| run(ticker().unwrap()));
+            match spawned {
+                Ok(_) => Some(Ticker {
+                    calls: Mutex::new(Calls::default()),
+                    changed: Condvar::new(),
+                }),
+                Err(err) => {
+                    log::warn!("failed to spawn the logcall progress thread: {err}");
+                    None
+                }
+            }
+        })
+        .as_ref()
+}
+
+fn run(ticker: &Ticker) {
+    let mut calls = ticker.calls.lock().unwrap_or_else(|err| err.into_inner());
+    loop {
+        let now = Instant::now();
+        let Some(&(next, id)) = calls.schedule.first() else {
+            calls = ticker
+                .changed
+                .wait(calls)
+                .unwrap_or_else(|err| err.into_inner());
+            continue;
+        };
+        if next > now {
+            calls = ticker
+                .changed
+                .wait_timeout(calls, next - now)
+                .unwrap_or_else(|err| err.into_inner())
+                .0;
+            continue;
+        }
+        calls.schedule.remove(&(next, id));
+        let mut call = calls.running.remove(&id).unwrap();
+        calls.logging = Some(id);
+        // Logged outside of the lock, as the logger may call into instrumented functions.
+        drop(calls);
+        log::log!(
+            target: call.target,
+            call.level,
+            "{}({}) still running ({:?} elapsed)",
+            call.fn_name,
+            call.args,
+            call.start.elapsed()
+        );
+        calls = ticker.calls.lock().unwrap_or_else(|err| err.into_inner());
+        if !std::mem::take(&mut calls.completed) {
+            call.next = Instant::now() + call.interval;
+            calls.schedule.insert((call.next, id));
+            calls.running.insert(id, call);
+        }
+        calls.logging = None;
+        ticker.changed.notify_all();
+    }
+}
+
+// Logs `fn(args) still running (..s elapsed)` at every interval until the guard is dropped,
+// i.e. until the call completes, even if it panics. No progress record is logged once the guard
+// is dropped.
+//
+// Nothing is logged if the level is disabled for the target.
+#[doc(hidden)]
+pub struct Progress {
+    id: Option<u64>,
+}
+
+impl Progress {
+    pub fn start(
+        interval: Duration,
+        level: Level,
+        target: &'static str,
+        fn_name: &'static str,
+        args: String,
+    ) -> Self {
+        if !log::log_enabled!(target: target, level) {
+            return Progress { id: None };
+        }
+        let Some(ticker) = ticker() else {
+            return Progress { id: None };
+        };
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let start = Instant::now();
+        let call = Running {
+            interval,
+            level,
+            target,
+            fn_name,
+            args,
+            start,
+            next: start + interval,
+        };
+        let mut calls = ticker.calls.lock().unwrap_or_else(|err| err.into_inner());
+        calls.schedule.insert((call.next, id));
+        calls.running.insert(id, call);
+        // The thread only needs to wake up early for a new earliest record.
+        if calls.schedule.first().map(|&(_, first)| first) == Some(id) {
+            ticker.changed.notify_all();
+        }
+        Progress { id: Some(id) }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        let (Some(id), Some(ticker)) = (self.id, ticker()) else {
+            return;
+        };
+        let mut calls = ticker.calls.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(call) = calls.running.remove(&id) {
+            calls.schedule.remove(&(call.next, id));
+        } else if calls.logging == Some(id) {
+            // Waits for the progress record being logged, so that none follows the call.
+            calls.completed = true;
+            while calls.logging == Some(id) {
+                calls = ticker
+                    .changed
+                    .wait(calls)
+                    .unwrap_or_else(|err| err.into_inner());
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use std::io::Write;
+
+use log::Level;
+use log::LevelFilter;
+use log::Log;
+use log::Metadata;
+use log::Record;
+use log::SetLoggerError;
+
+// The environment variable holding the filter, as with `env_logger`.
+const FILTER_ENV: &str = "RUST_LOG";
+
+// The filter when `RUST_LOG` is not set, which shows the records of `#[logcall]` at its
+// default level.
+const DEFAULT_FILTER: &str = "debug";
+
+/// Installs a console logger writing the records to stderr, filtered by the `RUST_LOG`
+/// environment variable, or at `debug` if it is not set.
+///
+/// The filter is a comma-separated list of directives, each either a level, e.g. `info`, or a
+/// target prefix and a level, e.g. `my_crate::db=trace`. The directive with the longest prefix
+/// matching the target of a record applies.
+///
+/// ```no_run
+/// logcall::quick_init();
+/// ```
+///
+/// # Panics
+///
+/// Panics if a logger has already been installed.
+pub fn quick_init() {
+    try_quick_init().expect("a logger has already been installed");
+}
+
+/// Like [`quick_init`], returning an error instead of panicking if a logger has already been
+/// installed.
+pub fn try_quick_init() -> Result<(), SetLoggerError> {
+    let filter = std::env::var(FILTER_ENV).unwrap_or_else(|_| DEFAULT_FILTER.to_string());
+    let logger = QuickLogger::parse(&filter);
+    let max_level = logger.max_level();
+    log::set_boxed_logger(Box::new(logger))?;
+    log::set_max_level(max_level);
+    Ok(())
+}
+
+// A minimal console logger, so that examples and small tools can see the records of `logcall`
+// without setting up a logger crate.
+struct QuickLogger {
+    // The target prefix, empty for the default, and the level of each directive, sorted by
+    // decreasing prefix length.
+    directives: Vec<(String, LevelFilter)>,
+}
+
+impl QuickLogger {
+    fn parse(filter: &str) -> Self {
+        let mut directives: Vec<_> = filter
+            .split(',')
+            .map(str::trim)
+            .filter(|directive| !directive.is_empty())
+            .filter_map(|directive| match directive.split_once('=') {
+                Some((target, level)) => {
+                    Some((target.trim().to_string(), level.trim().parse().ok()?))
+                }
+                // A bare target enables all of its records.
+                None => match directive.parse() {
+                    Ok(level) => Some((String::new(), level)),
+                    Err(_) => Some((directive.to_string(), LevelFilter::Trace)),
+                },
+            })
+            .collect();
+        if !directives.iter().any(|(target, _)| target.is_empty()) {
+            directives.push((String::new(), LevelFilter::Error));
+        }
+        directives.sort_by_key(|(target, _)| std::cmp::Reverse(target.len()));
+        QuickLogger { directives }
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.directives
+            .iter()
+            .map(|(_, level)| *level)
+            .max()
+            .unwrap_or(LevelFilter::Off)
+    }
+
+    fn level(&self, target: &str) -> LevelFilter {
+        self.directives
+            .iter()
+            .find(|(prefix, _)| {
+                prefix.is_empty()
+                    || target == prefix
+                    || target
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .map_or(LevelFilter::Off, |(_, level)| *level)
+    }
+}
+
+impl Log for QuickLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let level = match record.level() {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN ",
+            Level::Info => "INFO ",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+        let _ = writeln!(
+            std::io::stderr().lock(),
+            "[{} {}] {}",
+            level,
+            record.target(),
+            record.args()
+        );
+    }
+
+    fn flush(&self) {
+        let _ = std::io::stderr().flush();
+    }
+}
@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+use log::Level;
+
+/// A structured record of an instrumented call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// The level the call was logged at.
+    pub level: Level,
+    /// The target of the record, the module path of the function unless overridden.
+    pub target: &'static str,
+    /// The module path of the instrumented function.
+    pub module: &'static str,
+    /// The full path of the instrumented function.
+    pub fn_name: &'static str,
+    /// The rendered arguments.
+    pub args: String,
+    /// The rendered return value.
+    pub ret: String,
+    /// The duration of the call.
+    pub elapsed: Duration,
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+use std::fmt::Debug;
+
+// Rendered in place of the value of a redacted field.
+pub(crate) const REDACTED: &str = "<redacted>";
+
+// Renders a return value with the values of the fields listed in `#[logcall(ret_redact(...))]`
+// masked, at any depth, e.g. `Ok(Session { user: "alice", token: <redacted> })`. The keys of
+// maps, e.g. `{"token": "abc"}`, are matched as well.
+#[doc(hidden)]
+pub struct Redact<'a, T: ?Sized>(pub &'a T, pub &'static [&'static str]);
+
+impl<T: Debug + ?Sized> Debug for Redact<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&redact_debug(&format!("{:?}", self.0), self.1))
+    }
+}
+
+// Masks the fields of a `Debug` rendering, which is scanned for the `name: ` following a `{`
+// or a `, `, outside of string and char literals.
+pub(crate) fn redact_debug(debug: &str, fields: &[&str]) -> String {
+    let bytes = debug.as_bytes();
+    let mut redacted = String::with_capacity(debug.len());
+    let mut copied = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' | b'\'' => i = skip_literal(bytes, i),
+            b'{' | b',' => {
+                i += 1;
+                while bytes.get(i) == Some(&b' ') {
+                    i += 1;
+                }
+                let Some((name, value_start)) = field_name(debug, i) else {
+                    continue;
+                };
+                if fields.contains(&name) {
+                    let value_end = skip_value(bytes, value_start);
+                    redacted.push_str(&debug[copied..value_start]);
+                    redacted.push_str(REDACTED);
+                    copied = value_end;
+                    i = value_end;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    redacted.push_str(&debug[copied..]);
+    redacted
+}
+
+// The name of the field at `start`, either an identifier or a string key, and the start of its
+// value after the `: `.
+fn field_name(debug: &str, start: usize) -> Option<(&str, usize)> {
+    let bytes = debug.as_bytes();
+    let (name, end) = if bytes.get(start) == Some(&b'"') {
+        let end = skip_literal(bytes, start);
+        (debug.get(start + 1..end - 1)?, end)
+    } else {
+        let start = if debug[start..].starts_with("r#") {
+            start + 2
+        } else {
+            start
+        };
+        let len = debug[start..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(debug.len() - start);
+        (&debug[start..start + len], start + len)
+    };
+    debug[end..].starts_with(": ").then_some((name, end + 2))
+}
+
+// The end of the string or char literal at `start`.
+pub(crate) fn skip_literal(bytes: &[u8], start: usize) -> usize {
+    let quote = bytes[start];
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            byte if byte == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+// The end of the value at `start`, which is the next `,` or closing bracket outside of nested
+// brackets and literals.
+pub(crate) fn skip_value(bytes: &[u8], start: usize) -> usize {
+    let mut depth = 0usize;
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' | b'\'' => {
+                i = skip_literal(bytes, i);
+                continue;
+            }
+            b'{' | b'[' | b'(' => depth += 1,
+            b'}' | b']' | b')' if depth == 0 => return trim_end(bytes, start, i),
+            b'}' | b']' | b')' => depth -= 1,
+            b',' if depth == 0 => return i,
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+// Excludes the space before the closing bracket of a struct.
+fn trim_end(bytes: &[u8], start: usize, end: usize) -> usize {
+    let mut end = end;
+    while end > start && bytes[end - 1] == b' ' {
+        end -= 1;
+    }
+    end
+}
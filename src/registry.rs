@@ -0,0 +1,239 @@
+//! The registry of instrumented functions.
+//!
+//! With the `registry` feature, every function annotated with `#[logcall]` is registered at
+//! startup, whether it's called or not, along with the number of times it has been called and
+//! the number of bytes it has logged.
+//!
+//! Every function is also given a compact numeric ID, which the records of the functions
+//! annotated with `#[logcall(fn_id)]` show instead of their path, e.g. `#17(a = 1) => 2`. The
+//! paths are restored from the map written by [`write_id_map`].
+
+use std::fmt;
+use std::fmt::Display;
+use std::io;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Once;
+use std::sync::OnceLock;
+
+/// An instrumented function.
+#[derive(Debug)]
+pub struct Function {
+    name: &'static str,
+    file: &'static str,
+    line: u32,
+    calls: AtomicU64,
+    logged_bytes: AtomicU64,
+    id: AtomicU32,
+    id_name: OnceLock<String>,
+}
+
+impl Function {
+    #[doc(hidden)]
+    pub const fn new(name: &'static str, file: &'static str, line: u32) -> Self {
+        Function {
+            name,
+            file,
+            line,
+            calls: AtomicU64::new(0),
+            logged_bytes: AtomicU64::new(0),
+            id: AtomicU32::new(0),
+            id_name: OnceLock::new(),
+        }
+    }
+
+    /// Returns the ID of the function: its index among all the instrumented functions sorted
+    /// by name, as returned by [`functions`].
+    ///
+    /// The IDs are the same for every run of a given build, but may change when functions are
+    /// added, removed or renamed, so that they must be read with the map of the same build.
+    pub fn id(&self) -> u32 {
+        static ASSIGN_IDS: Once = Once::new();
+        ASSIGN_IDS.call_once(|| {
+            for (id, function) in functions().into_iter().enumerate() {
+                function.id.store(id as u32, Ordering::Relaxed);
+            }
+        });
+        self.id.load(Ordering::Relaxed)
+    }
+
+    // `#17`, the name of the function in the records with `#[logcall(fn_id)]`.
+    #[doc(hidden)]
+    pub fn id_name(&'static self) -> &'static str {
+        self.id_name.get_or_init(|| format!("#{}", self.id()))
+    }
+
+    /// Returns the path of the function, made of the module path and the function name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the file the function is defined in.
+    pub fn file(&self) -> &'static str {
+        self.file
+    }
+
+    /// Returns the line the function is defined on.
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the number of times the function has been called.
+    pub fn calls(&self) -> u64 {
+        self.calls.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of bytes of the messages the function has logged.
+    ///
+    /// The bytes are counted as the logger formats the messages, so records that are filtered
+    /// out by the logger are not counted, while the timestamp, level and other decorations
+    /// added by the logger are not included.
+    pub fn logged_bytes(&self) -> u64 {
+        self.logged_bytes.load(Ordering::Relaxed)
+    }
+
+    #[doc(hidden)]
+    pub fn record_call(&self) {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+// Counts the bytes of the message as it is formatted, and adds them to the function.
+#[doc(hidden)]
+pub struct CountBytes<T>(pub &'static Function, pub T);
+
+impl<T: Display> Display for CountBytes<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use std::fmt::Write;
+
+        let mut counter = Counter { inner: f, bytes: 0 };
+        let result = write!(counter, "{}", self.1);
+        self.0
+            .logged_bytes
+            .fetch_add(counter.bytes, Ordering::Relaxed);
+        result
+    }
+}
+
+struct Counter<'a, 'b> {
+    inner: &'a mut fmt::Formatter<'b>,
+    bytes: u64,
+}
+
+impl fmt::Write for Counter<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.bytes += s.len() as u64;
+        self.inner.write_str(s)
+    }
+}
+
+#[doc(hidden)]
+pub struct Registration(pub &'static Function);
+
+inventory::collect!(Registration);
+
+/// Returns all the instrumented functions, sorted by name.
+pub fn functions() -> Vec<&'static Function> {
+    let mut functions: Vec<_> = inventory::iter::<Registration>
+        .into_iter()
+        .map(|registration| registration.0)
+        .collect();
+    functions.sort_by_key(|function| (function.name, function.line));
+    functions
+}
+
+/// Returns the instrumented functions that have never been called, sorted by name.
+pub fn never_called() -> Vec<&'static Function> {
+    let mut functions = functions();
+    functions.retain(|function| function.calls() == 0);
+    functions
+}
+
+/// Writes the map of the IDs of the instrumented functions to their paths, one function per
+/// line, e.g. `#17 main::charge (src/main.rs:42)`, to read the records of `#[logcall(fn_id)]`.
+///
+/// ```no_run
+/// let file = std::fs::File::create("logcall-ids.txt")?;
+/// logcall::registry::write_id_map(std::io::BufWriter::new(file))?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn write_id_map(mut writer: impl io::Write) -> io::Result<()> {
+    for function in functions() {
+        writeln!(
+            writer,
+            "#{} {} ({}:{})",
+            function.id(),
+            function.name,
+            function.file,
+            function.line
+        )?;
+    }
+    writer.flush()
+}
+
+/// Logs a `warn` record for every instrumented function that has never been called.
+pub fn report_never_called() {
+    for function in never_called() {
+        log::warn!(
+            "{} ({}:{}) was never called",
+            function.name,
+            function.file,
+            function.line
+        );
+    }
+}
+
+/// Runs [`report_never_called`] when the process exits normally, i.e. when `main` returns or
+/// `std::process::exit` is called.
+///
+/// Useful to verify that a test suite covers the critical paths, or to detect the code behind
+/// dead feature flags.
+pub fn report_never_called_at_exit() {
+    extern "C" {
+        fn atexit(callback: extern "C" fn()) -> std::os::raw::c_int;
+    }
+
+    extern "C" fn report() {
+        report_never_called();
+        log::logger().flush();
+    }
+
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| {
+        // SAFETY: `report` is a valid `extern "C"` callback taking no arguments.
+        if unsafe { atexit(report) } != 0 {
+            log::warn!("failed to register the never-called report");
+        }
+    });
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register {
+    ($name:literal) => {
+        static __LOGCALL_FUNCTION: $crate::registry::Function =
+            $crate::registry::Function::new(concat!(module_path!(), "::", $name), file!(), line!());
+        $crate::__private::inventory::submit!($crate::registry::Registration(&__LOGCALL_FUNCTION));
+        __LOGCALL_FUNCTION.record_call();
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fn_id {
+    () => {
+        __LOGCALL_FUNCTION.id_name()
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __count_bytes {
+    ($message:expr) => {
+        format_args!(
+            "{}",
+            $crate::registry::CountBytes(&__LOGCALL_FUNCTION, $message)
+        )
+    };
+}
@@ -0,0 +1,127 @@
+use std::any::Any;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Debug;
+use std::sync::RwLock;
+
+/// Renders the arguments of a type in the records instead of its `Debug` implementation, e.g.
+/// `user:12345` for a `UserId`, so that the records use the same vocabulary as the rest of the
+/// logs.
+///
+/// Implemented by `#[logcall::render_as("user:{}")]`, or by hand:
+///
+/// ```
+/// struct UserId(u64);
+///
+/// impl logcall::Render for UserId {
+///     fn render(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         write!(f, "user:{}", self.0)
+///     }
+/// }
+/// ```
+pub trait Render {
+    /// Writes the value as it should appear in the records.
+    fn render(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+impl<T: Render + ?Sized> Render for &T {
+    fn render(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).render(f)
+    }
+}
+
+impl<T: Render + ?Sized> Render for &mut T {
+    fn render(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).render(f)
+    }
+}
+
+type Renderer = Box<dyn Fn(&dyn Any, &mut fmt::Formatter<'_>) -> fmt::Result + Send + Sync>;
+
+static RENDERERS: RwLock<Option<HashMap<TypeId, Renderer>>> = RwLock::new(None);
+
+/// Installs the renderer of a type marked with `#[logcall::render_as]` without a format,
+/// replacing the previous one, if any. The values are rendered with their `Debug`
+/// implementation until a renderer is installed.
+///
+/// ```
+/// #[derive(Debug)]
+/// #[logcall::render_as]
+/// struct OrderId(u64);
+///
+/// logcall::set_renderer(|id: &OrderId, f| write!(f, "order:{}", id.0));
+/// ```
+pub fn set_renderer<T: 'static>(
+    renderer: impl Fn(&T, &mut fmt::Formatter<'_>) -> fmt::Result + Send + Sync + 'static,
+) {
+    let renderer: Renderer = Box::new(move |value, f| match value.downcast_ref::<T>() {
+        Some(value) => renderer(value, f),
+        None => Ok(()),
+    });
+    RENDERERS
+        .write()
+        .unwrap_or_else(|err| err.into_inner())
+        .get_or_insert_with(HashMap::new)
+        .insert(TypeId::of::<T>(), renderer);
+}
+
+// Renders a value of a type marked with `#[logcall::render_as]` without a format, through the
+// renderer installed by `set_renderer`, or its `Debug` implementation otherwise.
+#[doc(hidden)]
+pub fn render_registered<T: Debug + 'static>(value: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let renderers = RENDERERS.read().unwrap_or_else(|err| err.into_inner());
+    match renderers
+        .as_ref()
+        .and_then(|renderers| renderers.get(&TypeId::of::<T>()))
+    {
+        Some(renderer) => renderer(value, f),
+        None => Debug::fmt(value, f),
+    }
+}
+
+// Selects how an argument is rendered, using autoref specialization:
+// `(&&RenderProbe(&value)).render_as()` resolves to `RenderKind`, which renders the value with
+// its `Render` implementation, when it has one, and to `NoRenderKind`, which renders the value
+// with its own `Debug`, otherwise.
+#[doc(hidden)]
+pub struct RenderProbe<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait RenderKind<'a> {
+    type Output: Debug;
+
+    fn render_as(&self) -> Self::Output;
+}
+
+#[doc(hidden)]
+pub trait NoRenderKind<'a> {
+    type Output;
+
+    fn render_as(&self) -> Self::Output;
+}
+
+impl<'a, T: ?Sized> NoRenderKind<'a> for RenderProbe<'a, T> {
+    type Output = &'a T;
+
+    fn render_as(&self) -> &'a T {
+        self.0
+    }
+}
+
+impl<'a, T: Render + ?Sized> RenderKind<'a> for &RenderProbe<'a, T> {
+    type Output = RenderAs<'a, T>;
+
+    fn render_as(&self) -> RenderAs<'a, T> {
+        RenderAs(self.0)
+    }
+}
+
+#[doc(hidden)]
+pub struct RenderAs<'a, T: ?Sized>(&'a T);
+
+impl<T: Render + ?Sized> Debug for RenderAs<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.render(f)
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(feature = "eyre")]
+use std::fmt;
+use std::fmt::Debug;
+
+// Selects how the return value is rendered, using autoref specialization:
+// `(&&ReportProbe(&value)).render_report()` resolves to `ReportKind` when the `eyre` feature is
+// enabled and the value is an `eyre::Report` or a `Result<_, eyre::Report>`, and to
+// `NoReportKind`, which renders the value with its own `Debug`, otherwise.
+#[doc(hidden)]
+pub struct ReportProbe<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait ReportKind<'a> {
+    type Output: Debug;
+
+    fn render_report(&self) -> Self::Output;
+}
+
+#[doc(hidden)]
+pub trait NoReportKind<'a> {
+    type Output;
+
+    fn render_report(&self) -> Self::Output;
+}
+
+impl<'a, T: ?Sized> NoReportKind<'a> for ReportProbe<'a, T> {
+    type Output = &'a T;
+
+    fn render_report(&self) -> &'a T {
+        self.0
+    }
+}
+
+#[cfg(feature = "eyre")]
+impl<'a, T: Debug> ReportKind<'a> for &ReportProbe<'a, Result<T, eyre::Report>> {
+    type Output = RenderReport<'a, T>;
+
+    fn render_report(&self) -> RenderReport<'a, T> {
+        RenderReport::Result(self.0)
+    }
+}
+
+#[cfg(feature = "eyre")]
+impl<'a> ReportKind<'a> for &ReportProbe<'a, eyre::Report> {
+    type Output = RenderReport<'a, ()>;
+
+    fn render_report(&self) -> RenderReport<'a, ()> {
+        RenderReport::Report(self.0)
+    }
+}
+
+// Renders the full report of the installed `eyre` handler, including the chain of causes and
+// the backtrace, if captured, regardless of the flags of the outer formatter.
+#[cfg(feature = "eyre")]
+#[doc(hidden)]
+pub enum RenderReport<'a, T> {
+    Result(&'a Result<T, eyre::Report>),
+    Report(&'a eyre::Report),
+}
+
+#[cfg(feature = "eyre")]
+impl<T: Debug> Debug for RenderReport<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use std::fmt::Write;
+
+        match self {
+            RenderReport::Result(Ok(value)) => f.debug_tuple("Ok").field(value).finish(),
+            RenderReport::Result(Err(report)) => {
+                f.write_str("Err(")?;
+                write!(StripAnsi(&mut *f), "{:?}", report)?;
+                f.write_str(")")
+            }
+            RenderReport::Report(report) => write!(StripAnsi(&mut *f), "{:?}", report),
+        }
+    }
+}
+
+// Drops the ANSI escape sequences emitted by colored handlers such as `color-eyre`, which are
+// noise once the record is written to a file or shipped to a log collector.
+#[cfg(feature = "eyre")]
+struct StripAnsi<W>(W);
+
+#[cfg(feature = "eyre")]
+impl<W: fmt::Write> fmt::Write for StripAnsi<W> {
+    fn write_str(&mut self, mut s: &str) -> fmt::Result {
+        while let Some(start) = s.find('\x1b') {
+            self.0.write_str(&s[..start])?;
+            let rest = &s[start + 1..];
+            // Skip a CSI sequence `ESC [ params final`, where the final byte is in `@..=~`.
+            s = match rest.strip_prefix('[') {
+                Some(csi) => match csi.find(|c: char| ('@'..='~').contains(&c)) {
+                    Some(end) => &csi[end + 1..],
+                    None => "",
+                },
+                None => rest,
+            };
+        }
+        self.0.write_str(s)
+    }
+}
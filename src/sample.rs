@@ -0,0 +1,15 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+
+use crate::hasher::hash;
+
+// Decides whether the calls for `key` are logged by `#[logcall(sample_by = "...")]`.
+//
+// The hasher uses fixed keys, unless `set_hasher` installs another one, so a key is either
+// always or never sampled at a given rate, and keys sampled at a rate are still sampled at any
+// higher rate.
+#[doc(hidden)]
+pub fn sampled<T: Hash + ?Sized>(key: &T, rate: f64) -> bool {
+    let hash = hash(DefaultHasher::new(), |mut hasher| key.hash(&mut hasher));
+    (hash as f64) < rate * u64::MAX as f64
+}
@@ -0,0 +1,38 @@
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::time::Instant;
+
+use log::Level;
+
+use crate::Record;
+
+/// A destination for the structured records of the functions annotated with
+/// `#[logcall(sink = "...")]`, e.g. a database, a queue or a test harness.
+pub trait LogcallSink {
+    /// Handles the record of a call.
+    fn record(&self, record: Record);
+}
+
+// Hands the record of a call to the sink of `#[logcall(sink = "...")]`.
+#[doc(hidden)]
+#[allow(clippy::too_many_arguments)]
+pub fn sink<S: LogcallSink + ?Sized>(
+    sink: &S,
+    level: Level,
+    target: &'static str,
+    module: &'static str,
+    fn_name: &'static str,
+    args: &dyn Display,
+    ret: &dyn Debug,
+    start: Instant,
+) {
+    sink.record(Record {
+        level,
+        target,
+        module,
+        fn_name,
+        args: args.to_string(),
+        ret: format!("{ret:?}"),
+        elapsed: start.elapsed(),
+    });
+}
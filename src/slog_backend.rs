@@ -0,0 +1,71 @@
+use log::Level;
+
+// The `slog` level of a `log` level.
+#[doc(hidden)]
+pub fn slog_level(level: Level) -> slog::Level {
+    match level {
+        Level::Error => slog::Level::Error,
+        Level::Warn => slog::Level::Warning,
+        Level::Info => slog::Level::Info,
+        Level::Debug => slog::Level::Debug,
+        Level::Trace => slog::Level::Trace,
+    }
+}
+
+// Emits the record to the logger of `#[logcall(slog = "...")]`.
+//
+// `slog` stores the level in the static metadata of the record, so the `log` level, which is
+// dynamic when boosted by `after_error`, is matched against each of the `slog` levels.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __slog {
+    ($logger:expr, $level:expr, $message:expr) => {
+        match $level {
+            $crate::__private::log::Level::Error => $crate::__private::slog::log!(
+                $logger,
+                $crate::__private::slog::Level::Error,
+                "",
+                "{}",
+                $message
+            ),
+            $crate::__private::log::Level::Warn => $crate::__private::slog::log!(
+                $logger,
+                $crate::__private::slog::Level::Warning,
+                "",
+                "{}",
+                $message
+            ),
+            $crate::__private::log::Level::Info => $crate::__private::slog::log!(
+                $logger,
+                $crate::__private::slog::Level::Info,
+                "",
+                "{}",
+                $message
+            ),
+            $crate::__private::log::Level::Debug => $crate::__private::slog::log!(
+                $logger,
+                $crate::__private::slog::Level::Debug,
+                "",
+                "{}",
+                $message
+            ),
+            $crate::__private::log::Level::Trace => $crate::__private::slog::log!(
+                $logger,
+                $crate::__private::slog::Level::Trace,
+                "",
+                "{}",
+                $message
+            ),
+        }
+    };
+}
+
+// Whether the logger of `#[logcall(slog = "...")]` would record the level, like
+// `log::log_enabled!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __slog_enabled {
+    ($logger:expr, $level:expr) => {
+        $crate::__private::slog::Drain::is_enabled(&$logger, $crate::__private::slog_level($level))
+    };
+}
@@ -0,0 +1,28 @@
+use std::time::Duration;
+use std::time::Instant;
+
+use log::Level;
+
+// The level of the record of a call with `#[logcall(slow = "250ms")]`: escalated to `slow` when
+// the call lasted at least `threshold`, and kept otherwise, e.g. for an `Err` already logged at
+// a more severe level.
+#[doc(hidden)]
+pub fn slow_level(level: Level, slow: Level, start: Instant, threshold: Duration) -> Level {
+    if start.elapsed() >= threshold {
+        // `Error` is the smallest level.
+        level.min(slow)
+    } else {
+        level
+    }
+}
+
+// The level of the record of a call with `#[logcall(elapsed_levels(1s = "info", 5s = "warn"))]`:
+// escalated to the level of the highest threshold the call reached, by increasing threshold.
+#[doc(hidden)]
+pub fn elapsed_level(level: Level, start: Instant, rungs: &[(Duration, Level)]) -> Level {
+    let elapsed = start.elapsed();
+    match rungs.iter().rev().find(|(threshold, _)| elapsed >= *threshold) {
+        Some((_, rung)) => level.min(*rung),
+        None => level,
+    }
+}
@@ -0,0 +1,30 @@
+use std::fmt::Debug;
+use std::time::Instant;
+
+use tracing::field;
+use tracing::Span;
+
+// Records `ret` and `duration_us` onto the current tracing span, returning `false` if there is
+// no current span, in which case the record is logged as usual.
+//
+// Only the fields declared when the span was created are recorded, e.g. with
+// `#[tracing::instrument(fields(ret, duration_us))]`.
+#[doc(hidden)]
+pub fn record_span(ret: &dyn Debug, start: Instant) -> bool {
+    let span = Span::current();
+    if span.is_disabled() {
+        return false;
+    }
+    span.record("ret", field::debug(ret));
+    span.record("duration_us", start.elapsed().as_micros() as u64);
+    true
+}
+
+// Creates the span of `#[logcall(span = "...")]`, named after the function.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __span {
+    ($level:ident, $name:literal) => {
+        $crate::__private::tracing::span!($crate::__private::tracing::Level::$level, $name)
+    };
+}
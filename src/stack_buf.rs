@@ -0,0 +1,83 @@
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::fmt::Write;
+
+// The marker appended to truncated output.
+const ELLIPSIS: &str = "...";
+
+// A fixed-capacity string buffer on the stack, used by `heapless` mode in place of `format!`.
+//
+// Writes beyond the capacity are truncated at a character boundary and the rendered output
+// ends with an ellipsis.
+#[doc(hidden)]
+pub struct StackBuf<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+    truncated: bool,
+}
+
+impl<const N: usize> StackBuf<N> {
+    pub fn new() -> Self {
+        StackBuf {
+            buf: [0; N],
+            len: 0,
+            truncated: false,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only whole characters are ever copied into the buffer.
+        std::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+impl<const N: usize> Default for StackBuf<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Write for StackBuf<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.truncated {
+            return Ok(());
+        }
+
+        let remaining = N - self.len;
+        let mut end = s.len().min(remaining);
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.buf[self.len..self.len + end].copy_from_slice(&s.as_bytes()[..end]);
+        self.len += end;
+        self.truncated = end < s.len();
+        Ok(())
+    }
+}
+
+impl<const N: usize> Display for StackBuf<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())?;
+        if self.truncated {
+            f.write_str(ELLIPSIS)?;
+        }
+        Ok(())
+    }
+}
+
+// Renders the `Debug` output of a value through a `StackBuf`, truncating it to `N` bytes.
+#[doc(hidden)]
+pub struct Truncate<'a, T: ?Sized, const N: usize>(pub &'a T);
+
+impl<T: Debug + ?Sized, const N: usize> Debug for Truncate<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = StackBuf::<N>::new();
+        write!(buf, "{:?}", self.0)?;
+        Display::fmt(&buf, f)
+    }
+}
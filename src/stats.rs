@@ -0,0 +1,189 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+
+// The statistics of the functions called at least once, in the order of their first call.
+static FUNCTIONS: Mutex<Vec<&'static StatsCell>> = Mutex::new(Vec::new());
+
+// The statistics of a function, aggregated by every call and registered on the first one.
+#[doc(hidden)]
+pub struct StatsCell {
+    fn_name: OnceLock<&'static str>,
+    calls: AtomicU64,
+    errors: AtomicU64,
+    total_nanos: AtomicU64,
+    min_nanos: AtomicU64,
+    max_nanos: AtomicU64,
+}
+
+impl StatsCell {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        StatsCell {
+            fn_name: OnceLock::new(),
+            calls: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            total_nanos: AtomicU64::new(0),
+            min_nanos: AtomicU64::new(u64::MAX),
+            max_nanos: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record(&'static self, fn_name: &'static str, error: bool, start: Instant) {
+        let nanos = u64::try_from(start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        self.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.min_nanos.fetch_min(nanos, Ordering::Relaxed);
+        self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+        if error {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        // Counted last, so that a snapshot never sees a call without its duration.
+        self.calls.fetch_add(1, Ordering::Release);
+        if self.fn_name.set(fn_name).is_ok() {
+            FUNCTIONS
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .push(self);
+        }
+    }
+
+    fn snapshot(&self) -> Option<FunctionStats> {
+        let calls = self.calls.load(Ordering::Acquire);
+        let fn_name = *self.fn_name.get()?;
+        Some(FunctionStats {
+            fn_name,
+            calls,
+            errors: self.errors.load(Ordering::Relaxed),
+            total: Duration::from_nanos(self.total_nanos.load(Ordering::Relaxed)),
+            min: Duration::from_nanos(self.min_nanos.load(Ordering::Relaxed)),
+            max: Duration::from_nanos(self.max_nanos.load(Ordering::Relaxed)),
+        })
+    }
+}
+
+/// The statistics of an instrumented function, as returned by [`stats`](crate::stats).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionStats {
+    fn_name: &'static str,
+    calls: u64,
+    errors: u64,
+    total: Duration,
+    min: Duration,
+    max: Duration,
+}
+
+impl FunctionStats {
+    /// Returns the name of the function, as in its records.
+    pub fn fn_name(&self) -> &'static str {
+        self.fn_name
+    }
+
+    /// Returns the number of completed calls.
+    pub fn calls(&self) -> u64 {
+        self.calls
+    }
+
+    /// Returns the number of calls that returned an `Err`.
+    pub fn errors(&self) -> u64 {
+        self.errors
+    }
+
+    /// Returns the total duration of the calls.
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    /// Returns the duration of the shortest call.
+    pub fn min(&self) -> Duration {
+        self.min
+    }
+
+    /// Returns the duration of the longest call.
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// Returns the mean duration of the calls.
+    pub fn mean(&self) -> Duration {
+        Duration::from_nanos((self.total.as_nanos() / u128::from(self.calls.max(1))) as u64)
+    }
+}
+
+/// A snapshot of the statistics of the instrumented functions, sorted by name.
+#[derive(Debug, Clone, Default)]
+pub struct Stats(Vec<FunctionStats>);
+
+impl Stats {
+    /// Returns an iterator over the statistics of the functions.
+    pub fn iter(&self) -> std::slice::Iter<'_, FunctionStats> {
+        self.0.iter()
+    }
+
+    /// Returns the statistics of the function with that name, if it has been called.
+    pub fn get(&self, fn_name: &str) -> Option<&FunctionStats> {
+        self.0.iter().find(|stats| stats.fn_name == fn_name)
+    }
+}
+
+impl IntoIterator for Stats {
+    type Item = FunctionStats;
+    type IntoIter = std::vec::IntoIter<FunctionStats>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Stats {
+    type Item = &'a FunctionStats;
+    type IntoIter = std::slice::Iter<'a, FunctionStats>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// Returns a snapshot of the call counts, error counts and durations of every instrumented
+/// function called so far.
+///
+/// The functions are counted once their calls complete, so that a function whose calls are
+/// all still running is not included yet.
+pub fn stats() -> Stats {
+    let functions = FUNCTIONS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    let mut stats: Vec<_> = functions
+        .into_iter()
+        .filter_map(StatsCell::snapshot)
+        .collect();
+    stats.sort_by_key(|stats| stats.fn_name);
+    Stats(stats)
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __stats_start {
+    ($start:ident) => {
+        let $start = ::std::time::Instant::now();
+    };
+}
+
+// Records the call with the `stats` feature.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __stats {
+    ($fn_name:expr, $value:expr, $start:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__private::{NoOutcomeKind as _, ResultOutcomeKind as _};
+        static __LOGCALL_STATS: $crate::__private::StatsCell = $crate::__private::StatsCell::new();
+        __LOGCALL_STATS.record(
+            $fn_name,
+            (&&$crate::__private::OutcomeProbe($value)).outcome() == Some("err"),
+            $start,
+        )
+    }};
+}
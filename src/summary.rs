@@ -0,0 +1,216 @@
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Condvar;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+
+use log::Level;
+
+// The durations are counted in buckets of 8 per power of two of nanoseconds, so that the
+// percentiles are within 1/16 of the actual durations, without storing them.
+const SUB_BUCKETS: usize = 8;
+const BUCKETS: usize = (64 - 2) * SUB_BUCKETS;
+
+// Per-function state of `#[logcall(summarize = "60s")]`: the calls of the current window,
+// counted lock-free, and the parameters of the summary, set by the first call.
+#[doc(hidden)]
+pub struct Summary {
+    meta: OnceLock<Meta>,
+    errors: AtomicU64,
+    // The most severe level of the calls, as `Level as usize`, or `usize::MAX` without calls.
+    level: AtomicUsize,
+    buckets: [AtomicU64; BUCKETS],
+}
+
+struct Meta {
+    interval: Duration,
+    target: &'static str,
+    fn_name: &'static str,
+}
+
+// The summarized functions by the end of their current window, and the thread logging their
+// summaries. A single thread serves all the functions.
+struct Summarizer {
+    due: Mutex<Vec<(Instant, &'static Summary)>>,
+    changed: Condvar,
+}
+
+static SUMMARIZER: OnceLock<Option<Summarizer>> = OnceLock::new();
+
+fn summarizer() -> Option<&'static Summarizer> {
+    SUMMARIZER
+        .get_or_init(|| {
+            let spawned = std::thread::Builder::new()
+                .name("logcall-summary".to_string())
+                .spawn(|| run(summarizer().unwrap()));
+            match spawned {
+                Ok(_) => Some(Summarizer {
+                    due: Mutex::new(Vec::new()),
+                    changed: Condvar::new(),
+                }),
+                Err(err) => {
+                    log::warn!("failed to spawn the logcall summary thread: {err}");
+                    None
+                }
+            }
+        })
+        .as_ref()
+}
+
+fn run(summarizer: &Summarizer) {
+    let mut due = summarizer.due.lock().unwrap_or_else(|err| err.into_inner());
+    loop {
+        let now = Instant::now();
+        let Some(&(deadline, _)) = due.iter().min_by_key(|(deadline, _)| *deadline) else {
+            due = summarizer
+                .changed
+                .wait(due)
+                .unwrap_or_else(|err| err.into_inner());
+            continue;
+        };
+        if deadline > now {
+            due = summarizer
+                .changed
+                .wait_timeout(due, deadline - now)
+                .unwrap_or_else(|err| err.into_inner())
+                .0;
+            continue;
+        }
+        let mut ended = Vec::new();
+        for (deadline, summary) in due.iter_mut() {
+            if *deadline <= now {
+                let interval = summary.meta.get().unwrap().interval;
+                *deadline += interval;
+                ended.push(*summary);
+            }
+        }
+        // Logged outside of the lock, as the logger may call into instrumented functions.
+        drop(due);
+        for summary in ended {
+            summary.flush();
+        }
+        due = summarizer.due.lock().unwrap_or_else(|err| err.into_inner());
+    }
+}
+
+impl Summary {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const ZERO: AtomicU64 = AtomicU64::new(0);
+        Summary {
+            meta: OnceLock::new(),
+            errors: AtomicU64::new(0),
+            level: AtomicUsize::new(usize::MAX),
+            buckets: [ZERO; BUCKETS],
+        }
+    }
+
+    // Counts the call in the current window, which is logged by the summary thread at its end.
+    #[allow(clippy::too_many_arguments)]
+    pub fn record(
+        &'static self,
+        interval: Duration,
+        level: Level,
+        target: &'static str,
+        fn_name: &'static str,
+        outcome: Option<&'static str>,
+        start: Instant,
+    ) {
+        let nanos = u64::try_from(start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        self.buckets[bucket(nanos)].fetch_add(1, Ordering::Relaxed);
+        if outcome == Some("err") {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        self.level.fetch_min(level as usize, Ordering::Relaxed);
+        if self.meta.get().is_none() {
+            self.register(interval, target, fn_name);
+        }
+    }
+
+    #[cold]
+    fn register(&'static self, interval: Duration, target: &'static str, fn_name: &'static str) {
+        let meta = Meta {
+            interval,
+            target,
+            fn_name,
+        };
+        if self.meta.set(meta).is_err() {
+            return;
+        }
+        if let Some(summarizer) = summarizer() {
+            let mut due = summarizer.due.lock().unwrap_or_else(|err| err.into_inner());
+            due.push((Instant::now() + interval, self));
+            summarizer.changed.notify_one();
+        }
+    }
+
+    // Logs `fn summary over 60s: calls=1200 errors=3 p50=1.2ms p95=8.4ms p99=20.1ms` at the
+    // most severe level of the calls of the window, unless it had no calls.
+    fn flush(&self) {
+        let meta = self.meta.get().unwrap();
+        let buckets: Vec<u64> = self
+            .buckets
+            .iter()
+            .map(|bucket| bucket.swap(0, Ordering::Relaxed))
+            .collect();
+        let errors = self.errors.swap(0, Ordering::Relaxed);
+        let level = self.level.swap(usize::MAX, Ordering::Relaxed);
+        let calls: u64 = buckets.iter().sum();
+        let Some(level) = Level::iter().find(|l| *l as usize == level) else {
+            return;
+        };
+        if calls == 0 {
+            return;
+        }
+        log::log!(
+            target: meta.target,
+            level,
+            "{} summary over {:?}: calls={} errors={} p50={:?} p95={:?} p99={:?}",
+            meta.fn_name,
+            meta.interval,
+            calls,
+            errors,
+            percentile(&buckets, calls, 50),
+            percentile(&buckets, calls, 95),
+            percentile(&buckets, calls, 99),
+        );
+    }
+}
+
+// The durations under 8ns have a bucket each, and the longer ones share the buckets of their
+// power of two by their 3 bits following the most significant one.
+fn bucket(nanos: u64) -> usize {
+    if nanos < SUB_BUCKETS as u64 {
+        return nanos as usize;
+    }
+    let msb = 63 - nanos.leading_zeros() as usize;
+    let shift = msb - 3;
+    let sub = (nanos >> shift) as usize - SUB_BUCKETS;
+    (shift + 1) * SUB_BUCKETS + sub
+}
+
+// The middle of the durations of the bucket.
+fn bucket_value(bucket: usize) -> Duration {
+    if bucket < SUB_BUCKETS {
+        return Duration::from_nanos(bucket as u64);
+    }
+    let shift = bucket / SUB_BUCKETS - 1;
+    let lower = ((SUB_BUCKETS + bucket % SUB_BUCKETS) as u64) << shift;
+    Duration::from_nanos(lower + (1 << shift) / 2)
+}
+
+fn percentile(buckets: &[u64], calls: u64, percent: u64) -> Duration {
+    let rank = (calls * percent).div_ceil(100).max(1);
+    let mut seen = 0;
+    for (bucket, count) in buckets.iter().enumerate() {
+        seen += count;
+        if seen >= rank {
+            return bucket_value(bucket);
+        }
+    }
+    Duration::ZERO
+}
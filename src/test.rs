@@ -0,0 +1,63 @@
+//! Assertions on the instrumentation, for tests.
+//!
+//! With the `registry` feature, a test can assert that the functions that policy requires to be
+//! logged, e.g. the handlers of an audit-critical module, are still instrumented, so CI fails
+//! when the attribute is removed or forgotten:
+//!
+//! ```ignore
+//! #[test]
+//! fn payments_are_logged() {
+//!     logcall::test::assert_instrumented!(my_app::payments::charge);
+//!     logcall::test::assert_instrumented!(crate::payments::Processor::refund);
+//! }
+//! ```
+
+use crate::registry;
+
+#[doc(inline)]
+pub use crate::__assert_instrumented as assert_instrumented;
+
+/// Returns whether the function at `path`, e.g. `my_app::payments::charge`, is instrumented.
+///
+/// Methods are registered under the path of their module, so `my_app::payments::Processor::refund`
+/// matches `my_app::payments::refund` as well.
+pub fn is_instrumented(path: &str) -> bool {
+    let method = path
+        .rsplit_once("::")
+        .and_then(|(parent, name)| Some(format!("{}::{}", parent.rsplit_once("::")?.0, name)));
+    registry::functions()
+        .iter()
+        .any(|function| function.name() == path || Some(function.name()) == method.as_deref())
+}
+
+// `crate::payments::charge` => `my_app::payments::charge`
+#[doc(hidden)]
+pub fn resolve_path(path: &str, module_path: &str) -> String {
+    match path.strip_prefix("crate::") {
+        Some(rest) => {
+            let krate = module_path.split("::").next().unwrap_or(module_path);
+            format!("{krate}::{rest}")
+        }
+        None => path.to_string(),
+    }
+}
+
+/// Asserts that the function at the given path is instrumented with `#[logcall]`.
+///
+/// The path starts with the crate name, or with `crate` for the functions of the current crate.
+/// The assertion fails with the `disabled` feature, which removes the instrumentation.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __assert_instrumented {
+    ($($segment:ident)::+) => {{
+        let path = $crate::test::resolve_path(
+            concat!($(stringify!($segment), "::"),+).trim_end_matches("::"),
+            module_path!(),
+        );
+        assert!(
+            $crate::test::is_instrumented(&path),
+            "`{}` is not instrumented with `#[logcall]`",
+            path
+        );
+    }};
+}
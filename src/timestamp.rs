@@ -0,0 +1,53 @@
+use std::fmt;
+use std::fmt::Display;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+
+static EPOCH: OnceLock<Instant> = OnceLock::new();
+
+// Takes the epoch at the start of the process, from the initializers run before `main`. On the
+// other platforms, it is taken by the first timestamped record.
+#[used]
+#[cfg_attr(
+    any(target_os = "linux", target_os = "android", target_os = "freebsd"),
+    link_section = ".init_array"
+)]
+#[cfg_attr(target_vendor = "apple", link_section = "__DATA,__mod_init_func")]
+#[cfg_attr(windows, link_section = ".CRT$XCU")]
+static INIT_EPOCH: extern "C" fn() = {
+    extern "C" fn init_epoch() {
+        EPOCH.get_or_init(Instant::now);
+    }
+    init_epoch
+};
+
+// Renders ` t_ns=1234567` for `#[logcall(timestamp)]`: the nanoseconds elapsed on the monotonic
+// clock since the start of the process, taken when the record is built, so the latency between
+// two records doesn't depend on the resolution of the backend's clock.
+#[doc(hidden)]
+pub struct Timestamp(u128);
+
+impl Timestamp {
+    pub fn now() -> Self {
+        let now = Instant::now();
+        Timestamp(now.duration_since(*EPOCH.get_or_init(|| now)).as_nanos())
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, " t_ns={}", self.0)
+    }
+}
+
+// Renders ` elapsed=1.234ms` for `#[logcall(elapsed)]`: the wall time from the start of the call,
+// or from the first poll of an async function, until the record is built.
+#[doc(hidden)]
+pub struct Elapsed(pub Duration);
+
+impl Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, " elapsed={:?}", self.0)
+    }
+}
@@ -0,0 +1,38 @@
+use std::fmt;
+use std::fmt::Debug;
+use std::sync::RwLock;
+
+type Tokenizer = Box<dyn Fn(&str) -> String + Send + Sync>;
+
+static TOKENIZER: RwLock<Option<Tokenizer>> = RwLock::new(None);
+
+// Rendered in place of the value when no tokenizer is installed, so that raw values never
+// reach the logs.
+const UNTOKENIZED: &str = "<untokenized>";
+
+/// Installs the callback used for arguments listed in `#[logcall(tokenize(...))]`.
+///
+/// The callback receives the `Debug` rendering of the value and should return a stable
+/// pseudonymous token, e.g. a keyed hash, so that records remain joinable across calls
+/// without containing the raw value.
+///
+/// ```
+/// logcall::set_tokenizer(|value| format!("tok_{}", value.len()));
+/// ```
+pub fn set_tokenizer(tokenizer: impl Fn(&str) -> String + Send + Sync + 'static) {
+    *TOKENIZER.write().unwrap_or_else(|err| err.into_inner()) = Some(Box::new(tokenizer));
+}
+
+// Renders a value through the installed tokenizer.
+#[doc(hidden)]
+pub struct Tokenize<'a, T: ?Sized>(pub &'a T);
+
+impl<T: Debug + ?Sized> Debug for Tokenize<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tokenizer = TOKENIZER.read().unwrap_or_else(|err| err.into_inner());
+        match &*tokenizer {
+            Some(tokenizer) => f.write_str(&tokenizer(&format!("{:?}", self.0))),
+            None => f.write_str(UNTOKENIZED),
+        }
+    }
+}
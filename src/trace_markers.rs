@@ -0,0 +1,94 @@
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::OnceLock;
+
+// The `trace_marker` file of ftrace, opened on the first call, if the tracing file system is
+// mounted and writable. The markers are only supported on Linux.
+static TRACE_MARKER: OnceLock<Option<File>> = OnceLock::new();
+
+fn trace_marker() -> Option<&'static File> {
+    TRACE_MARKER
+        .get_or_init(|| {
+            if !cfg!(target_os = "linux") {
+                return None;
+            }
+            [
+                "/sys/kernel/tracing/trace_marker",
+                "/sys/kernel/debug/tracing/trace_marker",
+            ]
+            .into_iter()
+            .find_map(|path| OpenOptions::new().write(true).open(path).ok())
+        })
+        .as_ref()
+}
+
+// Writes the marker with a single `write`, so that the markers of concurrent calls don't
+// interleave.
+fn write_marker(marker: String) {
+    if let Some(mut file) = trace_marker() {
+        let _ = file.write_all(marker.as_bytes());
+    }
+}
+
+// Delimits a call with markers: `B|pid|name` and `E|pid` for a synchronous call, and
+// `S|pid|name|cookie` and `F|pid|name|cookie` for an async call.
+#[doc(hidden)]
+pub struct MarkerGuard {
+    name: &'static str,
+    // The cookie of an async slice, which pairs its markers.
+    cookie: Option<u64>,
+}
+
+impl MarkerGuard {
+    pub fn begin(name: &'static str) -> Self {
+        if trace_marker().is_some() {
+            write_marker(format!("B|{}|{}", std::process::id(), name));
+        }
+        MarkerGuard { name, cookie: None }
+    }
+
+    pub fn begin_async(name: &'static str) -> Self {
+        static NEXT_COOKIE: AtomicU64 = AtomicU64::new(1);
+        let cookie = NEXT_COOKIE.fetch_add(1, Ordering::Relaxed);
+        if trace_marker().is_some() {
+            write_marker(format!("S|{}|{}|{}", std::process::id(), name, cookie));
+        }
+        MarkerGuard {
+            name,
+            cookie: Some(cookie),
+        }
+    }
+
+    pub fn end(self) {}
+}
+
+impl Drop for MarkerGuard {
+    fn drop(&mut self) {
+        if trace_marker().is_none() {
+            return;
+        }
+        let pid = std::process::id();
+        match self.cookie {
+            None => write_marker(format!("E|{pid}")),
+            Some(cookie) => write_marker(format!("F|{}|{}|{}", pid, self.name, cookie)),
+        }
+    }
+}
+
+// Delimits the call with the `trace-markers` feature.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __marker_guard {
+    (begin $guard:ident, $fn_name:expr) => {
+        let $guard = $crate::__private::MarkerGuard::begin($fn_name);
+    };
+    (begin_async $guard:ident, $fn_name:expr) => {
+        let $guard = $crate::__private::MarkerGuard::begin_async($fn_name);
+    };
+    (end $guard:ident) => {
+        $guard.end();
+    };
+}
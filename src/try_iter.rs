@@ -0,0 +1,108 @@
+use std::fmt::Debug;
+
+use log::Level;
+
+// The iterator returned by a function annotated with `#[logcall(try_iter)]`.
+//
+// The function returns before any item is produced, so the record is logged by the iterator
+// instead: at `err_level` on the first `Err` item, or at `ok_level` once the iterator is
+// exhausted, or dropped, without an error. Either way, the number of `Ok` items seen so far is
+// reported.
+#[doc(hidden)]
+pub struct TryIter<I> {
+    inner: I,
+    ok_level: Option<Level>,
+    err_level: Option<Level>,
+    ok_target: &'static str,
+    err_target: &'static str,
+    fn_name: &'static str,
+    input: String,
+    ok: usize,
+    logged: bool,
+}
+
+impl<I> TryIter<I> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        inner: I,
+        ok_level: Option<Level>,
+        err_level: Option<Level>,
+        ok_target: &'static str,
+        err_target: &'static str,
+        fn_name: &'static str,
+        input: String,
+    ) -> Self {
+        TryIter {
+            inner,
+            ok_level,
+            err_level,
+            ok_target,
+            err_target,
+            fn_name,
+            input,
+            ok: 0,
+            logged: false,
+        }
+    }
+
+    fn log_ok(&mut self, suffix: &str) {
+        if self.logged {
+            return;
+        }
+        self.logged = true;
+        if let Some(level) = self.ok_level {
+            log::log!(
+                target: self.ok_target,
+                level,
+                "{}({}) => {} Ok items{}",
+                self.fn_name,
+                self.input,
+                self.ok,
+                suffix
+            );
+        }
+    }
+}
+
+impl<I, T, E> Iterator for TryIter<I>
+where
+    I: Iterator<Item = Result<T, E>>,
+    E: Debug,
+{
+    type Item = Result<T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.inner.next();
+        match &item {
+            Some(Ok(_)) => self.ok += 1,
+            Some(Err(err)) => {
+                if !self.logged {
+                    self.logged = true;
+                    if let Some(level) = self.err_level {
+                        log::log!(
+                            target: self.err_target,
+                            level,
+                            "{}({}) => Err({:?}) after {} Ok items",
+                            self.fn_name,
+                            self.input,
+                            err,
+                            self.ok
+                        );
+                    }
+                }
+            }
+            None => self.log_ok(""),
+        }
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<I> Drop for TryIter<I> {
+    fn drop(&mut self) {
+        self.log_ok(", dropped");
+    }
+}
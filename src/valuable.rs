@@ -0,0 +1,46 @@
+use std::fmt::Debug;
+
+use tracing::field::DebugValue;
+
+// Selects how a value is recorded as a field of the `tracing` events with the `valuable`
+// feature, using autoref specialization: `(&&ValuableProbe(&value)).field()` resolves to
+// `ValuableKind`, which records it through `valuable`, keeping its structure, when it
+// implements `Valuable` and `--cfg tracing_unstable` is set, and to `DebugFieldKind`, which
+// records its `Debug`, otherwise.
+#[doc(hidden)]
+pub struct ValuableProbe<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait ValuableKind<'a> {
+    fn field(&self) -> valuable::Value<'a>;
+}
+
+#[doc(hidden)]
+pub trait DebugFieldKind<'a, T: Debug + ?Sized> {
+    fn field(&self) -> DebugValue<&'a T>;
+}
+
+impl<'a, T: Debug + ?Sized> DebugFieldKind<'a, T> for ValuableProbe<'a, T> {
+    fn field(&self) -> DebugValue<&'a T> {
+        tracing::field::debug(self.0)
+    }
+}
+
+// `tracing` only implements its `Value` for `valuable::Value` with `--cfg tracing_unstable`.
+#[cfg(tracing_unstable)]
+impl<'a, T: valuable::Valuable + ?Sized> ValuableKind<'a> for &ValuableProbe<'a, T> {
+    fn field(&self) -> valuable::Value<'a> {
+        self.0.as_value()
+    }
+}
+
+// Records a value as a field of the `tracing` events with the `valuable` feature.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __valuable {
+    ($value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__private::{DebugFieldKind as _, ValuableKind as _};
+        (&&$crate::__private::ValuableProbe($value)).field()
+    }};
+}
@@ -0,0 +1,45 @@
+// Integration tests are compiled with `cfg(test)`, unlike the trybuild cases.
+#![cfg(not(feature = "disabled"))]
+
+use std::sync::Mutex;
+
+static LEVELS: Mutex<Vec<log::Level>> = Mutex::new(Vec::new());
+
+struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        LEVELS.lock().unwrap().push(record.level());
+    }
+
+    fn flush(&self) {}
+}
+
+#[logcall::logcall(ok = "info", err = "error", test_level = "trace")]
+fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+#[logcall::logcall("warn", test_level = "debug")]
+async fn double(a: u32) -> u32 {
+    a * 2
+}
+
+#[test]
+fn test_level() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    parse("1").unwrap();
+    parse("x").unwrap_err();
+    pollster::block_on(double(2));
+
+    assert_eq!(
+        *LEVELS.lock().unwrap(),
+        [log::Level::Trace, log::Level::Trace, log::Level::Debug]
+    );
+}
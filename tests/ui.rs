@@ -1,6 +1,82 @@
 #[test]
 fn ui() {
     let t = trybuild::TestCases::new();
-    t.compile_fail("tests/ui/err/*.rs");
-    t.pass("tests/ui/ok/*.rs");
+    // `disabled` turns every other feature into a no-op.
+    #[cfg(feature = "disabled")]
+    t.pass("tests/ui/features/disabled.rs");
+    // `kv` moves the arguments and the return value out of the messages.
+    #[cfg(all(feature = "kv", not(feature = "disabled")))]
+    t.pass("tests/ui/features/kv.rs");
+    // Without `std`, only the `defmt` backend is available.
+    #[cfg(all(feature = "defmt", not(feature = "std"), not(feature = "disabled")))]
+    t.pass("tests/ui/features/defmt.rs");
+    #[cfg(all(feature = "std", not(any(feature = "disabled", feature = "kv"))))]
+    {
+        t.compile_fail("tests/ui/err/*.rs");
+        t.pass("tests/ui/ok/*.rs");
+        #[cfg(feature = "alloc-count")]
+        t.pass("tests/ui/features/alloc-count.rs");
+        #[cfg(feature = "audit")]
+        t.pass("tests/ui/features/audit.rs");
+        #[cfg(feature = "call-graph")]
+        t.pass("tests/ui/features/call-graph.rs");
+        #[cfg(feature = "chrome-trace")]
+        t.pass("tests/ui/features/chrome-trace.rs");
+        #[cfg(feature = "cpu-time")]
+        t.pass("tests/ui/features/cpu-time.rs");
+        #[cfg(feature = "defmt")]
+        t.pass("tests/ui/features/defmt.rs");
+        #[cfg(feature = "eyre")]
+        t.pass("tests/ui/features/eyre.rs");
+        #[cfg(feature = "fastrace")]
+        t.pass("tests/ui/features/fastrace.rs");
+        #[cfg(all(feature = "fastrace", feature = "serde"))]
+        t.pass("tests/ui/features/fastrace-serde.rs");
+        #[cfg(feature = "journald")]
+        t.pass("tests/ui/features/journald.rs");
+        #[cfg(feature = "metrics")]
+        t.pass("tests/ui/features/metrics.rs");
+        #[cfg(feature = "metrics")]
+        t.pass("tests/ui/features/metrics-error-label.rs");
+        #[cfg(feature = "mobile")]
+        t.pass("tests/ui/features/mobile.rs");
+        #[cfg(feature = "otel")]
+        t.pass("tests/ui/features/otel.rs");
+        #[cfg(all(feature = "otel", feature = "serde"))]
+        t.pass("tests/ui/features/otel-serde.rs");
+        #[cfg(feature = "puffin")]
+        t.pass("tests/ui/features/puffin.rs");
+        #[cfg(feature = "quick-logger")]
+        t.pass("tests/ui/features/quick-logger.rs");
+        #[cfg(feature = "registry")]
+        t.pass("tests/ui/features/assert-instrumented.rs");
+        #[cfg(feature = "registry")]
+        t.pass("tests/ui/features/registry.rs");
+        #[cfg(feature = "registry")]
+        t.pass("tests/ui/features/fn-id.rs");
+        #[cfg(feature = "sentry")]
+        t.pass("tests/ui/features/sentry.rs");
+        #[cfg(feature = "serde")]
+        t.pass("tests/ui/features/serde.rs");
+        #[cfg(feature = "slog")]
+        t.pass("tests/ui/features/slog.rs");
+        #[cfg(feature = "stats")]
+        t.pass("tests/ui/features/stats.rs");
+        #[cfg(feature = "trace-markers")]
+        t.pass("tests/ui/features/trace-markers.rs");
+        #[cfg(feature = "tracing")]
+        t.pass("tests/ui/features/tracing.rs");
+        #[cfg(feature = "tracing")]
+        t.pass("tests/ui/features/tracing-backend.rs");
+        #[cfg(feature = "tracing")]
+        t.pass("tests/ui/features/tracing-span.rs");
+        #[cfg(feature = "tracy")]
+        t.pass("tests/ui/features/tracy.rs");
+        #[cfg(feature = "tokio")]
+        t.pass("tests/ui/features/tokio.rs");
+        #[cfg(feature = "valuable")]
+        t.pass("tests/ui/features/valuable.rs");
+        #[cfg(feature = "wasm-console")]
+        t.pass("tests/ui/features/wasm-console.rs");
+    }
 }
@@ -0,0 +1,25 @@
+// The logger of the UI tests, which collects the messages of the records in `LINES`, and them
+// along with their levels in `RECORDS`.
+#![allow(dead_code)]
+
+use std::sync::Mutex;
+
+pub static LINES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+pub static RECORDS: Mutex<Vec<(log::Level, String)>> = Mutex::new(Vec::new());
+
+pub struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        // Rendered before locking, as rendering may panic.
+        let line = record.args().to_string();
+        LINES.lock().unwrap().push(line.clone());
+        RECORDS.lock().unwrap().push((record.level(), line));
+    }
+
+    fn flush(&self) {}
+}
@@ -0,0 +1,4 @@
+#[logcall::logcall("info", slow = "18446744073709551615h")]
+fn f() {}
+
+fn main() {}
@@ -0,0 +1,5 @@
+logcall::extern_shim! {
+    fn strlen(s: *const std::os::raw::c_char) -> usize;
+}
+
+fn main() {}
@@ -0,0 +1,5 @@
+logcall::extern_shim! {
+    unsafe fn checksum(data: Option<&[u8; 16]>) -> u32;
+}
+
+fn main() {}
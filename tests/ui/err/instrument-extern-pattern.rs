@@ -0,0 +1,9 @@
+fn add((a, b): (u32, u32)) -> u32 {
+    a + b
+}
+
+logcall::instrument_extern! {
+    add as fn add_logged((a, b): (u32, u32)) -> u32;
+}
+
+fn main() {}
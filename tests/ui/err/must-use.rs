@@ -0,0 +1,22 @@
+#![deny(unused_must_use)]
+
+#[logcall::logcall("info")]
+#[must_use]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[logcall::logcall("info")]
+#[must_use]
+async fn add_async(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+async fn run() {
+    add(1, 2);
+    add_async(1, 2).await;
+}
+
+fn main() {
+    let _ = run();
+}
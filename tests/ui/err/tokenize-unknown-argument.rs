@@ -0,0 +1,4 @@
+#[logcall::logcall("info", tokenize(password))]
+fn login(email: &str) {}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[logcall::logcall("info")]
+#[track_caller]
+fn poll_parse(input: &str) -> std::task::Poll<Result<u32, std::num::ParseIntError>> {
+    std::task::Poll::Ready(Ok(input.parse()?))
+}
+
+fn main() {
+    let _ = poll_parse("1");
+}
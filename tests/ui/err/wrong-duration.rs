@@ -0,0 +1,4 @@
+#[logcall::logcall("info", dedup = "10 parsecs")]
+fn f() {}
+
+fn main() {}
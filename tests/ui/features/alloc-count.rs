@@ -0,0 +1,51 @@
+use std::alloc::System;
+
+use logcall::CountingAllocator;
+
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator::new(System);
+
+#[logcall::logcall("info", allocations)]
+fn zeroes(n: usize) -> usize {
+    vec![0u8; n].len()
+}
+
+#[logcall::logcall("info", allocations)]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[logcall::logcall("info", allocations)]
+async fn boxed(n: u64) -> u64 {
+    *Box::new(n)
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    zeroes(100);
+    add(1, 2);
+    pollster::block_on(boxed(7));
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 3);
+    assert!(
+        lines[0].ends_with("::zeroes(n = 100) => 100 allocs=1 alloc_bytes=100"),
+        "{lines:?}"
+    );
+    assert!(
+        lines[1].ends_with("::add(a = 1, b = 2) => 3 allocs=0 alloc_bytes=0"),
+        "{lines:?}"
+    );
+    assert!(
+        lines[2].ends_with("::boxed(n = 7) => 7 allocs=1 alloc_bytes=8"),
+        "{lines:?}"
+    );
+}
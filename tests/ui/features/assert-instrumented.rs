@@ -0,0 +1,43 @@
+mod payments {
+    #[logcall::logcall("info")]
+    pub fn charge(amount: u32) -> u32 {
+        amount
+    }
+
+    pub fn refund(amount: u32) -> u32 {
+        amount
+    }
+
+    pub struct Processor;
+
+    #[logcall::logcall("info")]
+    impl Processor {
+        pub fn capture(&self, amount: u32) -> u32 {
+            amount
+        }
+    }
+}
+
+fn main() {
+    logcall::test::assert_instrumented!(crate::payments::charge);
+    logcall::test::assert_instrumented!(crate::payments::Processor::capture);
+    assert!(logcall::test::is_instrumented(&format!(
+        "{}::payments::charge",
+        module_path!()
+    )));
+    assert!(!logcall::test::is_instrumented(&format!(
+        "{}::payments::refund",
+        module_path!()
+    )));
+
+    std::panic::set_hook(Box::new(|_| {}));
+    let err = std::panic::catch_unwind(|| {
+        logcall::test::assert_instrumented!(crate::payments::refund);
+    })
+    .unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(
+        message.ends_with("::payments::refund` is not instrumented with `#[logcall]`"),
+        "{message}"
+    );
+}
@@ -0,0 +1,27 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info", audit)]
+fn transfer(amount: u32) -> u32 {
+    amount
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    transfer(1);
+    logcall::audit::set_key(b"secret".to_vec());
+    transfer(2);
+
+    let lines = LINES.lock().unwrap();
+    assert!(lines[0].ends_with("::transfer(amount = 1) => 1 sig=unsigned"));
+    assert!(!logcall::audit::verify(b"secret", &lines[0]));
+    assert!(lines[1].contains("::transfer(amount = 2) => 2 sig="));
+    assert!(logcall::audit::verify(b"secret", &lines[1]));
+    assert!(!logcall::audit::verify(b"other", &lines[1]));
+    assert!(!logcall::audit::verify(b"secret", &lines[1].replace("=> 2", "=> 3")));
+}
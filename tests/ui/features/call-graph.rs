@@ -0,0 +1,56 @@
+#[logcall::logcall("info")]
+fn parse(input: &str) -> usize {
+    input.len()
+}
+
+fn validate(input: &str) -> usize {
+    parse(input)
+}
+
+#[logcall::logcall("info")]
+async fn fetch(input: &str) -> usize {
+    parse(input)
+}
+
+#[logcall::logcall("info")]
+async fn handle(input: &str) -> usize {
+    validate(input) + fetch(input).await + fetch(input).await
+}
+
+fn main() {
+    parse("before");
+
+    logcall::call_graph::start();
+    pollster::block_on(handle("a"));
+    parse("b");
+    logcall::call_graph::stop();
+
+    parse("after");
+
+    let edges = logcall::call_graph::edges();
+    let edges: Vec<_> = edges
+        .iter()
+        .map(|(caller, callee, calls)| {
+            let short = |name: &str| name.rsplit("::").next().unwrap().to_string();
+            (short(caller), short(callee), *calls)
+        })
+        .collect();
+    assert_eq!(
+        edges,
+        [
+            ("<root>".to_string(), "handle".to_string(), 1),
+            ("<root>".to_string(), "parse".to_string(), 1),
+            ("fetch".to_string(), "parse".to_string(), 2),
+            ("handle".to_string(), "fetch".to_string(), 2),
+            ("handle".to_string(), "parse".to_string(), 1),
+        ],
+    );
+
+    let mut dot = Vec::new();
+    logcall::call_graph::write_dot(&mut dot).unwrap();
+    let dot = String::from_utf8(dot).unwrap();
+    assert!(dot.starts_with("digraph logcall {\n"), "{}", dot);
+    assert!(dot.contains("\"<root>\" -> \""), "{}", dot);
+    assert!(dot.contains("::fetch\" [label=\"2\"];\n"), "{}", dot);
+    assert!(dot.ends_with("}\n"), "{}", dot);
+}
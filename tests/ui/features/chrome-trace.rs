@@ -0,0 +1,37 @@
+#[logcall::logcall("info")]
+fn parse(input: &str) -> usize {
+    input.len()
+}
+
+#[logcall::logcall("info")]
+async fn handle(input: &str) -> usize {
+    parse(input)
+}
+
+fn main() {
+    parse("before");
+
+    logcall::chrome_trace::start();
+    pollster::block_on(handle("a\"b"));
+    std::thread::spawn(|| parse("thread")).join().unwrap();
+    logcall::chrome_trace::stop();
+
+    parse("after");
+
+    let mut trace = Vec::new();
+    logcall::chrome_trace::write(&mut trace).unwrap();
+    let trace = String::from_utf8(trace).unwrap();
+
+    assert!(trace.starts_with("{\"traceEvents\":["), "{}", trace);
+    assert!(trace.ends_with("],\"displayTimeUnit\":\"ms\"}"), "{}", trace);
+    assert_eq!(trace.matches("\"ph\":\"X\"").count(), 3, "{}", trace);
+    assert_eq!(trace.matches("::parse\"").count(), 2, "{}", trace);
+    assert_eq!(trace.matches("::handle\"").count(), 1, "{}", trace);
+    assert!(trace.contains("\"tid\":1}"), "{}", trace);
+    assert!(trace.contains("\"tid\":2}"), "{}", trace);
+
+    logcall::chrome_trace::clear();
+    let mut trace = Vec::new();
+    logcall::chrome_trace::write(&mut trace).unwrap();
+    assert_eq!(trace, b"{\"traceEvents\":[],\"displayTimeUnit\":\"ms\"}");
+}
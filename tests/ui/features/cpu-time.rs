@@ -0,0 +1,64 @@
+use std::time::Duration;
+use std::time::Instant;
+
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info", cpu_time)]
+fn spin(ms: u64) -> u64 {
+    let start = Instant::now();
+    let mut n = 0u64;
+    while start.elapsed() < Duration::from_millis(ms) {
+        n = std::hint::black_box(n.wrapping_add(1));
+    }
+    ms
+}
+
+#[logcall::logcall("info", cpu_time)]
+async fn wait(ms: u64) -> u64 {
+    std::thread::sleep(Duration::from_millis(ms));
+    ms
+}
+
+// "1.5ms", "850µs", "12ns"
+fn millis(duration: &str) -> f64 {
+    let unit_start = duration.find(|c: char| c.is_alphabetic()).unwrap();
+    let (amount, unit) = duration.split_at(unit_start);
+    let amount: f64 = amount.parse().unwrap();
+    match unit {
+        "ns" => amount / 1_000_000.0,
+        "µs" => amount / 1_000.0,
+        "ms" => amount,
+        "s" => amount * 1_000.0,
+        _ => panic!("unexpected unit in {duration}"),
+    }
+}
+
+// (wall, cpu)
+fn times(line: &str) -> (f64, f64) {
+    let (_, times) = line.split_once(" elapsed=").unwrap();
+    let (wall, cpu) = times.split_once(" cpu=").unwrap();
+    (millis(wall), millis(cpu))
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    spin(30);
+    pollster::block_on(wait(30));
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].contains("::spin(ms = 30) => 30 elapsed="), "{lines:?}");
+    assert!(lines[1].contains("::wait(ms = 30) => 30 elapsed="), "{lines:?}");
+
+    // Busy work burns CPU time, while waiting doesn't.
+    let (wall, cpu) = times(&lines[0]);
+    assert!(wall >= 30.0 && cpu >= 15.0, "{lines:?}");
+    let (wall, cpu) = times(&lines[1]);
+    assert!(wall >= 30.0 && cpu < 15.0, "{lines:?}");
+}
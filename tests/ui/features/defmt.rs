@@ -0,0 +1,75 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use either::Either;
+
+// The number of records. `defmt` encodes them with the indices of their interned strings, which
+// only the linker script of the firmware assigns, so they are counted rather than decoded.
+// Below the `error` level, the records are compiled out unless `DEFMT_LOG` enables them.
+static RECORDS: AtomicUsize = AtomicUsize::new(0);
+
+#[defmt::global_logger]
+struct Logger;
+
+unsafe impl defmt::Logger for Logger {
+    fn acquire() {
+        RECORDS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    unsafe fn flush() {}
+
+    unsafe fn release() {}
+
+    unsafe fn write(_: &[u8]) {}
+}
+
+defmt::timestamp!("{=u32}", 0);
+
+#[derive(defmt::Format)]
+struct Sensor {
+    id: u8,
+}
+
+#[logcall::logcall("error", backend = "defmt")]
+fn read(sensor: &Sensor, retries: u32) -> u16 {
+    sensor.id as u16 * 100 + retries as u16
+}
+
+// The arguments are borrowed by the body, so that they can be logged after the call.
+#[logcall::logcall("error", backend = "defmt")]
+fn label(name: &str, sensor: Sensor) -> u8 {
+    let Sensor { id } = sensor;
+    name.len() as u8 + id
+}
+
+#[logcall::logcall(ok = "info", err = "error", backend = "defmt")]
+fn parse(input: &str) -> Result<u8, ()> {
+    input.parse().map_err(|_| ())
+}
+
+#[logcall::logcall(left = "info", right = "error", backend = "defmt")]
+fn lookup(key: u8) -> Either<u8, i8> {
+    if key < 128 {
+        Either::Left(key)
+    } else {
+        Either::Right(-1)
+    }
+}
+
+#[logcall::logcall("error", backend = "defmt")]
+async fn measure(sensor: &Sensor) -> u8 {
+    sensor.id
+}
+
+fn main() {
+    assert_eq!(read(&Sensor { id: 1 }, 2), 102);
+    assert_eq!(label("temp", Sensor { id: 1 }), 5);
+    assert_eq!(parse("7"), Ok(7));
+    assert_eq!(parse("x"), Err(()));
+    assert_eq!(lookup(1), Either::Left(1));
+    assert_eq!(lookup(200), Either::Right(-1));
+    assert_eq!(pollster::block_on(measure(&Sensor { id: 3 })), 3);
+
+    // `read`, `label`, `parse("x")`, `lookup(200)` and `measure`.
+    assert_eq!(RECORDS.load(Ordering::Relaxed), 5);
+}
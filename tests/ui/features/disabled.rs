@@ -0,0 +1,63 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+/// Adds two numbers.
+#[logcall::logcall("info")]
+#[must_use]
+#[inline]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[logcall::logcall(ok = "info", err = "error", after_error)]
+fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+#[logcall::logcall("info")]
+async fn double(a: u32) -> u32 {
+    a * 2
+}
+
+// Without instrumentation, the return value doesn't need to implement `Debug`.
+struct Opaque;
+
+#[logcall::logcall]
+fn opaque() -> Opaque {
+    Opaque
+}
+
+#[logcall::wrap_dyn("info")]
+trait Handler {
+    fn call(&self, request: u32) -> u32;
+}
+
+struct Echo;
+
+impl Handler for Echo {
+    fn call(&self, request: u32) -> u32 {
+        request
+    }
+}
+
+logcall::instrument_extern! {
+    u32::checked_add as fn checked_add_logged(a: u32, b: u32) -> Option<u32>;
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    assert_eq!(add(1, 2), 3);
+    assert_eq!(parse("1"), Ok(1));
+    assert!(parse("x").is_err());
+    assert_eq!(pollster::block_on(double(2)), 4);
+    let Opaque = opaque();
+    assert_eq!(LoggedHandler::new(Echo).call(1), 1);
+    assert_eq!(checked_add_logged(1, 2), Some(3));
+
+    assert!(LINES.lock().unwrap().is_empty());
+}
@@ -0,0 +1,53 @@
+use eyre::WrapErr;
+
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+// A colored handler, in the spirit of `color-eyre`.
+struct Handler;
+
+impl eyre::EyreHandler for Handler {
+    fn debug(
+        &self,
+        error: &(dyn std::error::Error + 'static),
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(f, "\x1b[31m{}\x1b[0m", error)?;
+        let mut source = error.source();
+        while let Some(cause) = source {
+            write!(f, "\n\nCaused by:\n    \x1b[33m{}\x1b[0m", cause)?;
+            source = cause.source();
+        }
+        Ok(())
+    }
+}
+
+#[logcall::logcall(ok = "info", err = "error")]
+fn load(path: &str) -> eyre::Result<u32> {
+    if path.is_empty() {
+        return Ok(0);
+    }
+    Err(eyre::eyre!("file not found")).wrap_err("failed to load config")
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+    eyre::set_hook(Box::new(|_| Box::new(Handler))).unwrap();
+
+    load("").ok();
+    load("config.toml").ok();
+
+    let lines = LINES.lock().unwrap();
+    assert!(lines[0].ends_with("::load(path = \"\") => Ok(0)"), "{}", lines[0]);
+    assert!(
+        lines[1].ends_with(
+            "::load(path = \"config.toml\") => Err(failed to load config\n\nCaused by:\n    file not found)"
+        ),
+        "{}",
+        lines[1]
+    );
+}
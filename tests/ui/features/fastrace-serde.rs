@@ -0,0 +1,52 @@
+use std::sync::Mutex;
+
+use fastrace::collector::Config;
+use fastrace::collector::Reporter;
+use fastrace::collector::SpanContext;
+use fastrace::collector::SpanRecord;
+use fastrace::Span;
+use serde::Serialize;
+
+static SPANS: Mutex<Vec<SpanRecord>> = Mutex::new(Vec::new());
+
+struct Collector;
+
+impl Reporter for Collector {
+    fn report(&mut self, spans: Vec<SpanRecord>) {
+        SPANS.lock().unwrap().extend(spans);
+    }
+}
+
+// Not `Debug`: the events carry the same JSON as the records.
+#[derive(Serialize)]
+struct Account {
+    name: &'static str,
+    api_key: &'static str,
+}
+
+#[logcall::logcall("info", format = "json", ret_redact(api_key))]
+fn accounts() -> Vec<Account> {
+    vec![Account {
+        name: "ops",
+        api_key: "k1",
+    }]
+}
+
+fn main() {
+    fastrace::set_reporter(Collector, Config::default());
+
+    {
+        let root = Span::root("root", SpanContext::random());
+        let _guard = root.set_local_parent();
+        accounts();
+    }
+    fastrace::flush();
+
+    let spans = SPANS.lock().unwrap();
+    let events = &spans[0].events;
+    assert_eq!(events.len(), 1, "{events:?}");
+    assert_eq!(
+        events[0].properties[1].1.as_ref(),
+        r#"[{"api_key":"<redacted>","name":"ops"}]"#
+    );
+}
@@ -0,0 +1,55 @@
+use std::sync::Mutex;
+
+use fastrace::collector::Config;
+use fastrace::collector::Reporter;
+use fastrace::collector::SpanContext;
+use fastrace::collector::SpanRecord;
+use fastrace::Span;
+
+static SPANS: Mutex<Vec<SpanRecord>> = Mutex::new(Vec::new());
+
+struct Collector;
+
+impl Reporter for Collector {
+    fn report(&mut self, spans: Vec<SpanRecord>) {
+        SPANS.lock().unwrap().extend(spans);
+    }
+}
+
+#[logcall::logcall("info")]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[logcall::logcall(ok = "info", err = "error")]
+async fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+fn main() {
+    fastrace::set_reporter(Collector, Config::default());
+
+    // Outside of a span, the call is only logged.
+    add(0, 0);
+    {
+        let root = Span::root("root", SpanContext::random());
+        let _guard = root.set_local_parent();
+        add(1, 2);
+        pollster::block_on(parse("a")).unwrap_err();
+    }
+    fastrace::flush();
+
+    let spans = SPANS.lock().unwrap();
+    assert_eq!(spans.len(), 1);
+    let events = &spans[0].events;
+    assert_eq!(events.len(), 2, "{events:?}");
+    assert!(events[0].name.ends_with("::add"));
+    let properties = events[0]
+        .properties
+        .iter()
+        .map(|(key, value)| (key.as_ref(), value.as_ref()))
+        .collect::<Vec<_>>();
+    assert_eq!(properties, [("input", "a = 1, b = 2"), ("output", "3")]);
+    assert!(events[1].name.ends_with("::parse"));
+    assert!(events[1].properties[1].1.starts_with("Err(ParseIntError"));
+}
@@ -0,0 +1,48 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info", fn_id)]
+fn charge(amount: u32) -> u32 {
+    amount
+}
+
+#[logcall::logcall("info", fn_id)]
+async fn refund(amount: u32) -> u32 {
+    amount
+}
+
+#[logcall::logcall("info")]
+fn audit(amount: u32) -> u32 {
+    amount
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    charge(1);
+    pollster::block_on(refund(2));
+    audit(3);
+
+    let functions = logcall::registry::functions();
+    let ids: Vec<_> = functions.iter().map(|function| function.id()).collect();
+    assert_eq!(ids, [0, 1, 2]);
+    assert!(functions[1].name().ends_with("::charge"));
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines[0], "#1(amount = 1) => 1");
+    assert_eq!(lines[1], "#2(amount = 2) => 2");
+    assert!(lines[2].ends_with("::audit(amount = 3) => 3"), "{}", lines[2]);
+
+    let mut map = Vec::new();
+    logcall::registry::write_id_map(&mut map).unwrap();
+    let map = String::from_utf8(map).unwrap();
+    let map: Vec<_> = map.lines().collect();
+    assert_eq!(map.len(), 3);
+    assert!(map[0].starts_with("#0 ") && map[0].contains("::audit ("), "{:?}", map);
+    assert!(map[1].starts_with("#1 ") && map[1].contains("::charge ("), "{:?}", map);
+    assert!(map[2].starts_with("#2 ") && map[2].ends_with("fn-id.rs:13)"), "{:?}", map);
+}
@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use log::kv::Key;
+use log::kv::Value;
+use log::kv::VisitSource;
+
+static RECORDS: Mutex<Vec<(String, BTreeMap<String, String>)>> = Mutex::new(Vec::new());
+
+struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        struct Collect(BTreeMap<String, String>);
+
+        impl<'kvs> VisitSource<'kvs> for Collect {
+            fn visit_pair(
+                &mut self,
+                key: Key<'kvs>,
+                value: Value<'kvs>,
+            ) -> Result<(), log::kv::Error> {
+                self.0.insert(key.to_string(), value.to_string());
+                Ok(())
+            }
+        }
+
+        let mut fields = Collect(BTreeMap::new());
+        record.key_values().visit(&mut fields).unwrap();
+        RECORDS
+            .lock()
+            .unwrap()
+            .push((record.args().to_string(), fields.0));
+    }
+
+    fn flush(&self) {}
+}
+
+#[logcall::logcall(ok = "info", err = "error", journald)]
+fn charge(account: &str, amount: u32) -> Result<u32, String> {
+    if amount > 100 {
+        Err("insufficient funds".to_string())
+    } else {
+        Ok(100 - amount)
+    }
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    charge("alice", 30).unwrap();
+    charge("bob", 300).unwrap_err();
+
+    let records = RECORDS.lock().unwrap();
+    assert_eq!(records.len(), 2);
+
+    let (message, fields) = &records[0];
+    assert!(message.ends_with("::charge(account = \"alice\", amount = 30) => Ok(70)"));
+    assert_eq!(fields["PRIORITY"], "6");
+    assert!(fields["CODE_FUNC"].ends_with("::charge"));
+    assert_eq!(fields["FN_NAME"], fields["CODE_FUNC"]);
+    assert_eq!(fields["ARGS"], "account = \"alice\", amount = 30");
+    assert_eq!(fields["ACCOUNT"], "\"alice\"");
+    assert_eq!(fields["AMOUNT"], "30");
+    assert_eq!(fields["RET"], "Ok(70)");
+    assert_eq!(fields["RESULT"], "ok");
+
+    let (_, fields) = &records[1];
+    assert_eq!(fields["PRIORITY"], "3");
+    assert_eq!(fields["RET"], "Err(\"insufficient funds\")");
+    assert_eq!(fields["RESULT"], "err");
+}
@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use log::kv::Key;
+use log::kv::Value;
+use log::kv::VisitSource;
+
+static RECORDS: Mutex<Vec<(log::Level, String, BTreeMap<String, String>)>> =
+    Mutex::new(Vec::new());
+
+struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        struct Collect(BTreeMap<String, String>);
+
+        impl<'kvs> VisitSource<'kvs> for Collect {
+            fn visit_pair(
+                &mut self,
+                key: Key<'kvs>,
+                value: Value<'kvs>,
+            ) -> Result<(), log::kv::Error> {
+                self.0.insert(key.to_string(), value.to_string());
+                Ok(())
+            }
+        }
+
+        let mut fields = Collect(BTreeMap::new());
+        record.key_values().visit(&mut fields).unwrap();
+        RECORDS
+            .lock()
+            .unwrap()
+            .push((record.level(), record.args().to_string(), fields.0));
+    }
+
+    fn flush(&self) {}
+}
+
+#[logcall::logcall(ok = "info", err = "error", err_fingerprint)]
+fn charge(account: String, amount: u32) -> Result<u32, String> {
+    drop(account);
+    if amount > 100 {
+        Err("insufficient funds".to_string())
+    } else {
+        Ok(100 - amount)
+    }
+}
+
+#[logcall::logcall("debug")]
+async fn ping() -> bool {
+    true
+}
+
+#[logcall::logcall("info", group(headers, cookies, as = "http_meta"))]
+fn handle(headers: Vec<&str>, url: &str, cookies: Option<&str>) -> u16 {
+    let _ = (headers, url, cookies);
+    200
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    charge("alice".to_string(), 30).unwrap();
+    charge("bob".to_string(), 300).unwrap_err();
+    pollster::block_on(ping());
+    handle(vec!["accept"], "/", None);
+
+    let records = RECORDS.lock().unwrap();
+    assert_eq!(records.len(), 4);
+
+    let (level, message, fields) = &records[0];
+    assert_eq!(*level, log::Level::Info);
+    assert_eq!(message, "call");
+    assert!(fields["fn"].ends_with("::charge"));
+    assert_eq!(fields["account"], "\"alice\"");
+    assert_eq!(fields["amount"], "30");
+    assert_eq!(fields["ret"], "Ok(70)");
+    assert_eq!(fields["outcome"], "ok");
+    assert!(fields["duration_ms"].parse::<f64>().unwrap() >= 0.0);
+
+    let (level, message, fields) = &records[1];
+    assert_eq!(*level, log::Level::Error);
+    assert!(message.starts_with("call fingerprint="), "{message}");
+    assert_eq!(fields["ret"], "Err(\"insufficient funds\")");
+    assert_eq!(fields["outcome"], "err");
+
+    let (_, message, fields) = &records[2];
+    assert_eq!(message, "call");
+    assert!(fields["fn"].ends_with("::ping"));
+    assert_eq!(fields["ret"], "true");
+    assert_eq!(fields["outcome"], "ok");
+    assert_eq!(fields.len(), 4);
+
+    let (_, _, fields) = &records[3];
+    assert_eq!(fields["http_meta"], "{headers: [\"accept\"], cookies: None}");
+    assert_eq!(fields["url"], "\"/\"");
+    assert_eq!(fields.len(), 6);
+}
@@ -0,0 +1,92 @@
+use metrics_util::debugging::DebugValue;
+use metrics_util::debugging::DebuggingRecorder;
+
+// Labelled with the type name, not with the values of the errors.
+#[derive(Debug)]
+enum FetchError {
+    Timeout { after_ms: u64 },
+    NotFound(String),
+}
+
+// As derived by `strum::IntoStaticStr`.
+#[derive(Debug)]
+enum PayError {
+    Declined,
+}
+
+impl From<&PayError> for &'static str {
+    fn from(err: &PayError) -> &'static str {
+        match err {
+            PayError::Declined => "declined",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct StoreError {
+    code: u16,
+}
+
+fn store_error_label(err: &StoreError) -> String {
+    format!("code_{}", err.code)
+}
+
+#[logcall::logcall(err = "error", metrics)]
+fn fetch(id: u64) -> Result<u64, FetchError> {
+    match id {
+        0 => Err(FetchError::Timeout { after_ms: 5000 }),
+        1 => Err(FetchError::NotFound(id.to_string())),
+        _ => Ok(id),
+    }
+}
+
+#[logcall::logcall(err = "error", metrics)]
+async fn pay(amount: u64) -> Result<(), PayError> {
+    let _ = amount;
+    Err(PayError::Declined)
+}
+
+#[logcall::logcall(err = "error", metrics(error_label = "store_error_label"))]
+fn store(code: u16) -> Result<(), StoreError> {
+    Err(StoreError { code })
+}
+
+fn main() {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    metrics::with_local_recorder(&recorder, || {
+        fetch(0).unwrap_err();
+        fetch(0).unwrap_err();
+        fetch(1).unwrap_err();
+        fetch(2).unwrap();
+        pollster::block_on(pay(10)).unwrap_err();
+        store(503).unwrap_err();
+    });
+
+    let mut errors = snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .filter(|(key, _, _, _)| key.key().name() == "fn_errors")
+        .map(|(key, _, _, value)| {
+            let labels = key
+                .key()
+                .labels()
+                .map(|label| label.value().rsplit("::").next().unwrap().to_string())
+                .collect::<Vec<_>>();
+            let DebugValue::Counter(count) = value else {
+                panic!("expected a counter");
+            };
+            (labels, count)
+        })
+        .collect::<Vec<_>>();
+    errors.sort();
+    assert_eq!(
+        errors,
+        [
+            (vec!["fetch".to_string(), "FetchError".to_string()], 3),
+            (vec!["pay".to_string(), "declined".to_string()], 1),
+            (vec!["store".to_string(), "code_503".to_string()], 1),
+        ]
+    );
+}
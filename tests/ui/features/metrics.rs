@@ -0,0 +1,60 @@
+use metrics_util::debugging::DebugValue;
+use metrics_util::debugging::DebuggingRecorder;
+
+#[logcall::logcall("info", metrics)]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[logcall::logcall(ok = "info", err = "error", metrics)]
+async fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+fn main() {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    metrics::with_local_recorder(&recorder, || {
+        add(1, 2);
+        add(3, 4);
+        pollster::block_on(parse("1")).unwrap();
+        pollster::block_on(parse("a")).unwrap_err();
+    });
+
+    let mut metrics = snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .map(|(key, _, _, value)| {
+            let key = key.key();
+            let function = key.labels().next().unwrap().value().to_string();
+            let function = function.rsplit("::").next().unwrap().to_string();
+            (key.name().to_string(), function, value)
+        })
+        .collect::<Vec<_>>();
+    metrics.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+
+    let counters = metrics
+        .iter()
+        .filter_map(|(name, function, value)| match value {
+            DebugValue::Counter(count) => Some((name.as_str(), function.as_str(), *count)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        counters,
+        [("fn_calls", "add", 2), ("fn_calls", "parse", 2), ("fn_errors", "parse", 1)]
+    );
+
+    let histograms = metrics
+        .iter()
+        .filter_map(|(name, function, value)| match value {
+            DebugValue::Histogram(durations) => Some((name.as_str(), function.as_str(), durations.len())),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        histograms,
+        [("fn_duration_seconds", "add", 2), ("fn_duration_seconds", "parse", 2)]
+    );
+}
@@ -0,0 +1,25 @@
+// Off Android and Apple platforms, the records are printed to stderr.
+
+#[logcall::logcall("info", emit = "mobile")]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[logcall::logcall(ok = "debug", err = "error", emit = "mobile", target = "shop::payments")]
+async fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+#[logcall::logcall("warn", emit = "mobile", schema)]
+fn describe(text: String) -> usize {
+    text.len()
+}
+
+fn main() {
+    logcall::set_os_log_subsystem("com.example.app");
+
+    add(1, 2);
+    pollster::block_on(parse("1")).unwrap();
+    pollster::block_on(parse("a")).unwrap_err();
+    describe("a\0b".repeat(2000));
+}
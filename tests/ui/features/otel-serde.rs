@@ -0,0 +1,46 @@
+use opentelemetry::trace::Tracer;
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_sdk::trace::InMemorySpanExporter;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use serde::Serialize;
+
+// Not `Debug`: the events carry the same JSON as the records.
+#[derive(Serialize)]
+struct Account {
+    name: &'static str,
+    api_key: &'static str,
+}
+
+#[logcall::logcall("info", format = "json", ret_redact(api_key))]
+fn accounts() -> Vec<Account> {
+    vec![Account {
+        name: "ops",
+        api_key: "k1",
+    }]
+}
+
+fn main() {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let tracer = provider.tracer("test");
+
+    tracer.in_span("root", |_| {
+        accounts();
+    });
+    provider.force_flush().unwrap();
+
+    let spans = exporter.get_finished_spans().unwrap();
+    let events = &spans[0].events.events;
+    assert_eq!(events.len(), 1, "{events:?}");
+    let ret = events[0]
+        .attributes
+        .iter()
+        .find(|kv| kv.key.as_str() == "ret")
+        .unwrap();
+    assert_eq!(
+        ret.value.to_string(),
+        r#"[{"api_key":"<redacted>","name":"ops"}]"#
+    );
+}
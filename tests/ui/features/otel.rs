@@ -0,0 +1,55 @@
+use opentelemetry::trace::Tracer;
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_sdk::trace::InMemorySpanExporter;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+
+#[logcall::logcall("info")]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[logcall::logcall(ok = "info", err = "error")]
+fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+fn main() {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let tracer = provider.tracer("test");
+
+    // Without an active span, the call is only logged.
+    add(0, 0);
+    tracer.in_span("root", |_| {
+        add(1, 2);
+        parse("1").unwrap();
+        parse("a").unwrap_err();
+    });
+    provider.force_flush().unwrap();
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let events = &spans[0].events.events;
+    assert_eq!(events.len(), 3, "{events:?}");
+    let attributes = |i: usize| {
+        events[i]
+            .attributes
+            .iter()
+            .map(|kv| (kv.key.to_string(), kv.value.to_string()))
+            .collect::<Vec<_>>()
+    };
+
+    assert!(events[0].name.ends_with("::add"));
+    let add = attributes(0);
+    assert!(add[0].1.ends_with("::add"));
+    assert_eq!(add[1..], [
+        ("args".to_string(), "a = 1, b = 2".to_string()),
+        ("ret".to_string(), "3".to_string()),
+    ]);
+    assert_eq!(attributes(1)[3], ("outcome".to_string(), "ok".to_string()));
+    let err = attributes(2);
+    assert!(err[2].1.starts_with("Err(ParseIntError"));
+    assert_eq!(err[3], ("outcome".to_string(), "err".to_string()));
+}
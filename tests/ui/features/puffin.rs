@@ -0,0 +1,39 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+
+static FRAMES: Mutex<Vec<Arc<puffin::FrameData>>> = Mutex::new(Vec::new());
+
+#[logcall::logcall("info")]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[logcall::logcall("info")]
+async fn double(a: u32) -> u32 {
+    a * 2
+}
+
+fn main() {
+    puffin::GlobalProfiler::lock().add_sink(Box::new(|frame| FRAMES.lock().unwrap().push(frame)));
+
+    // Nothing is recorded until the scopes are on.
+    add(0, 0);
+    puffin::set_scopes_on(true);
+    add(1, 2);
+    add(3, 4);
+    pollster::block_on(double(2));
+    puffin::GlobalProfiler::lock().new_frame();
+
+    let frames = FRAMES.lock().unwrap();
+    assert_eq!(frames.len(), 1);
+    let mut names = frames[0]
+        .scope_delta
+        .iter()
+        .map(|scope| scope.function_name.to_string())
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names.len(), 2, "{names:?}");
+    assert!(names[0].ends_with("::add"), "{names:?}");
+    assert!(names[1].ends_with("::double"), "{names:?}");
+    assert_eq!(frames[0].meta().num_scopes, 3);
+}
@@ -0,0 +1,38 @@
+use log::Level;
+
+#[logcall::logcall("debug")]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+mod db {
+    pub mod pool {
+        #[logcall::logcall("trace")]
+        pub fn acquire() -> u32 {
+            1
+        }
+    }
+}
+
+fn main() {
+    // The crate name of the test is generated, so filter the tested modules by their path.
+    let crate_name = module_path!();
+    std::env::set_var(
+        "RUST_LOG",
+        format!("info,{crate_name}=debug,{crate_name}::db::pool=trace,{crate_name}::d=error"),
+    );
+    logcall::quick_init();
+    assert!(logcall::try_quick_init().is_err());
+
+    assert_eq!(log::max_level(), log::LevelFilter::Trace);
+    assert!(log::log_enabled!(target: crate_name, Level::Debug));
+    assert!(!log::log_enabled!(target: crate_name, Level::Trace));
+    assert!(log::log_enabled!(target: &format!("{crate_name}::db::pool"), Level::Trace));
+    // Prefixes match whole path segments.
+    assert!(log::log_enabled!(target: &format!("{crate_name}::db"), Level::Debug));
+    assert!(log::log_enabled!(target: "other", Level::Info));
+    assert!(!log::log_enabled!(target: "other", Level::Debug));
+
+    add(1, 2);
+    db::pool::acquire();
+}
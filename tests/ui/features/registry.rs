@@ -0,0 +1,68 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info")]
+fn charge(amount: u32) -> u32 {
+    amount
+}
+
+#[logcall::logcall("info")]
+async fn refund(amount: u32) -> u32 {
+    amount
+}
+
+#[logcall::logcall("info")]
+fn legacy_export(amount: u32) -> u32 {
+    amount
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    charge(1);
+    charge(2);
+    pollster::block_on(refund(3));
+
+    let functions = logcall::registry::functions();
+    let names: Vec<_> = functions.iter().map(|function| function.name()).collect();
+    assert_eq!(names.len(), 3);
+    assert!(names[0].ends_with("::charge"), "{:?}", names);
+    assert!(names[1].ends_with("::legacy_export"), "{:?}", names);
+    assert!(names[2].ends_with("::refund"), "{:?}", names);
+    assert_eq!(functions[0].calls(), 2);
+    assert_eq!(functions[1].calls(), 0);
+    assert_eq!(functions[2].calls(), 1);
+    assert!(functions[0].file().ends_with("registry.rs"));
+
+    let lines = LINES.lock().unwrap();
+    let logged_bytes = |name: &str| -> u64 {
+        lines
+            .iter()
+            .filter(|line| line.contains(name))
+            .map(|line| line.len() as u64)
+            .sum()
+    };
+    assert_eq!(functions[0].logged_bytes(), logged_bytes("::charge("));
+    assert_eq!(functions[1].logged_bytes(), 0);
+    assert_eq!(functions[2].logged_bytes(), logged_bytes("::refund("));
+    assert!(functions[0].logged_bytes() > functions[2].logged_bytes());
+    drop(lines);
+
+    let never_called = logcall::registry::never_called();
+    assert_eq!(never_called.len(), 1);
+    assert_eq!(never_called[0].name(), names[1]);
+
+    LINES.lock().unwrap().clear();
+    logcall::registry::report_never_called();
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].contains("::legacy_export (/"), "{}", lines[0]);
+    assert!(lines[0].ends_with("registry.rs:18) was never called"), "{}", lines[0]);
+    drop(lines);
+
+    logcall::registry::report_never_called_at_exit();
+}
@@ -0,0 +1,42 @@
+use sentry_core::protocol::Value;
+
+#[logcall::logcall("info")]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[logcall::logcall(ok = "info", err = "error")]
+fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+fn main() {
+    let events = sentry_core::test::with_captured_events(|| {
+        add(1, 2);
+        parse("1").unwrap();
+        parse("a").unwrap_err();
+        sentry_core::capture_message("failed", sentry_core::Level::Error);
+    });
+
+    assert_eq!(events.len(), 1);
+    let breadcrumbs = &events[0].breadcrumbs.values;
+    assert_eq!(breadcrumbs.len(), 3, "{breadcrumbs:?}");
+    assert!(breadcrumbs
+        .iter()
+        .all(|breadcrumb| breadcrumb.category.as_deref() == Some("logcall")));
+
+    assert!(breadcrumbs[0].message.as_ref().unwrap().ends_with("::add"));
+    assert_eq!(breadcrumbs[0].level, sentry_core::Level::Info);
+    assert_eq!(
+        breadcrumbs[0].data.get("args"),
+        Some(&Value::from("a = 1, b = 2"))
+    );
+    assert_eq!(breadcrumbs[1].data.get("error"), None);
+
+    assert!(breadcrumbs[2].message.as_ref().unwrap().ends_with("::parse"));
+    assert_eq!(breadcrumbs[2].level, sentry_core::Level::Error);
+    assert_eq!(
+        breadcrumbs[2].data.get("error"),
+        Some(&Value::from("ParseIntError { kind: InvalidDigit }"))
+    );
+}
@@ -0,0 +1,110 @@
+use serde::Serialize;
+
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[derive(Serialize)]
+struct Order {
+    id: u32,
+    items: Vec<&'static str>,
+}
+
+// Not `Serialize`, so rendered with `Debug`.
+#[derive(Debug)]
+struct Token(u32);
+
+#[logcall::logcall(ok = "info", err = "error", format = "json")]
+fn place(order: Order, token: Token) -> Result<u32, String> {
+    if order.items.is_empty() {
+        Err(format!("empty order {}", token.0))
+    } else {
+        Ok(order.id)
+    }
+}
+
+#[logcall::logcall("info", format = "json")]
+async fn total<T: std::fmt::Debug>(prices: Vec<f64>, tag: T) -> f64 {
+    let _ = tag;
+    prices.iter().sum()
+}
+
+#[logcall::logcall("info", format = "json", group(headers, cookies, as = "http_meta"))]
+fn handle(headers: Vec<&str>, url: &str, cookies: Option<&str>) -> u16 {
+    let _ = (headers, url, cookies);
+    200
+}
+
+#[derive(Serialize)]
+struct Account {
+    name: &'static str,
+    api_key: &'static str,
+}
+
+#[logcall::logcall("info", format = "json", ret_redact(api_key))]
+fn accounts() -> Vec<Account> {
+    vec![Account {
+        name: "ops",
+        api_key: "k1",
+    }]
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    place(
+        Order {
+            id: 7,
+            items: vec!["apple"],
+        },
+        Token(1),
+    )
+    .unwrap();
+    place(
+        Order {
+            id: 8,
+            items: vec![],
+        },
+        Token(2),
+    )
+    .unwrap_err();
+    pollster::block_on(total(vec![1.5, 2.0], "tag"));
+    logcall::Context::new()
+        .with_request_id("abc")
+        .scope_sync(|| pollster::block_on(total(vec![], 1)));
+    handle(vec!["accept"], "/", None);
+    accounts();
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 6);
+    let values: Vec<serde_json::Value> = lines
+        .iter()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    assert!(values[0]["fn"].as_str().unwrap().ends_with("::place"));
+    assert_eq!(
+        values[0]["args"],
+        serde_json::json!({"order": {"id": 7, "items": ["apple"]}, "token": "Token(1)"})
+    );
+    assert_eq!(values[0]["ret"], serde_json::json!({"Ok": 7}));
+    assert_eq!(values[1]["ret"], serde_json::json!({"Err": "empty order 2"}));
+    // Generic arguments are rendered with `Debug`.
+    assert_eq!(
+        values[2]["args"],
+        serde_json::json!({"prices": [1.5, 2.0], "tag": "\"tag\""})
+    );
+    assert_eq!(values[2]["ret"], serde_json::json!(3.5));
+    assert_eq!(values[3]["fields"], "request_id=abc");
+    assert_eq!(
+        lines[4].split_once(",\"args\":").unwrap().1,
+        "{\"http_meta\":{\"headers\":[\"accept\"],\"cookies\":null},\"url\":\"/\"},\"ret\":200}"
+    );
+    assert_eq!(
+        values[5]["ret"],
+        serde_json::json!([{"name": "ops", "api_key": "<redacted>"}])
+    );
+}
@@ -0,0 +1,83 @@
+use std::sync::Mutex;
+
+use slog::Drain;
+use slog::Level;
+use slog::Logger;
+use slog::Never;
+use slog::OwnedKVList;
+use slog::Record;
+
+static RECORDS: Mutex<Vec<(Level, String)>> = Mutex::new(Vec::new());
+
+struct Collect;
+
+impl Drain for Collect {
+    type Ok = ();
+    type Err = Never;
+
+    fn log(&self, record: &Record, _: &OwnedKVList) -> Result<(), Never> {
+        RECORDS
+            .lock()
+            .unwrap()
+            .push((record.level(), record.msg().to_string()));
+        Ok(())
+    }
+}
+
+struct Service {
+    logger: Logger,
+}
+
+impl Service {
+    #[logcall::logcall(slog = "self.logger", level = "info")]
+    fn get(&self, id: u32) -> u32 {
+        id * 2
+    }
+
+    #[logcall::logcall(slog = "self.logger", ok = "debug", err = "warn")]
+    async fn fetch(&self, found: bool) -> Result<u32, String> {
+        if found {
+            Ok(1)
+        } else {
+            Err("not found".to_string())
+        }
+    }
+
+    // The logger is cloned before the body consumes `self`.
+    #[logcall::logcall(slog = "self.logger", level = "error")]
+    fn shutdown(self) -> bool {
+        drop(self);
+        true
+    }
+}
+
+#[logcall::logcall("info", slog = "logger")]
+fn add(logger: &Logger, a: u32, b: u32) -> u32 {
+    let _ = logger;
+    a + b
+}
+
+fn main() {
+    let service = Service {
+        logger: Logger::root(Collect, slog::o!()),
+    };
+
+    service.get(1);
+    pollster::block_on(service.fetch(true)).unwrap();
+    pollster::block_on(service.fetch(false)).unwrap_err();
+    add(&service.logger.clone(), 2, 3);
+    service.shutdown();
+
+    let records = RECORDS.lock().unwrap();
+    assert_eq!(records.len(), 5, "{records:?}");
+    assert_eq!(records[0].0, Level::Info);
+    assert!(records[0].1.ends_with("::Service::get(self, id = 1) => 2"));
+    assert_eq!(records[1].0, Level::Debug);
+    assert!(records[1].1.ends_with("::Service::fetch(self, found = true) => Ok(1)"));
+    assert_eq!(records[2].0, Level::Warning);
+    assert!(records[2].1.ends_with("::Service::fetch(self, found = false) => Err(\"not found\")"));
+    assert_eq!(records[3].0, Level::Info);
+    assert!(records[3].1.ends_with("::add(logger = Logger(), a = 2, b = 3) => 5"), "{}", records[3].1);
+    assert_eq!(records[4].0, Level::Error);
+    assert!(records[4].1.ends_with("::Service::shutdown(self) => true"));
+}
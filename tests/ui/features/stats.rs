@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+#[logcall::logcall("info")]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[logcall::logcall(ok = "info", err = "error")]
+async fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+#[logcall::logcall("debug")]
+fn sleep(ms: u64) {
+    std::thread::sleep(Duration::from_millis(ms));
+}
+
+#[logcall::logcall("debug")]
+fn never_called() {}
+
+fn main() {
+    add(1, 2);
+    add(3, 4);
+    pollster::block_on(parse("1")).unwrap();
+    pollster::block_on(parse("a")).unwrap_err();
+    pollster::block_on(parse("b")).unwrap_err();
+    sleep(10);
+    sleep(30);
+
+    let stats = logcall::stats();
+    let names = stats
+        .iter()
+        .map(|stats| stats.fn_name().rsplit("::").next().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["add", "parse", "sleep"]);
+
+    let add_stats = stats.iter().find(|stats| stats.fn_name().ends_with("::add")).unwrap();
+    assert_eq!((add_stats.calls(), add_stats.errors()), (2, 0));
+    let parse_stats = stats.iter().find(|stats| stats.fn_name().ends_with("::parse")).unwrap();
+    assert_eq!((parse_stats.calls(), parse_stats.errors()), (3, 2));
+
+    let sleep = stats.get(&format!("{}::sleep", module_path!())).unwrap();
+    assert_eq!(sleep.calls(), 2);
+    assert!(sleep.min() >= Duration::from_millis(10), "{sleep:?}");
+    assert!(sleep.min() < Duration::from_millis(30), "{sleep:?}");
+    assert!(sleep.max() >= Duration::from_millis(30), "{sleep:?}");
+    assert!(sleep.total() >= Duration::from_millis(40), "{sleep:?}");
+    assert_eq!(sleep.mean(), sleep.total() / 2);
+
+    add(5, 6);
+    assert_eq!(logcall::stats().get(add_stats.fn_name()).unwrap().calls(), 3);
+}
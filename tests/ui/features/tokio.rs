@@ -0,0 +1,31 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info", exec_context)]
+fn compute(a: u32) -> u32 {
+    a
+}
+
+#[logcall::logcall("info", exec_context)]
+async fn handle(a: u32) -> u32 {
+    compute(a)
+}
+
+#[tokio::main]
+async fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    tokio::spawn(handle(1)).await.unwrap();
+    tokio::task::spawn_blocking(|| compute(2)).await.unwrap();
+    std::thread::spawn(|| compute(3)).join().unwrap();
+
+    let lines = LINES.lock().unwrap();
+    assert!(lines[0].ends_with("::compute(a = 1) => 1 exec=worker"), "{}", lines[0]);
+    assert!(lines[1].ends_with("::handle(a = 1) => 1 exec=worker"), "{}", lines[1]);
+    assert!(lines[2].ends_with("::compute(a = 2) => 2 exec=blocking"), "{}", lines[2]);
+    assert!(lines[3].ends_with("::compute(a = 3) => 3 exec=thread"), "{}", lines[3]);
+}
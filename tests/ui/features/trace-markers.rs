@@ -0,0 +1,38 @@
+use std::fs;
+
+#[logcall::logcall("info")]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[logcall::logcall("info")]
+async fn double(a: u32) -> u32 {
+    a * 2
+}
+
+fn main() {
+    assert_eq!(add(1, 2), 3);
+    assert_eq!(pollster::block_on(double(2)), 4);
+
+    // The markers are only written where the tracing file system is mounted and writable.
+    let Ok(trace) = fs::read_to_string("/sys/kernel/tracing/trace") else {
+        return;
+    };
+    let pid = std::process::id();
+    let markers = trace
+        .lines()
+        .filter_map(|line| line.split_once("tracing_mark_write: "))
+        .map(|(_, marker)| marker)
+        .filter(|marker| marker.get(2..).is_some_and(|rest| rest.starts_with(&pid.to_string())))
+        .collect::<Vec<_>>();
+    if markers.is_empty() {
+        return;
+    }
+    assert_eq!(markers.len(), 4, "{markers:?}");
+    assert!(markers[0].starts_with(&format!("B|{pid}|")));
+    assert!(markers[0].ends_with("::add"));
+    assert_eq!(markers[1], format!("E|{pid}"));
+    assert!(markers[2].starts_with(&format!("S|{pid}|")));
+    assert!(markers[2].ends_with("::double|1"));
+    assert_eq!(markers[3], markers[2].replacen('S', "F", 1));
+}
@@ -0,0 +1,96 @@
+use std::fmt::Debug;
+use std::sync::Mutex;
+
+use tracing::field::Field;
+use tracing::field::Visit;
+use tracing::span;
+use tracing::Event;
+use tracing::Level;
+use tracing::Metadata;
+
+static EVENTS: Mutex<Vec<(Level, String, String)>> = Mutex::new(Vec::new());
+
+struct Subscriber;
+
+struct Message(String);
+
+impl Visit for Message {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{value:?}");
+        }
+    }
+}
+
+impl tracing::Subscriber for Subscriber {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut message = Message(String::new());
+        event.record(&mut message);
+        EVENTS.lock().unwrap().push((
+            *event.metadata().level(),
+            event.metadata().target().to_string(),
+            message.0,
+        ));
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+#[logcall::logcall(ok = "info", err = "error", backend = "tracing")]
+fn charge(amount: u32) -> Result<u32, String> {
+    if amount > 100 {
+        Err("insufficient funds".to_string())
+    } else {
+        Ok(100 - amount)
+    }
+}
+
+#[logcall::logcall(
+    ok = "trace",
+    err = "warn",
+    err_target = "alerts",
+    after_error(level = "debug", count = 1),
+    backend = "tracing"
+)]
+async fn fetch(found: bool) -> Result<u32, String> {
+    if found {
+        Ok(1)
+    } else {
+        Err("not found".to_string())
+    }
+}
+
+fn main() {
+    // No `log` logger is installed, the events go straight to the subscriber.
+    tracing::subscriber::set_global_default(Subscriber).unwrap();
+
+    charge(30).unwrap();
+    charge(300).unwrap_err();
+    pollster::block_on(fetch(false)).unwrap_err();
+    pollster::block_on(fetch(true)).unwrap();
+
+    let events = EVENTS.lock().unwrap();
+    assert_eq!(events.len(), 4, "{events:?}");
+    assert_eq!(events[0].0, Level::INFO);
+    assert!(events[0].2.ends_with("::charge(amount = 30) => Ok(70)"));
+    assert_eq!(events[1].0, Level::ERROR);
+    assert!(events[1].2.ends_with("::charge(amount = 300) => Err(\"insufficient funds\")"));
+    assert_eq!(events[2].0, Level::WARN);
+    assert_eq!(events[2].1, "alerts");
+    assert_eq!(events[3].0, Level::DEBUG);
+    assert!(events[3].2.ends_with("::fetch(found = true) => Ok(1)"));
+}
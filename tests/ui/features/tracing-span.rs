@@ -0,0 +1,115 @@
+use std::fmt::Debug;
+use std::sync::Mutex;
+
+use tracing::field::Field;
+use tracing::field::Visit;
+use tracing::span;
+use tracing::Event;
+use tracing::Level;
+use tracing::Metadata;
+
+// The message of each event, and the name of the span it was emitted in.
+static EVENTS: Mutex<Vec<(String, Option<&'static str>)>> = Mutex::new(Vec::new());
+
+struct Subscriber {
+    spans: Mutex<Vec<&'static Metadata<'static>>>,
+    current: Mutex<Vec<span::Id>>,
+}
+
+struct Message(String);
+
+impl Visit for Message {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{value:?}");
+        }
+    }
+}
+
+impl tracing::Subscriber for Subscriber {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attrs: &span::Attributes<'_>) -> span::Id {
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(attrs.metadata());
+        span::Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut message = Message(String::new());
+        event.record(&mut message);
+        let span = self.current.lock().unwrap().last().map(|id| {
+            let metadata = self.spans.lock().unwrap()[id.into_u64() as usize - 1];
+            assert_eq!(*metadata.level(), Level::INFO);
+            metadata.name()
+        });
+        EVENTS.lock().unwrap().push((message.0, span));
+    }
+
+    fn enter(&self, span: &span::Id) {
+        self.current.lock().unwrap().push(span.clone());
+    }
+
+    fn exit(&self, _: &span::Id) {
+        self.current.lock().unwrap().pop();
+    }
+}
+
+#[logcall::logcall("debug", span = "info", backend = "tracing")]
+fn charge(amount: u32) -> u32 {
+    tracing::info!("charging");
+    amount
+}
+
+#[logcall::logcall(ok = "info", span = "info", backend = "tracing")]
+async fn fetch(id: u32) -> Result<u32, String> {
+    async {}.await;
+    tracing::info!("fetching");
+    Ok(id)
+}
+
+#[async_trait::async_trait]
+trait Store {
+    async fn get(&self, id: u32) -> u32;
+}
+
+struct Memory;
+
+#[async_trait::async_trait]
+impl Store for Memory {
+    #[logcall::logcall("info", span = "info", backend = "tracing")]
+    async fn get(&self, id: u32) -> u32 {
+        id
+    }
+}
+
+fn main() {
+    tracing::subscriber::set_global_default(Subscriber {
+        spans: Mutex::new(Vec::new()),
+        current: Mutex::new(Vec::new()),
+    })
+    .unwrap();
+
+    charge(1);
+    pollster::block_on(fetch(2)).unwrap();
+    pollster::block_on(Memory.get(3));
+    tracing::info!("outside");
+
+    let events = EVENTS.lock().unwrap();
+    assert_eq!(events.len(), 6, "{events:?}");
+    assert_eq!(events[0], ("charging".to_string(), Some("charge")));
+    assert!(events[1].0.ends_with("::charge(amount = 1) => 1"));
+    assert_eq!(events[1].1, Some("charge"));
+    assert_eq!(events[2], ("fetching".to_string(), Some("fetch")));
+    assert!(events[3].0.ends_with("::fetch(id = 2) => Ok(2)"));
+    assert_eq!(events[3].1, Some("fetch"));
+    assert!(events[4].0.ends_with("::get(self, id = 3) => 3"));
+    assert_eq!(events[4].1, Some("get"));
+    assert_eq!(events[5], ("outside".to_string(), None));
+}
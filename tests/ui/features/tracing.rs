@@ -0,0 +1,110 @@
+use std::sync::Mutex;
+
+use tracing::field::Field;
+use tracing::field::Visit;
+use tracing::span;
+use tracing::Event;
+use tracing::Metadata;
+
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+static FIELDS: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+
+struct Subscriber {
+    spans: Mutex<Vec<&'static Metadata<'static>>>,
+    current: Mutex<Vec<span::Id>>,
+}
+
+struct Recorder;
+
+impl Visit for Recorder {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        FIELDS
+            .lock()
+            .unwrap()
+            .push((field.name().to_string(), value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        FIELDS
+            .lock()
+            .unwrap()
+            .push((field.name().to_string(), format!("{value:?}")));
+    }
+}
+
+impl tracing::Subscriber for Subscriber {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attrs: &span::Attributes<'_>) -> span::Id {
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(attrs.metadata());
+        span::Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, _: &span::Id, values: &span::Record<'_>) {
+        values.record(&mut Recorder);
+    }
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, span: &span::Id) {
+        self.current.lock().unwrap().push(span.clone());
+    }
+
+    fn exit(&self, _: &span::Id) {
+        self.current.lock().unwrap().pop();
+    }
+
+    fn current_span(&self) -> tracing_core::span::Current {
+        match self.current.lock().unwrap().last() {
+            Some(id) => {
+                let metadata = self.spans.lock().unwrap()[id.into_u64() as usize - 1];
+                tracing_core::span::Current::new(id.clone(), metadata)
+            }
+            None => tracing_core::span::Current::none(),
+        }
+    }
+}
+
+#[logcall::logcall("info", record_span)]
+fn charge(amount: u32) -> Result<u32, String> {
+    Ok(amount)
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+    tracing::subscriber::set_global_default(Subscriber {
+        spans: Mutex::new(Vec::new()),
+        current: Mutex::new(Vec::new()),
+    })
+    .unwrap();
+
+    // Without a current span, the record is logged as usual.
+    charge(1).ok();
+
+    let span = tracing::info_span!(
+        "handle",
+        ret = tracing::field::Empty,
+        duration_us = tracing::field::Empty
+    );
+    span.in_scope(|| charge(2)).ok();
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].ends_with("::charge(amount = 1) => Ok(1)"), "{}", lines[0]);
+
+    let fields = FIELDS.lock().unwrap();
+    assert_eq!(fields.len(), 2, "{:?}", fields);
+    assert_eq!(fields[0], ("ret".to_string(), "Ok(2)".to_string()));
+    assert_eq!(fields[1].0, "duration_us");
+}
@@ -0,0 +1,16 @@
+#[logcall::logcall("info")]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[logcall::logcall("info")]
+async fn double(a: u32) -> u32 {
+    a * 2
+}
+
+fn main() {
+    // The zones are recorded while the client is running, and sent to a connected profiler.
+    let _client = tracy_client::Client::start();
+    assert_eq!(add(1, 2), 3);
+    assert_eq!(pollster::block_on(double(2)), 4);
+}
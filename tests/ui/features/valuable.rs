@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::Mutex;
+
+use tracing::field::Field;
+use tracing::field::Visit;
+use tracing::span;
+use tracing::Event;
+use tracing::Metadata;
+
+static EVENTS: Mutex<Vec<BTreeMap<String, String>>> = Mutex::new(Vec::new());
+
+struct Subscriber;
+
+struct Fields(BTreeMap<String, String>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.insert(field.name().to_string(), format!("{value:?}"));
+    }
+
+}
+
+impl tracing::Subscriber for Subscriber {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(BTreeMap::new());
+        event.record(&mut fields);
+        EVENTS.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+struct Opaque;
+
+impl Debug for Opaque {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Opaque")
+    }
+}
+
+#[logcall::logcall("info", backend = "tracing")]
+fn total(items: &[u32], owner: String, opaque: &Opaque) -> Vec<u32> {
+    drop(owner);
+    items.iter().map(|item| item * 2).collect()
+}
+
+fn main() {
+    tracing::subscriber::set_global_default(Subscriber).unwrap();
+
+    total(&[1, 2, 3], "alice".to_string(), &Opaque);
+
+    let events = EVENTS.lock().unwrap();
+    let fields = &events[0];
+    assert!(fields["message"].contains("::total(items = [1, 2, 3]"));
+    // The owned arguments are rendered before the call, which may move them.
+    assert_eq!(fields["owner"], "\"\\\"alice\\\"\"");
+    assert_eq!(fields["opaque"], "Opaque");
+    // Without `--cfg tracing_unstable`, which `trybuild` doesn't pass on, the values that
+    // implement `Valuable` are recorded with their `Debug` as well.
+    assert_eq!(fields["items"], "[1, 2, 3]");
+    assert_eq!(fields["ret"], "[2, 4, 6]");
+}
@@ -0,0 +1,25 @@
+// Off `wasm32`, the console arguments are printed to stderr.
+
+#[logcall::logcall("info", emit = "console")]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[logcall::logcall(ok = "debug", err = "error", emit = "console", timestamp)]
+async fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+#[logcall::logcall("warn", emit = "console", dedup = "1s")]
+fn retry(attempt: u32) -> bool {
+    attempt > 2
+}
+
+fn main() {
+    add(1, 2);
+    pollster::block_on(parse("1")).unwrap();
+    pollster::block_on(parse("a")).unwrap_err();
+    for attempt in 0..4 {
+        retry(attempt);
+    }
+}
@@ -0,0 +1,35 @@
+#[logcall::logcall("info", accumulate)]
+fn f(a: u32) -> u32 {
+    a
+}
+
+#[logcall::logcall(ok = "info", err = "error", accumulate = "both")]
+async fn g(a: u32) -> Result<u32, u32> {
+    f(a);
+    Ok(a)
+}
+
+fn main() {
+    // Without an accumulator, the records are emitted as usual.
+    f(1);
+    assert!(logcall::drain().is_empty());
+
+    let records = logcall::Context::new().with_accumulator().scope_sync(|| {
+        f(1);
+        f(2);
+        logcall::drain()
+    });
+    assert_eq!(records.len(), 2);
+    assert!(records[0].fn_name.ends_with("::f"));
+    assert_eq!(records[0].args, "a = 1");
+    assert_eq!(records[1].ret, "2");
+
+    let records = pollster::block_on(logcall::Context::new().with_accumulator().scope(async {
+        g(3).await.ok();
+        logcall::drain()
+    }));
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].level, log::Level::Info);
+    assert!(records[1].fn_name.ends_with("::g"));
+    assert_eq!(records[1].ret, "Ok(3)");
+}
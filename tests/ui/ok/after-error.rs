@@ -0,0 +1,48 @@
+use logger::Logger;
+use logger::RECORDS;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall(ok = "trace", err = "error", after_error(level = "debug", count = 2))]
+fn divide(a: u32, b: u32) -> Result<u32, String> {
+    a.checked_div(b).ok_or_else(|| "division by zero".to_string())
+}
+
+#[logcall::logcall(err = "warn", after_error(count = 1))]
+async fn fetch(found: bool) -> Result<u32, String> {
+    if found {
+        Ok(1)
+    } else {
+        Err("not found".to_string())
+    }
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Debug);
+
+    divide(4, 2).unwrap();
+    divide(4, 0).unwrap_err();
+    divide(6, 2).unwrap();
+    divide(8, 2).unwrap();
+    divide(9, 3).unwrap();
+
+    pollster::block_on(fetch(true)).unwrap();
+    pollster::block_on(fetch(false)).unwrap_err();
+    pollster::block_on(fetch(true)).unwrap();
+    pollster::block_on(fetch(true)).unwrap();
+
+    let lines = RECORDS.lock().unwrap();
+    assert_eq!(lines.len(), 5, "{lines:?}");
+    assert_eq!(lines[0].0, log::Level::Error);
+    assert!(lines[0].1.ends_with("::divide(a = 4, b = 0) => Err(\"division by zero\")"));
+    assert_eq!(lines[1].0, log::Level::Debug);
+    assert!(lines[1].1.ends_with("::divide(a = 6, b = 2) => Ok(3)"));
+    assert_eq!(lines[2].0, log::Level::Debug);
+    assert!(lines[2].1.ends_with("::divide(a = 8, b = 2) => Ok(4)"));
+    assert_eq!(lines[3].0, log::Level::Warn);
+    assert!(lines[3].1.ends_with("::fetch(found = false) => Err(\"not found\")"));
+    assert_eq!(lines[4].0, log::Level::Debug);
+    assert!(lines[4].1.ends_with("::fetch(found = true) => Ok(1)"));
+}
@@ -0,0 +1,46 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info")]
+fn checksum(page: [u8; 4096], id: u32) -> u32 {
+    page.len() as u32 + id
+}
+
+#[logcall::logcall("info")]
+fn first<const N: usize>(items: &[u16; N]) -> u16 {
+    items[0]
+}
+
+#[logcall::logcall("info", array_summary = 4)]
+fn sum(values: &mut [i32; 6]) -> i32 {
+    values.iter().sum()
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    checksum([0; 4096], 7);
+    first(&[1, 2, 3]);
+    first(&[9; 100]);
+    sum(&mut [1, 2, 3, 4, 5, 6]);
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 4);
+    assert!(
+        lines[0].ends_with("::checksum(page = [u8; 4096] [0, 0, 0, 0, 0, 0, 0, 0, …], id = 7) => 4103"),
+        "{lines:?}"
+    );
+    assert!(lines[1].ends_with("::first(items = [1, 2, 3]) => 1"), "{lines:?}");
+    assert!(
+        lines[2].ends_with("::first(items = [u16; 100] [9, 9, 9, 9, 9, 9, 9, 9, …]) => 9"),
+        "{lines:?}"
+    );
+    assert!(
+        lines[3].ends_with("::sum(values = [i32; 6] [1, 2, 3, 4, …]) => 21"),
+        "{lines:?}"
+    );
+}
@@ -0,0 +1,44 @@
+#![deny(warnings)]
+
+/// Adds two numbers.
+#[logcall::logcall("info")]
+/// The attributes below `#[logcall]` are preserved.
+#[must_use]
+#[inline]
+#[allow(unused_variables)]
+fn add(a: u32, b: u32) -> u32 {
+    let unused = 0;
+    a + b
+}
+
+#[logcall::logcall("info", lint_attrs)]
+#[must_use = "the sum is the whole point"]
+#[allow(clippy::let_and_return, unused_variables)]
+async fn add_async(a: u32, b: u32) -> u32 {
+    let unused = 0;
+    let sum = a + b;
+    sum
+}
+
+#[async_trait::async_trait]
+trait Adder {
+    async fn add(&self, a: u32, b: u32) -> u32;
+}
+
+struct MyAdder;
+
+#[async_trait::async_trait]
+impl Adder for MyAdder {
+    #[logcall::logcall("info", lint_attrs)]
+    #[allow(unused_variables)]
+    async fn add(&self, a: u32, b: u32) -> u32 {
+        let unused = 0;
+        a + b
+    }
+}
+
+fn main() {
+    assert_eq!(add(1, 2), 3);
+    assert_eq!(pollster::block_on(add_async(1, 2)), 3);
+    assert_eq!(pollster::block_on(MyAdder.add(1, 2)), 3);
+}
@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use logger::Logger;
+use logger::RECORDS;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall(ok = "info", err = "error", auto_mute(threshold = "3/s", for = "50ms"))]
+fn poll(n: u32) -> Result<u32, u32> {
+    if n % 2 == 0 {
+        Ok(n)
+    } else {
+        Err(n)
+    }
+}
+
+// The records filtered out by the logger don't count.
+#[logcall::logcall("debug", auto_mute(threshold = "1/min"))]
+fn tick() {}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Info);
+
+    for _ in 0..10 {
+        tick();
+    }
+    for n in 0..6 {
+        poll(n).ok();
+    }
+    std::thread::sleep(Duration::from_millis(60));
+    poll(6).ok();
+
+    let records = RECORDS.lock().unwrap();
+    let messages = records
+        .iter()
+        .map(|(_, message)| message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(records.len(), 5, "{messages:?}");
+    assert!(messages[0].ends_with("::poll(n = 0) => Ok(0)"));
+    assert!(messages[1].ends_with("::poll(n = 1) => Err(1)"));
+    assert!(messages[2].ends_with("::poll(n = 2) => Ok(2)"));
+    assert_eq!(records[3].0, log::Level::Warn);
+    assert!(
+        messages[3].ends_with("::poll logged more than 3/s and is muted for 50ms"),
+        "{messages:?}"
+    );
+    assert!(messages[4].ends_with("::poll(n = 6) => Ok(6)"));
+}
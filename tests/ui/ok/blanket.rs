@@ -0,0 +1,87 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+pub struct Server;
+
+#[logcall::logcall("info", only_pub)]
+impl Server {
+    pub fn get(&self, id: u32) -> u32 {
+        self.lookup(id)
+    }
+
+    fn lookup(&self, id: u32) -> u32 {
+        id * 2
+    }
+
+    #[logcall::logcall("warn")]
+    fn evict(&self, id: u32) -> bool {
+        id > 10
+    }
+}
+
+#[logcall::logcall("info", match = "handle_*")]
+impl Clone for Server {
+    fn clone(&self) -> Self {
+        Server
+    }
+}
+
+#[logcall::logcall("info", only_async, match = "handle_*")]
+mod handlers {
+    pub async fn handle_ping(n: u32) -> u32 {
+        n
+    }
+
+    pub fn handle_sync(n: u32) -> u32 {
+        n
+    }
+
+    pub async fn helper(n: u32) -> u32 {
+        n
+    }
+}
+
+#[logcall::wrap_dyn("info", match = "handle")]
+trait Plugin {
+    fn handle(&self, request: u32) -> u32;
+
+    fn name(&self) -> &'static str;
+}
+
+struct Echo;
+
+impl Plugin for Echo {
+    fn handle(&self, request: u32) -> u32 {
+        request
+    }
+
+    fn name(&self) -> &'static str {
+        "echo"
+    }
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let server = Server;
+    server.get(1);
+    server.evict(2);
+    let _ = server.clone();
+    pollster::block_on(handlers::handle_ping(3));
+    handlers::handle_sync(4);
+    pollster::block_on(handlers::helper(5));
+    let plugin: Box<dyn Plugin> = Box::new(LoggedPlugin::new(Echo));
+    plugin.handle(6);
+    plugin.name();
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 4, "{lines:?}");
+    assert!(lines[0].ends_with("::get(self, id = 1) => 2"));
+    assert!(lines[1].ends_with("::evict(self, id = 2) => false"));
+    assert!(lines[2].ends_with("::handle_ping(n = 3) => 3"));
+    assert!(lines[3].ends_with("::handle(self, request = 6) => 6"));
+}
@@ -0,0 +1,43 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info", budget = "64B")]
+fn upload(name: &str, data: Vec<u8>) -> usize {
+    let _ = name;
+    data.len()
+}
+
+#[logcall::logcall(ok = "info", err = "error", budget = "1KiB")]
+async fn fetch(len: usize) -> Result<String, String> {
+    Ok("x".repeat(len))
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    upload("a", vec![1, 2]);
+    upload("a", vec![0; 100]);
+    upload("a", vec![0; 100]);
+    pollster::block_on(fetch(3)).unwrap();
+    pollster::block_on(fetch(2000)).unwrap();
+
+    let lines = LINES.lock().unwrap();
+    assert!(lines[0].ends_with("::upload(name = \"a\", data = [1, 2]) => 2"));
+    let summary = lines[1].split_once("::upload").unwrap().1;
+    assert!(
+        summary.starts_with(
+            "(name: &str, data: Vec<u8>) => usize budget_exceeded=true args_len=319 ret_len=3 hash="
+        ),
+        "{summary}"
+    );
+    // Identical calls get the same hash.
+    assert_eq!(lines[1], lines[2]);
+    assert!(lines[3].ends_with("::fetch(len = 3) => Ok(\"xxx\")"));
+    assert!(lines[4].contains(
+        "::fetch(len: usize) => Result<String, String> budget_exceeded=true args_len=10 ret_len=2006 hash="
+    ));
+}
@@ -0,0 +1,47 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall(err = "warn")]
+fn charge(id: u32, amount: u64) -> Result<u64, String> {
+    if amount == 0 {
+        return Err(format!("empty charge in {}", __logcall_record));
+    }
+    log::info!("charging in {__logcall_record}");
+    Ok(amount)
+}
+
+#[logcall::logcall("info")]
+async fn fetch(key: String) -> usize {
+    assert!(__logcall_record.fn_name().ends_with("::fetch"));
+    assert_eq!(__logcall_record.input().to_string(), "key = \"a\"");
+    key.len()
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    logcall::Context::new()
+        .with_request_id("42")
+        .scope_sync(|| charge(7, 0))
+        .unwrap_err();
+    charge(7, 100).unwrap();
+    pollster::block_on(fetch("a".to_string()));
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 3, "{lines:?}");
+    assert!(
+        lines[0].contains("::charge(id = 7, amount = 0) => Err(\"empty charge in "),
+        "{lines:?}"
+    );
+    assert!(
+        lines[0].ends_with("::charge(id = 7, amount = 0) request_id=42\") request_id=42"),
+        "{lines:?}"
+    );
+    assert!(lines[1].starts_with("charging in "), "{lines:?}");
+    assert!(lines[1].ends_with("::charge(id = 7, amount = 100)"), "{lines:?}");
+    assert!(lines[2].ends_with("::fetch(key = \"a\") => 1"), "{lines:?}");
+}
@@ -0,0 +1,51 @@
+use std::future::Future;
+use std::task::Context;
+
+use logger::Logger;
+use logger::RECORDS;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+// Pending on the first poll, and ready on the next ones.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> std::task::Poll<()> {
+        if self.0 {
+            return std::task::Poll::Ready(());
+        }
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        std::task::Poll::Pending
+    }
+}
+
+#[logcall::logcall(ok = "info", err = "error", cancelled = "warn")]
+async fn query(sql: &str) -> Result<usize, String> {
+    YieldNow(false).await;
+    Ok(sql.len())
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    pollster::block_on(query("select 1")).unwrap();
+    // Dropped after its first poll, e.g. by the losing branch of a `select!`.
+    let mut future = Box::pin(query("select 2"));
+    let waker = std::task::Waker::noop();
+    assert!(future.as_mut().poll(&mut Context::from_waker(waker)).is_pending());
+    drop(future);
+    // Never polled.
+    drop(query("select 3"));
+
+    let records = RECORDS.lock().unwrap();
+    assert_eq!(records.len(), 2, "{records:?}");
+    assert_eq!(records[0].0, log::Level::Info);
+    assert!(records[0].1.ends_with("::query(sql = \"select 1\") => Ok(8)"), "{records:?}");
+    assert_eq!(records[1].0, log::Level::Warn);
+    assert!(records[1].1.ends_with("::query(sql = \"select 2\") => <cancelled>"), "{records:?}");
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+
+use logger::Logger;
+use logger::RECORDS;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+struct Broken;
+
+impl fmt::Debug for Broken {
+    fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+        panic!("broken Debug")
+    }
+}
+
+#[logcall::logcall("info", catch_panic)]
+fn accept(_value: &Broken, n: u32) -> u32 {
+    n + 1
+}
+
+#[logcall::logcall("info", catch_panic)]
+fn produce() -> Broken {
+    Broken
+}
+
+#[logcall::logcall("info", catch_panic)]
+async fn produce_async() -> Broken {
+    Broken
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+    // Keep the output of the test quiet.
+    std::panic::set_hook(Box::new(|_| {}));
+
+    assert_eq!(accept(&Broken, 1), 2);
+    produce();
+    pollster::block_on(produce_async());
+    drop(std::panic::take_hook());
+
+    let records = RECORDS.lock().unwrap();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].0, log::Level::Info);
+    assert!(
+        records[0]
+            .1
+            .ends_with("::accept(<failed to render arguments>) => 2"),
+        "{records:?}"
+    );
+    assert_eq!(records[1].0, log::Level::Error);
+    assert!(
+        records[1]
+            .1
+            .ends_with("::produce: <failed to render arguments>: broken Debug"),
+        "{records:?}"
+    );
+    assert!(
+        records[2]
+            .1
+            .ends_with("::produce_async: <failed to render arguments>: broken Debug"),
+        "{records:?}"
+    );
+}
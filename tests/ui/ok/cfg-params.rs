@@ -0,0 +1,43 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+struct Disabled;
+
+#[logcall::logcall("info")]
+fn add(a: u32, #[cfg(any())] extra: Disabled, #[cfg(all())] b: u32) -> u32 {
+    a + b
+}
+
+// The first parameter is disabled.
+#[logcall::logcall("info", diff_by = "key")]
+fn update(#[cfg(any())] extra: Disabled, key: &str, value: u32) -> u32 {
+    value
+}
+
+struct Counter(u32);
+
+impl Counter {
+    #[logcall::logcall("info")]
+    async fn bump(&mut self, #[cfg(all())] by: u32, #[cfg(any())] extra: Disabled) -> u32 {
+        self.0 += by;
+        self.0
+    }
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    add(1, 2);
+    update("a", 1);
+    let mut counter = Counter(0);
+    pollster::block_on(counter.bump(3));
+
+    let records = LINES.lock().unwrap();
+    assert!(records[0].ends_with("::add(a = 1, b = 2) => 3"), "{}", records[0]);
+    assert!(records[1].ends_with("::update(key = \"a\", value = 1) => 1"), "{}", records[1]);
+    assert!(records[2].ends_with("::bump(self, by = 3) => 3"), "{}", records[2]);
+}
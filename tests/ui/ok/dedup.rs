@@ -0,0 +1,50 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info", dedup = "100ms")]
+fn poll(a: u32) -> u32 {
+    a
+}
+
+#[logcall::logcall("warn", dedup = "100ms")]
+fn retry() {}
+
+#[logcall::logcall(err = "error", dedup = "1h")]
+async fn fetch() -> Result<(), String> {
+    Err("unavailable".to_string())
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    for _ in 0..5 {
+        poll(1);
+    }
+    poll(2);
+    std::thread::sleep(std::time::Duration::from_millis(150));
+    poll(1);
+
+    for _ in 0..3 {
+        pollster::block_on(fetch()).ok();
+    }
+
+    // Nothing is called after the burst: the summary is emitted once the window is over.
+    for _ in 0..3 {
+        retry();
+    }
+    std::thread::sleep(std::time::Duration::from_millis(300));
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 7, "{lines:?}");
+    assert!(lines[0].ends_with("::poll(a = 1) => 1"));
+    assert!(lines[1].ends_with("::poll(a = 2) => 2"));
+    assert!(lines[2].ends_with("::poll(a = 1) => 1 repeated=4"));
+    assert!(lines[3].ends_with("::poll(a = 1) => 1"));
+    assert!(lines[4].ends_with("::fetch() => Err(\"unavailable\")"));
+    assert!(lines[5].ends_with("::retry() => ()"));
+    assert!(lines[6].ends_with("::retry() => () repeated=2"));
+}
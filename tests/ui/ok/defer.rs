@@ -0,0 +1,38 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info", defer)]
+fn step(tick: u32) -> u32 {
+    tick
+}
+
+#[logcall::logcall(ok = "info", err = "error", defer)]
+async fn load(tick: u32) -> Result<u32, u32> {
+    Err(tick)
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    step(1);
+    step(2);
+    pollster::block_on(load(3)).ok();
+    assert!(LINES.lock().unwrap().is_empty());
+
+    logcall::flush();
+    {
+        let lines = LINES.lock().unwrap();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].ends_with("::step(tick = 1) => 1"));
+        assert!(lines[1].ends_with("::step(tick = 2) => 2"));
+        assert!(lines[2].ends_with("::load(tick = 3) => Err(3)"));
+    }
+
+    // Records that were never flushed are emitted when the thread exits.
+    std::thread::spawn(|| step(4)).join().unwrap();
+    assert!(LINES.lock().unwrap()[3].ends_with("::step(tick = 4) => 4"));
+}
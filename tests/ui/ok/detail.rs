@@ -0,0 +1,67 @@
+use logger::Logger;
+use logger::RECORDS;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[derive(Debug)]
+struct Batch {
+    id: u32,
+    rows: Vec<u32>,
+}
+
+#[logcall::logcall("info", detail(trace = "full", info = "summary"))]
+fn ingest(id: u32, name: &str, data: &[u32], batch: Batch, parent: Option<u32>) -> usize {
+    data.len() + batch.rows.len() + name.len() + id as usize + parent.unwrap_or(0) as usize
+}
+
+#[logcall::logcall(ok = "trace", err = "warn", detail(trace = "full", info = "summary"))]
+fn fetch(keys: Vec<&str>) -> Result<usize, String> {
+    if keys.is_empty() {
+        Err("no keys".to_string())
+    } else {
+        Ok(keys.len())
+    }
+}
+
+fn take() -> Vec<(log::Level, String)> {
+    std::mem::take(&mut *RECORDS.lock().unwrap())
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let ingest = || {
+        let batch = Batch {
+            id: 1,
+            rows: vec![4, 5],
+        };
+        ingest(7, "a\"lice\u{e9}", &[1, 2, 3], batch, Some(1))
+    };
+    ingest();
+    let lines = take();
+    assert!(lines[0].1.ends_with(
+        "::ingest(id = 7, name = \"…\" (7 chars), data = [3 items], batch = Batch { .. }, parent = Some(..)) => 21"
+    ));
+
+    // The levels below `info`, down to `trace`, render the arguments in full.
+    let path = format!("{}::ingest", module_path!());
+    logcall::set_level_override(&path, log::Level::Debug);
+    ingest();
+    logcall::set_level_override(&path, log::Level::Warn);
+    ingest();
+    let lines = take();
+    assert!(lines[0].1.contains(
+        "::ingest(id = 7, name = \"a\\\"lice\u{e9}\", data = [1, 2, 3], batch = Batch { id: 1, rows: [4, 5] }, parent = Some(1))"
+    ));
+    assert!(lines[1].1.contains("(id = 7, name = \"…\" (7 chars), data = [3 items]"));
+
+    // The level depends on the outcome, so does the detail.
+    fetch(vec!["a", "b"]).unwrap();
+    fetch(vec![]).unwrap_err();
+    let lines = take();
+    assert!(lines[0].1.ends_with("::fetch(keys = [\"a\", \"b\"]) => Ok(2)"));
+    assert_eq!(lines[1].0, log::Level::Warn);
+    assert!(lines[1].1.ends_with("::fetch(keys = [0 items]) => Err(\"no keys\")"));
+}
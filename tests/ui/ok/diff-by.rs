@@ -0,0 +1,66 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[derive(Debug)]
+struct Spec {
+    replicas: u32,
+    image: String,
+    labels: Vec<&'static str>,
+}
+
+struct Controller;
+
+impl Controller {
+    #[logcall::logcall("info", diff_by = "name")]
+    fn reconcile(&self, name: &str, spec: &Spec, generation: u64) -> bool {
+        spec.replicas > 0 && generation > 0
+    }
+}
+
+#[logcall::logcall("info", diff_by = "id")]
+async fn sync(id: u32, state: Vec<u32>) -> usize {
+    state.len()
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let mut spec = Spec {
+        replicas: 1,
+        image: "app:1".to_string(),
+        labels: vec!["web"],
+    };
+    Controller.reconcile("web", &spec, 1);
+    spec.replicas = 3;
+    Controller.reconcile("web", &spec, 1);
+    Controller.reconcile("db", &spec, 1);
+    spec.labels.push("canary");
+    Controller.reconcile("web", &spec, 2);
+    Controller.reconcile("web", &spec, 2);
+
+    pollster::block_on(sync(1, vec![1, 2]));
+    pollster::block_on(sync(1, vec![1, 2, 3]));
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 7, "{lines:?}");
+    assert!(
+        lines[0].ends_with(
+            "::reconcile(self, name = \"web\", spec = Spec { replicas: 1, image: \"app:1\", labels: [\"web\"] }, generation = 1) => true"
+        ),
+        "{lines:?}"
+    );
+    assert!(lines[1].ends_with("::reconcile(self, name = \"web\", spec.replicas: 1 -> 3) => true"));
+    assert!(lines[2].ends_with(
+        "::reconcile(self, name = \"db\", spec = Spec { replicas: 3, image: \"app:1\", labels: [\"web\"] }, generation = 1) => true"
+    ));
+    assert!(lines[3].ends_with(
+        "::reconcile(self, name = \"web\", spec.labels: [\"web\"] -> [\"web\", \"canary\"], generation: 1 -> 2) => true"
+    ));
+    assert!(lines[4].ends_with("::reconcile(self, name = \"web\", unchanged) => true"));
+    assert!(lines[5].ends_with("::sync(id = 1, state = [1, 2]) => 2"));
+    assert!(lines[6].ends_with("::sync(id = 1, state: [1, 2] -> [1, 2, 3]) => 3"));
+}
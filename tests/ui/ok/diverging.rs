@@ -0,0 +1,38 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("warn")]
+fn fail(reason: &str) -> ! {
+    panic!("{reason}")
+}
+
+#[logcall::logcall("info")]
+async fn run_forever() -> ! {
+    loop {
+        std::future::pending::<()>().await;
+    }
+}
+
+#[logcall::logcall("info", heartbeat = "10ms")]
+fn serve(port: u16) -> ! {
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    let lines = LINES.lock().unwrap();
+    assert!(lines[0].ends_with("::fail(reason = \"boom\") => !"));
+    assert!(lines[1].ends_with("::serve(port = 8080) => !"));
+    assert!(lines[2].contains("::serve(port = 8080) still running ("));
+    std::process::exit(0)
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    std::panic::set_hook(Box::new(|_| {}));
+    std::thread::spawn(|| fail("boom")).join().ok();
+    let _ = run_forever;
+    serve(8080)
+}
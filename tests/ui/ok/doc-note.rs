@@ -0,0 +1,32 @@
+#![deny(missing_docs)]
+//! Functions instrumented with and without a doc note.
+
+/// Adds two numbers.
+#[logcall::logcall("info")]
+pub fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+/// Parses a number.
+#[logcall::logcall(ok = "debug", err = "error")]
+pub fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+/// Subtracts two numbers.
+#[logcall::logcall("info", undocumented)]
+pub fn subtract(a: u32, b: u32) -> u32 {
+    a - b
+}
+
+#[logcall::logcall("info")]
+fn multiply(a: u32, b: u32) -> u32 {
+    a * b
+}
+
+fn main() {
+    add(1, 2);
+    parse("1").unwrap();
+    subtract(2, 1);
+    multiply(2, 3);
+}
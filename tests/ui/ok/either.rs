@@ -0,0 +1,42 @@
+use either::Either;
+
+use logger::Logger;
+use logger::RECORDS;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall(left = "info", right = "debug")]
+fn lookup(key: u32) -> Either<u32, String> {
+    if key == 0 {
+        Either::Left(key)
+    } else {
+        Either::Right(key.to_string())
+    }
+}
+
+#[logcall::logcall(right = "warn")]
+async fn fallback(key: u32) -> Either<u32, String> {
+    lookup(key)
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    lookup(0);
+    lookup(1);
+    pollster::block_on(fallback(0));
+    pollster::block_on(fallback(2));
+
+    let lines = RECORDS.lock().unwrap();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0].0, log::Level::Info);
+    assert!(lines[0].1.ends_with("::lookup(key = 0) => Left(0)"), "{}", lines[0].1);
+    assert_eq!(lines[1].0, log::Level::Debug);
+    assert!(lines[1].1.ends_with("::lookup(key = 1) => Right(\"1\")"), "{}", lines[1].1);
+    assert_eq!(lines[2].0, log::Level::Info);
+    assert_eq!(lines[3].0, log::Level::Debug);
+    assert_eq!(lines[4].0, log::Level::Warn);
+    assert!(lines[4].1.ends_with("::fallback(key = 2) => Right(\"2\")"), "{}", lines[4].1);
+}
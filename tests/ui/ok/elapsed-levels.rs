@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use logger::Logger;
+use logger::RECORDS;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("trace", elapsed_levels(20ms = "debug", 40ms = "info", 60ms = "warn"))]
+fn handle(ms: u64) {
+    std::thread::sleep(Duration::from_millis(ms));
+}
+
+#[logcall::logcall(ok = "info", err = "error", elapsed_levels(20ms = "warn"))]
+async fn fetch(ms: u64) -> Result<u64, u64> {
+    std::thread::sleep(Duration::from_millis(ms));
+    if ms > 50 {
+        Err(ms)
+    } else {
+        Ok(ms)
+    }
+}
+
+// Only the calls reaching the first threshold are logged.
+#[logcall::logcall(elapsed_levels(20ms = "info", 1s = "error"))]
+fn query(ms: u64) {
+    std::thread::sleep(Duration::from_millis(ms));
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    handle(0);
+    handle(30);
+    handle(50);
+    handle(70);
+    pollster::block_on(fetch(0)).unwrap();
+    pollster::block_on(fetch(30)).unwrap();
+    pollster::block_on(fetch(60)).unwrap_err();
+    query(0);
+    query(30);
+
+    let records = RECORDS.lock().unwrap();
+    let levels = records.iter().map(|(level, _)| *level).collect::<Vec<_>>();
+    assert_eq!(
+        levels,
+        [
+            log::Level::Trace,
+            log::Level::Debug,
+            log::Level::Info,
+            log::Level::Warn,
+            log::Level::Info,
+            log::Level::Warn,
+            log::Level::Error,
+            log::Level::Info,
+        ]
+    );
+    assert!(records[7].1.ends_with("query(ms = 30) => ()"), "{}", records[7].1);
+}
@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info", elapsed)]
+fn wait(ms: u64) {
+    std::thread::sleep(Duration::from_millis(ms));
+}
+
+#[logcall::logcall(ok = "info", elapsed)]
+async fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    std::thread::sleep(Duration::from_millis(5));
+    input.parse()
+}
+
+fn elapsed_ms(line: &str) -> f64 {
+    let elapsed = line.rsplit_once(" elapsed=").unwrap().1;
+    elapsed.strip_suffix("ms").unwrap().parse().unwrap()
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    wait(5);
+    // Measured from the first poll, not from the creation of the future.
+    let future = parse("1");
+    std::thread::sleep(Duration::from_millis(200));
+    pollster::block_on(future).unwrap();
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].contains("::wait(ms = 5) => () elapsed="), "{lines:?}");
+    assert!(lines[1].contains("::parse(input = \"1\") => Ok(1) elapsed="), "{lines:?}");
+    assert!(elapsed_ms(&lines[0]) >= 5.0, "{lines:?}");
+    assert!((5.0..100.0).contains(&elapsed_ms(&lines[1])), "{lines:?}");
+}
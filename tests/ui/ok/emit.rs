@@ -0,0 +1,40 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+static RECORDS: AtomicUsize = AtomicUsize::new(0);
+
+struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, _: &log::Record) {
+        RECORDS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn flush(&self) {}
+}
+
+#[logcall::logcall("info", emit = "stderr")]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[logcall::logcall(ok = "debug", err = "error", emit = "stdout")]
+async fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+fn main() {
+    add(1, 2);
+    pollster::block_on(parse("1")).unwrap();
+    pollster::block_on(parse("a")).unwrap_err();
+
+    // The records are printed even though the logger is installed, but with logging disabled.
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Off);
+    add(3, 4);
+    assert_eq!(RECORDS.load(Ordering::Relaxed), 0);
+}
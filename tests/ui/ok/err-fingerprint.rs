@@ -0,0 +1,32 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall(ok = "info", err = "error", err_fingerprint)]
+fn fetch(id: u32) -> Result<u32, String> {
+    match id {
+        0 => Err("not found".to_string()),
+        1 => Err("timed out".to_string()),
+        id => Ok(id),
+    }
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    fetch(2).unwrap();
+    fetch(0).unwrap_err();
+    fetch(0).unwrap_err();
+    fetch(1).unwrap_err();
+
+    let lines = LINES.lock().unwrap();
+    assert!(lines[0].ends_with("::fetch(id = 2) => Ok(2)"));
+    // The fingerprint doesn't depend on the host or the arguments, only on the error.
+    assert!(lines[1].ends_with("::fetch(id = 0) => Err(\"not found\") fingerprint=e827d1f3"));
+    assert!(lines[2].ends_with("::fetch(id = 0) => Err(\"not found\") fingerprint=e827d1f3"));
+    assert!(lines[3].contains("=> Err(\"timed out\") fingerprint="));
+    assert!(!lines[3].ends_with("fingerprint=e827d1f3"));
+}
@@ -0,0 +1,32 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info", exec_context)]
+fn compute(a: u32) -> u32 {
+    a
+}
+
+#[logcall::logcall("info", exec_context)]
+async fn handle(a: u32) -> u32 {
+    compute(a)
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    compute(1);
+    std::thread::spawn(|| compute(2)).join().unwrap();
+    pollster::block_on(handle(3));
+    compute(4);
+
+    let lines = LINES.lock().unwrap();
+    assert!(lines[0].ends_with("::compute(a = 1) => 1 exec=main"), "{}", lines[0]);
+    assert!(lines[1].ends_with("::compute(a = 2) => 2 exec=thread"), "{}", lines[1]);
+    assert!(lines[2].ends_with("::compute(a = 3) => 3 exec=worker"), "{}", lines[2]);
+    assert!(lines[3].ends_with("::handle(a = 3) => 3 exec=worker"), "{}", lines[3]);
+    assert!(lines[4].ends_with("::compute(a = 4) => 4 exec=main"), "{}", lines[4]);
+}
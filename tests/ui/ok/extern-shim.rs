@@ -0,0 +1,36 @@
+use std::os::raw::c_int;
+
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+logcall::extern_shim! {
+    unsafe fn abs(x: c_int) -> c_int;
+    unsafe extern "C" {
+        #[logcall("info")]
+        pub fn cbrt(x: f64) -> f64;
+        /// Declared under another name.
+        #[link_name = "labs"]
+        fn long_abs(x: std::os::raw::c_long) -> std::os::raw::c_long;
+    }
+    // Not marked `unsafe`, so the wrapper is an `unsafe fn`.
+    fn toupper(c: c_int) -> c_int;
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    assert_eq!(abs(-3), 3);
+    assert_eq!(cbrt(8.0), 2.0);
+    assert_eq!(long_abs(-4), 4);
+    assert_eq!(unsafe { toupper(b'a' as c_int) }, b'A' as c_int);
+
+    let lines = LINES.lock().unwrap();
+    assert!(lines[0].ends_with("::abs(x = -3) => 3"), "{}", lines[0]);
+    assert!(lines[1].ends_with("::cbrt(x = 8.0) => 2.0"), "{}", lines[1]);
+    assert!(lines[2].ends_with("::long_abs(x = -4) => 4"), "{}", lines[2]);
+    assert!(lines[3].contains("::toupper(c = 97) => 65"), "{}", lines[3]);
+}
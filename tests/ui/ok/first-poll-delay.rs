@@ -0,0 +1,71 @@
+// The desugared signatures must not warn in the crate of the function.
+#![deny(warnings)]
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::time::Duration;
+
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+struct Cache<'a> {
+    entries: HashMap<&'a str, String>,
+}
+
+impl<'a> Cache<'a> {
+    #[logcall::logcall("info", first_poll_delay)]
+    async fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    #[logcall::logcall("info", first_poll_delay)]
+    async fn into_len(self, extra: impl Debug) -> usize {
+        format!("{extra:?}").len() + self.entries.len()
+    }
+}
+
+#[logcall::logcall(ok = "info", first_poll_delay, elapsed)]
+async fn parse<T: std::str::FromStr>(input: &str, f: impl Fn(&str) -> bool) -> Result<T, T::Err>
+where
+    T::Err: Debug,
+    T: Debug,
+{
+    assert!(f(input));
+    input.parse()
+}
+
+fn delay_ms(line: &str) -> f64 {
+    let delay = line.split(" first_poll_delay=").nth(1).unwrap();
+    let delay = delay.split(' ').next().unwrap();
+    delay.strip_suffix("ms").unwrap().parse().unwrap()
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let cache = Cache {
+        entries: HashMap::from([("a", "1".to_string())]),
+    };
+    // The delay runs from the creation of the future to its first poll.
+    let future = cache.get("a");
+    std::thread::sleep(Duration::from_millis(50));
+    assert_eq!(pollster::block_on(future), Some("1"));
+    pollster::block_on(parse::<u32>("7", |input| !input.is_empty())).unwrap();
+    assert_eq!(pollster::block_on(cache.into_len(1)), 2);
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 3, "{lines:?}");
+    assert!(
+        lines[0].contains("::get(self, key = \"a\") => Some(\"1\") first_poll_delay="),
+        "{lines:?}"
+    );
+    assert!(delay_ms(&lines[0]) >= 50.0, "{lines:?}");
+    assert!(lines[1].contains("::parse(input = \"7\", f = <"), "{lines:?}");
+    assert!(lines[1].contains(" => Ok(7) elapsed="), "{lines:?}");
+    assert!(lines[1].contains(" first_poll_delay="), "{lines:?}");
+    assert!(lines[2].contains(", extra = 1) => 2 first_poll_delay="), "{lines:?}");
+}
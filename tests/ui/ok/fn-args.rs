@@ -0,0 +1,53 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info")]
+fn apply(f: impl Fn(u32) -> u32, a: u32) -> u32 {
+    f(a)
+}
+
+#[logcall::logcall("info")]
+fn apply_ptr(f: fn(u32, u32) -> u32, a: u32) -> u32 {
+    f(a, a)
+}
+
+#[logcall::logcall("info")]
+fn apply_generic<F>(f: &mut F) -> u32
+where
+    F: FnMut() -> u32,
+{
+    f()
+}
+
+#[logcall::logcall("info")]
+fn apply_boxed(f: Box<dyn FnOnce(u32) -> u32>) -> u32 {
+    f(1)
+}
+
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    apply(|a| a + 1, 1);
+    apply_ptr(add, 2);
+    apply_generic(&mut || 3);
+    apply_boxed(Box::new(|a| a));
+
+    let lines = LINES.lock().unwrap();
+    assert!(lines[0].ends_with("::main::{{closure}} arity=1>, a = 1) => 2"), "{}", lines[0]);
+    assert!(
+        lines[1].ends_with("::apply_ptr(f = <fn(u32, u32) -> u32 arity=2>, a = 2) => 4"),
+        "{}",
+        lines[1]
+    );
+    assert!(lines[2].ends_with("::main::{{closure}} arity=0>) => 3"), "{}", lines[2]);
+    assert!(lines[3].contains("::apply_boxed(f = <alloc::boxed::Box<dyn "), "{}", lines[3]);
+    assert!(lines[3].ends_with(" arity=1>) => 1"), "{}", lines[3]);
+}
@@ -0,0 +1,49 @@
+use std::collections::BTreeMap;
+
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[derive(Debug)]
+struct Batch {
+    id: u32,
+    rows: Vec<(u32, &'static str)>,
+}
+
+#[logcall::logcall("info", head(items = 3))]
+fn ingest(data: &[u32], index: BTreeMap<&str, u32>, batch: Batch) -> usize {
+    data.len() + index.len() + batch.rows.len()
+}
+
+#[logcall::logcall("info", head(data, items = 2))]
+fn store(data: Vec<&str>, tags: Vec<&str>) {}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let data = (1..=1000).collect::<Vec<_>>();
+    let index = BTreeMap::from([("a", 1), ("b", 2), ("c", 3), ("d", 4)]);
+    let batch = Batch {
+        id: 7,
+        rows: vec![(1, "x, y"), (2, "]"), (3, "z"), (4, "w")],
+    };
+    ingest(&data, index, batch);
+    ingest(&[1, 2, 3], BTreeMap::new(), Batch { id: 8, rows: vec![] });
+    store(vec!["a", "b", "c"], vec!["x", "y", "z"]);
+
+    let lines = LINES.lock().unwrap();
+    assert!(lines[0].ends_with(
+        "::ingest(data = [1, 2, 3, … (+997 more)], \
+         index = {\"a\": 1, \"b\": 2, \"c\": 3, … (+1 more)}, \
+         batch = Batch { id: 7, rows: [(1, \"x, y\"), (2, \"]\"), (3, \"z\"), … (+1 more)] }) => 1008"
+    ));
+    assert!(lines[1].ends_with(
+        "::ingest(data = [1, 2, 3], index = {}, batch = Batch { id: 8, rows: [] }) => 3"
+    ));
+    assert!(lines[2].ends_with(
+        "::store(data = [\"a\", \"b\", … (+1 more)], tags = [\"x\", \"y\", \"z\"]) => ()"
+    ));
+}
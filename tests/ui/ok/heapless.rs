@@ -0,0 +1,37 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info", heapless)]
+fn f(a: u32) -> u32 {
+    a
+}
+
+#[logcall::logcall(ok = "info", err = "error", heapless = 8)]
+fn g(s: &str) -> Result<String, String> {
+    Ok(s.to_string())
+}
+
+#[logcall::logcall("info", heapless = 7, input = "s = {s}")]
+async fn h(s: &str) -> usize {
+    s.len()
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    f(1);
+    g("short").ok();
+    g("a longer string").ok();
+    pollster::block_on(h("éééé"));
+
+    let lines = LINES.lock().unwrap();
+    assert!(lines[0].ends_with("::f(a = 1) => 1"));
+    assert!(lines[1].ends_with("::g(s = \"sho...) => Ok(\"shor..."));
+    assert!(lines[2].ends_with("::g(s = \"a l...) => Ok(\"a lo..."));
+    // Truncation happens at character boundaries.
+    assert!(lines[3].ends_with("::h(s = é...) => 8"));
+}
@@ -0,0 +1,26 @@
+// The variables of the expansion must not collide with those of the function.
+#[allow(non_upper_case_globals)]
+static __AFTER_ERROR: u32 = 1;
+
+#[logcall::logcall("info", accumulate = "both")]
+fn add(__input_string: u32, __ret_value: u32, __call_start: u32) -> u32 {
+    __input_string + __ret_value + __call_start
+}
+
+#[logcall::logcall(ok = "info", err = "error", after_error)]
+#[track_caller]
+fn parse(__value: &str, __err: u32) -> Result<u32, std::num::ParseIntError> {
+    let value = __value.parse::<u32>()?;
+    Ok(value + __err + __AFTER_ERROR)
+}
+
+#[logcall::logcall("info", exec_context)]
+async fn fetch(__polling_guard: u32, __trace_guard: u32) -> u32 {
+    __polling_guard + __trace_guard
+}
+
+fn main() {
+    assert_eq!(add(1, 2, 3), 6);
+    assert_eq!(parse("1", 2).unwrap(), 4);
+    assert_eq!(pollster::block_on(fetch(1, 2)), 3);
+}
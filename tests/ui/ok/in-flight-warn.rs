@@ -0,0 +1,58 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+
+use logger::Logger;
+use logger::RECORDS;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+// Ready once woken by a thread after `0`.
+struct Delay(Duration, bool);
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.1 {
+            return Poll::Ready(());
+        }
+        self.1 = true;
+        let (delay, waker) = (self.0, cx.waker().clone());
+        std::thread::spawn(move || {
+            std::thread::sleep(delay);
+            waker.wake();
+        });
+        Poll::Pending
+    }
+}
+
+#[logcall::logcall("info", in_flight_warn = "50ms")]
+async fn fetch(ms: u64) -> u64 {
+    Delay(Duration::from_millis(ms), false).await;
+    ms
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    pollster::block_on(fetch(200));
+    pollster::block_on(fetch(0));
+    // A future dropped while pending is no longer watched.
+    let mut future = Box::pin(fetch(1000));
+    let waker = std::task::Waker::noop();
+    assert!(future.as_mut().poll(&mut Context::from_waker(waker)).is_pending());
+    drop(future);
+    std::thread::sleep(Duration::from_millis(150));
+
+    let records = RECORDS.lock().unwrap();
+    assert_eq!(records.len(), 3, "{records:?}");
+    assert_eq!(records[0].0, log::Level::Warn);
+    assert!(records[0].1.ends_with("::fetch(ms = 200) still in flight after 50ms"), "{records:?}");
+    assert!(records[1].1.ends_with("::fetch(ms = 200) => 200"), "{records:?}");
+    assert!(records[2].1.ends_with("::fetch(ms = 0) => 0"), "{records:?}");
+}
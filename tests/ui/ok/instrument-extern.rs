@@ -0,0 +1,35 @@
+mod foreign {
+    pub fn add(a: u32, b: u32) -> u32 {
+        a + b
+    }
+
+    pub fn parse(s: &str) -> Result<u32, std::num::ParseIntError> {
+        s.parse()
+    }
+
+    pub async fn double(a: u32) -> u32 {
+        a * 2
+    }
+
+    pub fn len<T: AsRef<str>>(s: T) -> usize {
+        s.as_ref().len()
+    }
+}
+
+logcall::instrument_extern! {
+    foreign::add as fn add_logged(a: u32, b: u32) -> u32;
+    #[logcall(ok = "info", err = "error")]
+    foreign::parse as pub fn parse_logged(s: &str) -> Result<u32, std::num::ParseIntError>;
+    #[logcall("info")]
+    foreign::double as pub(crate) async fn double_logged(a: u32) -> u32;
+    /// Generic wrappers are supported as well.
+    #[inline]
+    foreign::len as fn len_logged<T: AsRef<str> + std::fmt::Debug>(s: T) -> usize;
+}
+
+fn main() {
+    add_logged(1, 2);
+    parse_logged("1").ok();
+    pollster::block_on(double_logged(1));
+    len_logged("foo");
+}
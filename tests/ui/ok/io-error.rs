@@ -0,0 +1,38 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall(err = "error")]
+fn read(path: &str) -> std::io::Result<String> {
+    std::fs::read_to_string(path)
+}
+
+#[logcall::logcall("info")]
+fn invalid() -> Result<(), std::io::Error> {
+    Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "bad header"))
+}
+
+#[logcall::logcall(err = "error")]
+async fn other() -> Result<(), String> {
+    Err("not an io error".to_string())
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    read("/nonexistent/logcall").ok();
+    invalid().ok();
+    pollster::block_on(other()).ok();
+
+    let lines = LINES.lock().unwrap();
+    assert!(
+        lines[0].ends_with(" kind=NotFound errno=2 message=\"No such file or directory\""),
+        "{}",
+        lines[0]
+    );
+    assert!(lines[1].ends_with(" kind=InvalidData message=\"bad header\""), "{}", lines[1]);
+    assert!(lines[2].ends_with("::other() => Err(\"not an io error\")"), "{}", lines[2]);
+}
@@ -0,0 +1,169 @@
+use std::time::Duration;
+
+use logger::Logger;
+use logger::RECORDS;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::module_defaults(level = "warn")]
+mod db {
+    #[logcall::logcall("info")]
+    pub fn query(id: u32) -> u32 {
+        id
+    }
+
+    #[logcall::logcall(ok = "info")]
+    pub fn find(id: u32) -> Result<u32, ()> {
+        if id > 0 {
+            Ok(id)
+        } else {
+            Err(())
+        }
+    }
+
+    #[logcall::logcall(ok = "info", err = "error")]
+    pub fn insert(id: u32) -> Result<u32, u32> {
+        if id > 0 {
+            Ok(id)
+        } else {
+            Err(id)
+        }
+    }
+
+    #[logcall::logcall(err = "error")]
+    pub fn delete(id: u32) -> Result<u32, u32> {
+        if id > 0 {
+            Ok(id)
+        } else {
+            Err(id)
+        }
+    }
+
+    #[logcall::logcall("debug", slow = "200ms", slow_level = "error")]
+    pub fn vacuum(duration: std::time::Duration) {
+        std::thread::sleep(duration);
+    }
+
+    pub mod pool {
+        #[logcall::logcall]
+        pub fn acquire() {}
+    }
+}
+
+mod payments {
+    #[logcall::logcall("info")]
+    pub fn charge(amount: u32) -> u32 {
+        amount
+    }
+
+    #[logcall::logcall("info")]
+    pub fn refund(amount: u32) -> u32 {
+        amount
+    }
+}
+
+mod jobs {
+    use std::time::Duration;
+
+    #[logcall::logcall(ok = "info", err = "error")]
+    pub fn run(id: u32) -> Result<u32, u32> {
+        if id > 0 {
+            Ok(id)
+        } else {
+            Err(id)
+        }
+    }
+
+    #[logcall::logcall("debug", slow = "200ms", slow_level = "warn")]
+    pub fn wait(duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+fn take() -> Vec<(log::Level, String)> {
+    std::mem::take(&mut *RECORDS.lock().unwrap())
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    // The module defaults override the attributes, but don't log the variants they don't log.
+    db::query(1);
+    db::find(1).unwrap();
+    db::find(0).unwrap_err();
+    db::pool::acquire();
+    let lines = take();
+    assert_eq!(lines.len(), 3, "{lines:?}");
+    assert!(lines.iter().all(|(level, _)| *level == log::Level::Warn));
+    assert!(lines[2].1.ends_with("::db::pool::acquire() => ()"));
+
+    // The `Err` records keep their level, and the slow calls are still escalated.
+    db::insert(1).unwrap();
+    db::insert(0).unwrap_err();
+    db::delete(1).unwrap();
+    db::delete(0).unwrap_err();
+    db::vacuum(Duration::ZERO);
+    db::vacuum(Duration::from_millis(200));
+    let levels = take().into_iter().map(|(level, _)| level).collect::<Vec<_>>();
+    assert_eq!(
+        levels,
+        [
+            log::Level::Warn,
+            log::Level::Error,
+            log::Level::Error,
+            log::Level::Warn,
+            log::Level::Error
+        ]
+    );
+
+    // The runtime overrides take precedence over everything, the most specific first.
+    let payments = format!("{}::payments", module_path!());
+    logcall::set_level_override(&payments, log::Level::Debug);
+    logcall::set_level_override(&format!("{payments}::refund"), log::Level::Trace);
+    logcall::set_level_override(&format!("{}::db", module_path!()), log::Level::Error);
+    payments::charge(1);
+    payments::refund(2);
+    db::query(3);
+    let levels = take().into_iter().map(|(level, _)| level).collect::<Vec<_>>();
+    assert_eq!(
+        levels,
+        [log::Level::Debug, log::Level::Trace, log::Level::Error]
+    );
+
+    logcall::remove_level_override(&payments);
+    payments::charge(1);
+    payments::refund(2);
+    logcall::clear_level_overrides();
+    payments::refund(2);
+    db::query(3);
+    let levels = take().into_iter().map(|(level, _)| level).collect::<Vec<_>>();
+    assert_eq!(
+        levels,
+        [
+            log::Level::Info,
+            log::Level::Trace,
+            log::Level::Info,
+            log::Level::Warn
+        ]
+    );
+
+    // The `Err` records keep their level, and the slow calls are still escalated.
+    logcall::set_level_override("", log::Level::Trace);
+    jobs::run(1).unwrap();
+    jobs::run(0).unwrap_err();
+    jobs::wait(Duration::ZERO);
+    jobs::wait(Duration::from_millis(200));
+    logcall::clear_level_overrides();
+    let levels = take().into_iter().map(|(level, _)| level).collect::<Vec<_>>();
+    assert_eq!(
+        levels,
+        [
+            log::Level::Trace,
+            log::Level::Error,
+            log::Level::Trace,
+            log::Level::Warn
+        ]
+    );
+}
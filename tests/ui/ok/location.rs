@@ -0,0 +1,40 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info", location)]
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+struct Cart;
+
+#[logcall::logcall("info", location)]
+impl Cart {
+    fn total(&self) -> u32 {
+        3
+    }
+
+    async fn checkout(&self) -> bool {
+        true
+    }
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    add(1, 2);
+    Cart.total();
+    pollster::block_on(Cart.checkout());
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 3);
+    // The path is relative to the directory the test is compiled from.
+    assert!(lines[0].contains("::add(a = 1, b = 2) => 3 at="), "{lines:?}");
+    assert!(lines[0].ends_with("location.rs:8"), "{lines:?}");
+    assert!(lines[1].ends_with("location.rs:16"), "{lines:?}");
+    assert!(lines[2].ends_with("location.rs:20"), "{lines:?}");
+}
@@ -0,0 +1,30 @@
+use logcall::LoggedFn;
+
+struct Server {
+    on_request: LoggedFn<Box<dyn Fn(u32, &str) -> Result<u32, String>>>,
+}
+
+fn main() {
+    let add = LoggedFn::new("add", log::Level::Info, |a: u32, b: u32| a + b);
+    assert_eq!(add.call((1, 2)), 3);
+
+    let mut counter = 0;
+    let mut incr = LoggedFn::new("incr", log::Level::Debug, || {
+        counter += 1;
+        counter
+    });
+    incr.call_mut(());
+
+    let owned = String::from("owned");
+    let consume = LoggedFn::new("consume", log::Level::Trace, move |suffix: &str| owned + suffix);
+    consume.call_once(("!",));
+
+    let server = Server {
+        on_request: LoggedFn::new(
+            "on_request",
+            log::Level::Info,
+            Box::new(|id, path| if path.is_empty() { Err("empty path".to_string()) } else { Ok(id) }),
+        ),
+    };
+    server.on_request.call((1, "/")).ok();
+}
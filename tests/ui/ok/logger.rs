@@ -0,0 +1,42 @@
+use logger::Logger;
+use logger::RECORDS;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+mod audit {
+    // Wraps `log` with a mandatory context.
+    #[macro_export]
+    macro_rules! audit_log {
+        ($level:expr, $($arg:tt)+) => {
+            log::log!(target: "audit", $level, "[tenant=acme] {}", format_args!($($arg)+))
+        };
+    }
+}
+
+#[logcall::logcall("info", logger = "crate::audit_log")]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[logcall::logcall(ok = "debug", err = "error", logger = "audit_log")]
+async fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Info);
+
+    add(1, 2);
+    pollster::block_on(parse("1")).unwrap();
+    pollster::block_on(parse("a")).unwrap_err();
+
+    let lines = RECORDS.lock().unwrap();
+    assert_eq!(lines.len(), 2, "{lines:?}");
+    assert_eq!(lines[0].0, log::Level::Info);
+    assert!(lines[0].1.starts_with("[tenant=acme] "));
+    assert!(lines[0].1.ends_with("::add(a = 1, b = 2) => 3"));
+    assert_eq!(lines[1].0, log::Level::Error);
+    assert!(lines[1].1.ends_with("::parse(input = \"a\") => Err(ParseIntError { kind: InvalidDigit })"));
+}
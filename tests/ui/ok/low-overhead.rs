@@ -0,0 +1,62 @@
+use std::fmt;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+static RENDERED: AtomicUsize = AtomicUsize::new(0);
+
+struct Counted(u32);
+
+impl fmt::Debug for Counted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        RENDERED.fetch_add(1, Ordering::Relaxed);
+        write!(f, "{}", self.0)
+    }
+}
+
+#[logcall::logcall("debug", low_overhead)]
+fn add(a: Counted, b: u32) -> u32 {
+    a.0 + b
+}
+
+#[logcall::logcall(ok = "info", err = "error", low_overhead)]
+async fn fetch(id: Counted) -> Result<u32, String> {
+    Ok(id.0)
+}
+
+struct Store;
+
+impl Store {
+    #[logcall::logcall("info", low_overhead)]
+    fn get(&self, id: u32) -> u32 {
+        id
+    }
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Debug);
+
+    add(Counted(1), 2);
+    add(Counted(3), 4);
+    pollster::block_on(fetch(Counted(5))).unwrap();
+    Store.get(6);
+    assert_eq!(RENDERED.load(Ordering::Relaxed), 3);
+
+    // The inputs aren't rendered when the level is filtered out.
+    log::set_max_level(log::LevelFilter::Info);
+    add(Counted(7), 8);
+    assert_eq!(RENDERED.load(Ordering::Relaxed), 3);
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].ends_with("::add(a = 1, b = 2) => 3"));
+    assert!(lines[1].ends_with("::add(a = 3, b = 4) => 7"));
+    assert!(lines[2].ends_with("::fetch(id = 5) => Ok(5)"));
+    assert!(lines[3].ends_with("::Store::get(self, id = 6) => 6"));
+}
@@ -0,0 +1,47 @@
+macro_rules! make_fn {
+    ($name:ident, $arg:ident) => {
+        #[logcall::logcall("info")]
+        fn $name($arg: u32) -> u32 {
+            $arg
+        }
+    };
+}
+
+macro_rules! make_fn_inner {
+    ($name:ident) => {
+        #[logcall::logcall("info")]
+        fn $name(a: u32, b: u32) -> u32 {
+            a + b
+        }
+    };
+}
+
+macro_rules! make_fn_with_input {
+    ($name:ident) => {
+        #[logcall::logcall(ok = "info", input = "a = {a:?}, ..")]
+        fn $name(a: u32, b: u32) -> Result<u32, u32> {
+            Ok(a + b)
+        }
+    };
+}
+
+macro_rules! make_async_fn {
+    ($name:ident, $arg:ident) => {
+        #[logcall::logcall("info")]
+        async fn $name($arg: u32) -> u32 {
+            $arg
+        }
+    };
+}
+
+make_fn!(f, x);
+make_fn_inner!(g);
+make_fn_with_input!(h);
+make_async_fn!(i, y);
+
+fn main() {
+    f(1);
+    g(1, 2);
+    h(1, 2).ok();
+    pollster::block_on(i(1));
+}
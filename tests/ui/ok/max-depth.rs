@@ -0,0 +1,41 @@
+use logger::Logger;
+use logger::RECORDS;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("trace", max_depth = 3)]
+fn countdown(n: u32) -> u32 {
+    if n == 0 {
+        0
+    } else {
+        countdown(n - 1)
+    }
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    countdown(2);
+    assert!(RECORDS
+        .lock()
+        .unwrap()
+        .iter()
+        .all(|(level, _)| *level == log::Level::Trace));
+
+    countdown(5);
+    countdown(5);
+
+    let lines = RECORDS.lock().unwrap();
+    let errors: Vec<_> = lines
+        .iter()
+        .filter(|(level, _)| *level == log::Level::Error)
+        .collect();
+    assert_eq!(errors.len(), 1);
+    assert!(
+        errors[0].1.ends_with("::countdown(n = 2) exceeded max_depth=3 on thread \"main\""),
+        "{}",
+        errors[0].1
+    );
+}
@@ -0,0 +1,36 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall(ok = "info", err = "error", ok_if = "!ret.is_empty()")]
+fn poll(events: Vec<u32>, fail: bool) -> Result<Vec<u32>, String> {
+    if fail {
+        Err("disconnected".to_string())
+    } else {
+        Ok(events)
+    }
+}
+
+#[logcall::logcall(ok = "debug", ok_if = "*ret > 10")]
+async fn fetch(count: u32) -> Result<u32, String> {
+    Ok(count)
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    poll(vec![], false).unwrap();
+    poll(vec![1, 2], false).unwrap();
+    poll(vec![], true).unwrap_err();
+    pollster::block_on(fetch(3)).unwrap();
+    pollster::block_on(fetch(30)).unwrap();
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 3, "{lines:?}");
+    assert!(lines[0].ends_with("::poll(events = [1, 2], fail = false) => Ok([1, 2])"));
+    assert!(lines[1].ends_with("::poll(events = [], fail = true) => Err(\"disconnected\")"));
+    assert!(lines[2].ends_with("::fetch(count = 30) => Ok(30)"));
+}
@@ -0,0 +1,48 @@
+use std::fmt;
+
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+// Doesn't implement `Debug`.
+struct Page(Vec<u8>);
+
+#[derive(Debug)]
+struct NotFound(u32);
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "page {} not found", self.0)
+    }
+}
+
+#[logcall::logcall(ok = "info", err = "error", outcome_only)]
+fn load(id: u32) -> Result<Page, NotFound> {
+    if id == 0 {
+        Err(NotFound(id))
+    } else {
+        Ok(Page(vec![0; 4096]))
+    }
+}
+
+#[logcall::logcall(err = "warn", outcome_only)]
+async fn store(page: u32) -> Result<Page, String> {
+    Err(format!("disk full at page {page}"))
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    assert_eq!(load(1).unwrap().0.len(), 4096);
+    load(0).err().unwrap();
+    pollster::block_on(store(2)).err().unwrap();
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].ends_with("::load(id = 1) => ok"));
+    assert!(lines[1].ends_with("::load(id = 0) => err: page 0 not found"));
+    assert!(lines[2].ends_with("::store(page = 2) => err: disk full at page 2"));
+}
@@ -0,0 +1,69 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+// Ready once woken by a thread after `0`.
+struct Delay(Duration, bool);
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.1 {
+            return Poll::Ready(());
+        }
+        self.1 = true;
+        let (delay, waker) = (self.0, cx.waker().clone());
+        std::thread::spawn(move || {
+            std::thread::sleep(delay);
+            waker.wake();
+        });
+        Poll::Pending
+    }
+}
+
+#[logcall::logcall("info", poll_stats)]
+async fn fetch(busy_ms: u64, idle_ms: u64) -> u64 {
+    std::thread::sleep(Duration::from_millis(busy_ms));
+    Delay(Duration::from_millis(idle_ms), false).await;
+    busy_ms + idle_ms
+}
+
+// "1.5ms", "850µs", "12ns"
+fn millis(duration: &str) -> f64 {
+    let unit_start = duration.find(|c: char| c.is_alphabetic()).unwrap();
+    let (amount, unit) = duration.split_at(unit_start);
+    let amount: f64 = amount.parse().unwrap();
+    match unit {
+        "ns" => amount / 1_000_000.0,
+        "µs" => amount / 1_000.0,
+        "ms" => amount,
+        "s" => amount * 1_000.0,
+        _ => panic!("unexpected unit in {duration}"),
+    }
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    pollster::block_on(fetch(20, 30));
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 1);
+    let (message, stats) = lines[0].split_once(" polls=").unwrap();
+    assert!(message.ends_with("::fetch(busy_ms = 20, idle_ms = 30) => 50"));
+    let (polls, times) = stats.split_once(" busy=").unwrap();
+    let (busy, idle) = times.split_once(" idle=").unwrap();
+    assert_eq!(polls, "2");
+    assert!(millis(busy) >= 20.0, "{lines:?}");
+    assert!(millis(idle) >= 30.0, "{lines:?}");
+}
@@ -0,0 +1,80 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+static LINES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static PROGRESS_THREADS: Mutex<Vec<std::thread::ThreadId>> = Mutex::new(Vec::new());
+
+struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Info
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            let line = record.args().to_string();
+            if line.contains("still running") {
+                PROGRESS_THREADS
+                    .lock()
+                    .unwrap()
+                    .push(std::thread::current().id());
+            }
+            LINES.lock().unwrap().push(line);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[logcall::logcall(ok = "info", err = "error", progress_every = "20ms")]
+fn sync_files(count: u32) -> Result<u32, String> {
+    std::thread::sleep(Duration::from_millis(110));
+    Ok(count)
+}
+
+#[logcall::logcall("info", progress_every = "1s")]
+fn quick() -> u32 {
+    1
+}
+
+#[logcall::logcall("debug", progress_every = "10ms")]
+fn quiet() {
+    std::thread::sleep(Duration::from_millis(50));
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    sync_files(3).unwrap();
+    quick();
+    quiet();
+    // Give a late progress record the chance to show up.
+    std::thread::sleep(Duration::from_millis(50));
+
+    let lines = LINES.lock().unwrap();
+    let (progress, calls): (Vec<_>, Vec<_>) = lines
+        .iter()
+        .partition(|line| line.contains("still running"));
+    assert!(progress.len() >= 2, "{lines:?}");
+    assert!(progress
+        .iter()
+        .all(|line| line.contains("::sync_files(count = 3) still running (")));
+    assert_eq!(calls.len(), 2, "{lines:?}");
+    assert!(calls[0].ends_with("::sync_files(count = 3) => Ok(3)"));
+    assert!(calls[1].ends_with("::quick() => 1"));
+    // Nothing is logged after the call.
+    assert!(lines[lines.len() - 2].ends_with("=> Ok(3)"));
+    drop(lines);
+
+    // A single thread logs the progress of all the calls.
+    std::thread::scope(|scope| {
+        for count in 0..8 {
+            scope.spawn(move || sync_files(count).unwrap());
+        }
+    });
+    let mut threads = PROGRESS_THREADS.lock().unwrap().clone();
+    threads.dedup();
+    assert_eq!(threads.len(), 1, "{threads:?}");
+}
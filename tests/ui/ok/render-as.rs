@@ -0,0 +1,63 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[derive(Debug)]
+#[logcall::render_as("user:{}")]
+struct UserId(u64);
+
+#[derive(Debug)]
+#[logcall::render_as("order:{region}-{number}")]
+struct OrderId {
+    region: u16,
+    number: u64,
+}
+
+#[derive(Debug)]
+#[logcall::render_as]
+struct SessionId(u32);
+
+#[derive(Debug)]
+struct TenantId(&'static str);
+
+impl logcall::Render for TenantId {
+    fn render(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "tenant:{}", self.0)
+    }
+}
+
+#[logcall::logcall("info")]
+fn place(user: &UserId, order: OrderId, tenant: TenantId, quantity: u32) -> u32 {
+    quantity
+}
+
+#[logcall::logcall("info")]
+fn resume(session: SessionId) {}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let order = OrderId {
+        region: 3,
+        number: 12345,
+    };
+    place(&UserId(42), order, TenantId("acme"), 2);
+    // Rendered with `Debug` until a renderer is installed.
+    resume(SessionId(7));
+    logcall::set_renderer(|id: &SessionId, f| write!(f, "session:{:x}", id.0));
+    resume(SessionId(255));
+
+    let records = LINES.lock().unwrap();
+    assert!(
+        records[0].ends_with(
+            "::place(user = user:42, order = order:3-12345, tenant = tenant:acme, quantity = 2) => 2"
+        ),
+        "{}",
+        records[0]
+    );
+    assert!(records[1].ends_with("::resume(session = SessionId(7)) => ()"), "{}", records[1]);
+    assert!(records[2].ends_with("::resume(session = session:ff) => ()"), "{}", records[2]);
+}
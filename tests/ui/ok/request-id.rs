@@ -0,0 +1,36 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info")]
+fn f(a: u32) -> u32 {
+    a
+}
+
+#[logcall::logcall("info")]
+async fn g(a: u32) -> u32 {
+    f(a)
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    f(1);
+    logcall::Context::new()
+        .with_request_id("abc")
+        .scope_sync(|| {
+            assert_eq!(logcall::request_id().as_deref(), Some("abc"));
+            f(2)
+        });
+    pollster::block_on(logcall::Context::new().with_request_id("def").scope(g(3)));
+    assert_eq!(logcall::request_id(), None);
+
+    let lines = LINES.lock().unwrap();
+    assert!(lines[0].ends_with("::f(a = 1) => 1"));
+    assert!(lines[1].ends_with("::f(a = 2) => 2 request_id=abc"));
+    assert!(lines[2].ends_with("::f(a = 3) => 3 request_id=def"));
+    assert!(lines[3].ends_with("::g(a = 3) => 3 request_id=def"));
+}
@@ -0,0 +1,60 @@
+use std::collections::BTreeMap;
+
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[derive(Debug)]
+struct Credentials {
+    key: String,
+    secret: (u32, String),
+}
+
+#[derive(Debug)]
+struct Session {
+    user: &'static str,
+    token: String,
+    credentials: Option<Credentials>,
+}
+
+#[logcall::logcall(ok = "info", ret_redact(token, secret))]
+fn login(user: &'static str) -> Result<Session, String> {
+    Ok(Session {
+        user,
+        token: "abc, \"}def".to_string(),
+        credentials: Some(Credentials {
+            key: "token: x".to_string(),
+            secret: (1, "s".to_string()),
+        }),
+    })
+}
+
+#[logcall::logcall("info", ret_redact(password))]
+async fn settings() -> BTreeMap<&'static str, &'static str> {
+    BTreeMap::from([("password", "hunter2"), ("theme", "dark")])
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    login("alice").unwrap();
+    pollster::block_on(settings());
+
+    let lines = LINES.lock().unwrap();
+    assert!(
+        lines[0].ends_with(
+            "::login(user = \"alice\") => Ok(Session { user: \"alice\", token: <redacted>, \
+             credentials: Some(Credentials { key: \"token: x\", secret: <redacted> }) })"
+        ),
+        "{}",
+        lines[0]
+    );
+    assert!(
+        lines[1].ends_with("::settings() => {\"password\": <redacted>, \"theme\": \"dark\"}"),
+        "{}",
+        lines[1]
+    );
+}
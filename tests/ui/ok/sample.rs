@@ -0,0 +1,41 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info", sample_by = "user_id", sample = 0.5)]
+fn load(user_id: u64, page: u32) -> u32 {
+    page
+}
+
+#[logcall::logcall(err = "error", sample_by = "user", sample = 1.0)]
+async fn fetch(user: String) -> Result<(), String> {
+    Err(user)
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    for user_id in 0..100 {
+        load(user_id, 1);
+        load(user_id, 2);
+    }
+    pollster::block_on(fetch("alice".to_string())).unwrap_err();
+
+    let lines = LINES.lock().unwrap();
+    let (fetch, load) = lines.split_last().unwrap();
+    assert!(fetch.ends_with("::fetch(user = \"alice\") => Err(\"alice\")"));
+
+    // Either both calls of a user are logged, or none.
+    assert!(load.len() > 20 && load.len() < 180, "{}", load.len());
+    for pair in load.chunks(2) {
+        assert!(pair[0].contains(", page = 1) => 1"), "{pair:?}");
+        assert!(pair[1].contains(", page = 2) => 2"), "{pair:?}");
+        assert_eq!(
+            pair[0].split(", page").next(),
+            pair[1].split(", page").next()
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use logger::Logger;
+use logger::RECORDS;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info", schema)]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[logcall::logcall(ok = "debug", err = "warn", schema)]
+async fn lookup<'a>(names: &'a [&str], index: usize) -> Result<&'a str, String> {
+    names
+        .get(index)
+        .copied()
+        .ok_or_else(|| "not found".to_string())
+}
+
+struct Counter(u32);
+
+impl Counter {
+    #[logcall::logcall(schema)]
+    fn bump(&mut self, by: Option<u32>) {
+        self.0 += by.unwrap_or(1);
+    }
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    add(1, 2);
+    add(3, 4);
+    pollster::block_on(lookup(&["a"], 0)).unwrap();
+    pollster::block_on(lookup(&["a"], 1)).unwrap_err();
+    Counter(0).bump(None);
+
+    let lines = RECORDS.lock().unwrap();
+    let lines: Vec<_> = lines
+        .iter()
+        .map(|(level, line)| (*level, line.as_str()))
+        .collect();
+    assert_eq!(lines.len(), 8, "{lines:?}");
+    assert_eq!(lines[0].0, log::Level::Info);
+    assert!(
+        lines[0].1.ends_with(
+            "::add schema: params = (a: u32, b: u32), ret = u32, options = (\"info\", schema)"
+        ),
+        "{}",
+        lines[0].1
+    );
+    assert!(lines[1].1.ends_with("::add(a = 1, b = 2) => 3"));
+    assert!(lines[2].1.ends_with("::add(a = 3, b = 4) => 7"));
+    assert_eq!(lines[3].0, log::Level::Warn);
+    assert!(lines[3].1.ends_with("::lookup schema: params = (names: &'a [&str], index: usize), ret = Result<&'a str, String>, options = (ok = \"debug\", err = \"warn\", schema)"), "{}", lines[3].1);
+    assert_eq!(lines[6].0, log::Level::Debug);
+    assert!(
+        lines[6].1.ends_with(
+            "::bump schema: params = (&mut self, by: Option<u32>), ret = (), options = (schema)"
+        ),
+        "{}",
+        lines[6].1
+    );
+}
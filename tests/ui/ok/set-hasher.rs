@@ -0,0 +1,60 @@
+use std::hash::BuildHasherDefault;
+use std::hash::Hasher;
+
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+// Hashes everything to the same value, which is either the lowest or the highest.
+#[derive(Default)]
+struct ConstHasher<const HASH: u64>;
+
+impl<const HASH: u64> Hasher for ConstHasher<HASH> {
+    fn write(&mut self, _: &[u8]) {}
+
+    fn finish(&self) -> u64 {
+        HASH
+    }
+}
+
+#[logcall::logcall(ok = "info", err = "error", err_fingerprint)]
+fn fetch(id: u32) -> Result<u32, String> {
+    Err(format!("{id} not found"))
+}
+
+#[logcall::logcall("info", sample_by = "user", sample = 0.5)]
+fn visit(user: &str) {}
+
+fn take() -> Vec<String> {
+    std::mem::take(&mut *LINES.lock().unwrap())
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    fetch(0).unwrap_err();
+    fetch(1).unwrap_err();
+    let lines = take();
+    assert!(!lines[0].ends_with("fingerprint=00000000"));
+    assert_ne!(lines[0].split_once("fingerprint=").unwrap().1, lines[1].split_once("fingerprint=").unwrap().1);
+
+    logcall::set_hasher(BuildHasherDefault::<ConstHasher<0>>::default());
+    fetch(0).unwrap_err();
+    fetch(1).unwrap_err();
+    for user in ["alice", "bob", "carol"] {
+        visit(user);
+    }
+    let lines = take();
+    assert_eq!(lines.len(), 5, "{lines:?}");
+    assert!(lines[0].ends_with("fingerprint=00000000"));
+    assert!(lines[1].ends_with("fingerprint=00000000"));
+
+    logcall::set_hasher(BuildHasherDefault::<ConstHasher<{ u64::MAX }>>::default());
+    for user in ["alice", "bob", "carol"] {
+        visit(user);
+    }
+    assert_eq!(take(), Vec::<String>::new());
+}
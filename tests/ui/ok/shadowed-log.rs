@@ -0,0 +1,30 @@
+// The generated code must not depend on a `log` crate in scope at the call site.
+#[allow(dead_code)]
+mod log {}
+
+#[logcall::logcall]
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[logcall::logcall(ok = "info", err = "error", after_error)]
+fn parse(input: &str) -> Result<i32, std::num::ParseIntError> {
+    input.parse()
+}
+
+#[logcall::logcall("info", defer)]
+async fn fetch() -> u32 {
+    1
+}
+
+#[logcall::logcall("warn", dedup = "1s")]
+fn poll() -> bool {
+    true
+}
+
+fn main() {
+    add(1, 2);
+    parse("1").unwrap();
+    pollster::block_on(fetch());
+    poll();
+}
@@ -0,0 +1,69 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use logcall::LogcallSink;
+use logcall::Record;
+
+static LINES: AtomicUsize = AtomicUsize::new(0);
+
+struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, _: &log::Record) {
+        LINES.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn flush(&self) {}
+}
+
+struct Collect(Mutex<Vec<Record>>);
+
+impl LogcallSink for Collect {
+    fn record(&self, record: Record) {
+        self.0.lock().unwrap().push(record);
+    }
+}
+
+static SINK: Collect = Collect(Mutex::new(Vec::new()));
+
+#[logcall::logcall("info", sink = "SINK")]
+fn add(a: u32, b: u32) -> u32 {
+    std::thread::sleep(Duration::from_millis(1));
+    a + b
+}
+
+#[logcall::logcall(ok = "debug", err = "error", err_target = "audit", sink = "SINK", sink_mode = "both")]
+async fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    add(1, 2);
+    pollster::block_on(parse("a")).unwrap_err();
+
+    assert_eq!(LINES.load(Ordering::Relaxed), 1);
+    let records = SINK.0.lock().unwrap();
+    assert_eq!(records.len(), 2);
+
+    assert_eq!(records[0].level, log::Level::Info);
+    assert_eq!(records[0].target, records[0].module);
+    assert!(records[0].module.starts_with("trybuild"));
+    assert!(records[0].fn_name.ends_with("::add"));
+    assert_eq!(records[0].args, "a = 1, b = 2");
+    assert_eq!(records[0].ret, "3");
+    assert!(records[0].elapsed >= Duration::from_millis(1));
+
+    assert_eq!(records[1].level, log::Level::Error);
+    assert_eq!(records[1].target, "audit");
+    assert_eq!(records[1].args, "input = \"a\"");
+    assert_eq!(records[1].ret, "Err(ParseIntError { kind: InvalidDigit })");
+}
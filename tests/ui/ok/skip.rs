@@ -0,0 +1,78 @@
+use logcall::skip;
+
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+pub struct Vault;
+
+#[logcall::logcall("info")]
+impl Vault {
+    pub fn open(&self, id: u32) -> u32 {
+        id
+    }
+
+    #[skip]
+    pub fn unseal(&self, passphrase: &str) -> usize {
+        passphrase.len()
+    }
+}
+
+#[logcall::logcall("info")]
+mod handlers {
+    pub fn handle(n: u32) -> u32 {
+        n
+    }
+
+    #[logcall::skip]
+    pub fn secret(n: u32) -> u32 {
+        n
+    }
+
+    #[logcall::skip]
+    pub async fn token(n: u32) -> u32 {
+        n
+    }
+}
+
+#[logcall::wrap_dyn("info")]
+trait Plugin {
+    fn handle(&self, request: u32) -> u32;
+
+    #[logcall::skip]
+    fn credentials(&self) -> &'static str;
+}
+
+struct Echo;
+
+impl Plugin for Echo {
+    fn handle(&self, request: u32) -> u32 {
+        request
+    }
+
+    fn credentials(&self) -> &'static str {
+        "hunter2"
+    }
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    Vault.open(1);
+    Vault.unseal("hunter2");
+    handlers::handle(2);
+    handlers::secret(3);
+    pollster::block_on(handlers::token(4));
+    let plugin: Box<dyn Plugin> = Box::new(LoggedPlugin::new(Echo));
+    plugin.handle(5);
+    plugin.credentials();
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 3, "{lines:?}");
+    assert!(lines[0].ends_with("::open(self, id = 1) => 1"), "{lines:?}");
+    assert!(lines[1].ends_with("::handle(n = 2) => 2"), "{lines:?}");
+    assert!(lines[2].ends_with("::handle(self, request = 5) => 5"), "{lines:?}");
+}
@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use logger::Logger;
+use logger::RECORDS;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("debug", slow = "20ms", slow_level = "warn")]
+fn handle(ms: u64) {
+    std::thread::sleep(Duration::from_millis(ms));
+}
+
+#[logcall::logcall(ok = "info", err = "error", slow = "20ms")]
+async fn fetch(ms: u64) -> Result<u64, u64> {
+    std::thread::sleep(Duration::from_millis(ms));
+    if ms > 50 {
+        Err(ms)
+    } else {
+        Ok(ms)
+    }
+}
+
+// Only the slow calls are logged.
+#[logcall::logcall(slow = "20ms", slow_level = "info")]
+fn query(ms: u64) {
+    std::thread::sleep(Duration::from_millis(ms));
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    handle(0);
+    handle(30);
+    pollster::block_on(fetch(0)).unwrap();
+    pollster::block_on(fetch(30)).unwrap();
+    pollster::block_on(fetch(60)).unwrap_err();
+    query(0);
+    query(30);
+
+    let records = RECORDS.lock().unwrap();
+    let levels = records.iter().map(|(level, _)| *level).collect::<Vec<_>>();
+    assert_eq!(
+        levels,
+        [
+            log::Level::Debug,
+            log::Level::Warn,
+            log::Level::Info,
+            log::Level::Warn,
+            log::Level::Error,
+            log::Level::Info,
+        ],
+        "{records:?}"
+    );
+    assert!(records[1].1.contains("::handle(ms = 30) => ()"), "{records:?}");
+    assert!(records[5].1.contains("::query(ms = 30) => ()"), "{records:?}");
+}
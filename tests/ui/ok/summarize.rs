@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use logger::Logger;
+use logger::RECORDS;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("debug", summarize = "200ms")]
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[logcall::logcall(ok = "debug", err = "warn", summarize = "200ms")]
+async fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+#[logcall::logcall("info", summarize = "200ms")]
+fn idle() {}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    for i in 0..100 {
+        add(i, i);
+    }
+    pollster::block_on(parse("1")).unwrap();
+    pollster::block_on(parse("a")).unwrap_err();
+    pollster::block_on(parse("b")).unwrap_err();
+    assert!(RECORDS.lock().unwrap().is_empty());
+
+    std::thread::sleep(Duration::from_millis(300));
+    let records = std::mem::take(&mut *RECORDS.lock().unwrap());
+    assert_eq!(records.len(), 2, "{records:?}");
+    let (level, add) = records.iter().find(|(_, message)| message.contains("::add ")).unwrap();
+    assert_eq!(*level, log::Level::Debug);
+    assert!(add.contains(" summary over 200ms: calls=100 errors=0 p50="), "{add}");
+    assert!(add.contains(" p95=") && add.contains(" p99="), "{add}");
+    let (level, parse) = records.iter().find(|(_, message)| message.contains("::parse ")).unwrap();
+    assert_eq!(*level, log::Level::Warn);
+    assert!(parse.contains(" summary over 200ms: calls=3 errors=2 p50="), "{parse}");
+
+    // The windows without calls are not summarized.
+    idle();
+    std::thread::sleep(Duration::from_millis(500));
+    let records = RECORDS.lock().unwrap();
+    assert_eq!(records.len(), 1, "{records:?}");
+    assert!(records[0].1.contains("idle summary over 200ms: calls=1 errors=0"), "{records:?}");
+}
@@ -0,0 +1,96 @@
+use std::sync::Mutex;
+
+static LINES: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
+
+struct Logger;
+
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.target() != "app::muted"
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            LINES
+                .lock()
+                .unwrap()
+                .push((record.target().to_string(), record.args().to_string()));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[logcall::logcall(
+    ok = "info",
+    err = "error",
+    ok_target = "app::ops",
+    err_target = "app::errors"
+)]
+fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+#[logcall::logcall(err = "warn", err_target = "app::errors")]
+async fn fetch(found: bool) -> Result<u32, String> {
+    if found {
+        Ok(1)
+    } else {
+        Err("not found".to_string())
+    }
+}
+
+#[logcall::logcall("info", target = "payments::gateway")]
+fn charge(amount: u32) -> u32 {
+    amount
+}
+
+#[logcall::logcall(ok = "info", err = "error", target = "app", err_target = "app::errors")]
+fn check(ok: bool) -> Result<(), ()> {
+    if ok {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+mod nested {
+    #[logcall::logcall("info")]
+    pub fn inner() {}
+}
+
+#[logcall::logcall(ok = "info", ok_target = "app::muted")]
+fn muted() -> Result<(), ()> {
+    Ok(())
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    parse("1").unwrap();
+    parse("a").unwrap_err();
+    pollster::block_on(fetch(true)).unwrap();
+    pollster::block_on(fetch(false)).unwrap_err();
+    muted().unwrap();
+    charge(5);
+    check(true).unwrap();
+    check(false).unwrap_err();
+    nested::inner();
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 7, "{lines:?}");
+    assert_eq!(lines[0].0, "app::ops");
+    assert!(lines[0].1.ends_with("::parse(input = \"1\") => Ok(1)"));
+    assert_eq!(lines[1].0, "app::errors");
+    assert!(lines[1].1.contains("::parse(input = \"a\") => Err("));
+    assert_eq!(lines[2].0, "app::errors");
+    assert!(lines[2]
+        .1
+        .ends_with("::fetch(found = false) => Err(\"not found\")"));
+    assert_eq!(lines[3].0, "payments::gateway");
+    assert!(lines[3].1.ends_with("::charge(amount = 5) => 5"));
+    assert_eq!(lines[4].0, "app");
+    assert_eq!(lines[5].0, "app::errors");
+    assert!(lines[6].0.ends_with("::nested"), "{}", lines[6].0);
+}
@@ -0,0 +1,22 @@
+use logger::Logger;
+use logger::RECORDS;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+// Outside of `cfg(test)`, the configured levels apply.
+#[logcall::logcall(ok = "info", err = "error", test_level = "trace")]
+fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    parse("1").unwrap();
+    parse("x").unwrap_err();
+
+    let levels: Vec<_> = RECORDS.lock().unwrap().iter().map(|r| r.0).collect();
+    assert_eq!(levels, [log::Level::Info, log::Level::Error]);
+}
@@ -0,0 +1,66 @@
+use logger::Logger;
+use logger::RECORDS;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+// Neither `Debug`.
+struct Scene(u32);
+struct Frame(u32);
+struct RenderError;
+
+#[logcall::timeit("info")]
+fn render(scene: &Scene) -> Frame {
+    Frame(scene.0)
+}
+
+#[logcall::timeit(ok = "debug", err = "error")]
+async fn upload(frame: Frame) -> Result<u32, RenderError> {
+    if frame.0 > 0 {
+        Ok(frame.0)
+    } else {
+        Err(RenderError)
+    }
+}
+
+#[logcall::logcall("warn", only = "timing")]
+fn present(frame: Frame) -> Frame {
+    frame
+}
+
+#[logcall::logcall("info")]
+impl Scene {
+    #[logcall::timeit]
+    fn frame(&self) -> Frame {
+        Frame(self.0)
+    }
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let frame = render(&Scene(1));
+    pollster::block_on(upload(frame)).ok().unwrap();
+    pollster::block_on(upload(Frame(0))).err().unwrap();
+    present(Scene(2).frame());
+
+    let records = RECORDS.lock().unwrap();
+    let levels = records.iter().map(|(level, _)| *level).collect::<Vec<_>>();
+    assert_eq!(
+        levels,
+        [
+            log::Level::Info,
+            log::Level::Debug,
+            log::Level::Error,
+            log::Level::Debug,
+            log::Level::Warn,
+        ]
+    );
+    let names = ["render", "upload", "upload", "Scene::frame", "present"];
+    for ((_, message), name) in records.iter().zip(names) {
+        let (fn_name, elapsed) = message.split_once(" elapsed=").unwrap();
+        assert!(fn_name.ends_with(&format!("::{name}")), "{message}");
+        assert!(elapsed.ends_with('s'), "{message}");
+    }
+}
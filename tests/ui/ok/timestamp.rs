@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info", timestamp)]
+fn wait(ms: u64) {
+    std::thread::sleep(Duration::from_millis(ms));
+}
+
+#[logcall::logcall(ok = "info", timestamp)]
+async fn parse(input: &str) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+fn t_ns(line: &str) -> u128 {
+    line.rsplit_once(" t_ns=").unwrap().1.parse().unwrap()
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    wait(0);
+    wait(5);
+    pollster::block_on(parse("1")).unwrap();
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].contains("::wait(ms = 0) => () t_ns="), "{lines:?}");
+    assert!(lines[2].contains("::parse(input = \"1\") => Ok(1) t_ns="), "{lines:?}");
+    // Counted from the start of the process, not from the first record.
+    assert!(t_ns(&lines[0]) > 0, "{lines:?}");
+    assert!(t_ns(&lines[1]) - t_ns(&lines[0]) >= 5_000_000, "{lines:?}");
+    assert!(t_ns(&lines[2]) >= t_ns(&lines[1]));
+}
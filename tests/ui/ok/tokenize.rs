@@ -0,0 +1,31 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info", tokenize(email))]
+fn login(email: &str, attempt: u32) -> bool {
+    attempt == 1 && !email.is_empty()
+}
+
+#[logcall::logcall(ok = "info", tokenize(email, phone))]
+async fn register(email: String, phone: String) -> Result<(), String> {
+    let _ = (email, phone);
+    Ok(())
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    login("alice@example.com", 1);
+    logcall::set_tokenizer(|value| format!("tok_{}", value.len()));
+    login("alice@example.com", 2);
+    pollster::block_on(register("bob@example.com".to_string(), "555".to_string())).ok();
+
+    let lines = LINES.lock().unwrap();
+    assert!(lines[0].ends_with("::login(email = <untokenized>, attempt = 1) => true"));
+    assert!(lines[1].ends_with("::login(email = tok_19, attempt = 2) => false"));
+    assert!(lines[2].ends_with("::register(email = tok_17, phone = tok_5) => Ok(())"));
+}
@@ -0,0 +1,61 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info")]
+fn f(a: u32) -> u32 {
+    a
+}
+
+#[logcall::logcall("info")]
+async fn g(a: u32) -> u32 {
+    f(a)
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    logcall::Context::new()
+        .with_request_id("abc")
+        .with_traceparent("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+        .scope_sync(|| {
+            assert_eq!(
+                logcall::trace_id().as_deref(),
+                Some("4bf92f3577b34da6a3ce929d0e0e4736")
+            );
+            f(1)
+        });
+    pollster::block_on(
+        logcall::Context::new()
+            .with_trace_id("0af7651916cd43dd8448eb211c80319c")
+            .scope(g(2)),
+    );
+    // Invalid headers are ignored.
+    logcall::Context::new()
+        .with_traceparent("00-00000000000000000000000000000000-00f067aa0ba902b7-01")
+        .scope_sync(|| f(3));
+    logcall::Context::new()
+        .with_traceparent("00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01")
+        .scope_sync(|| f(4));
+
+    logcall::set_trace_id_hook(|| Some("hooked".to_string()));
+    f(5);
+    logcall::Context::new()
+        .with_trace_id("scoped")
+        .scope_sync(|| f(6));
+    assert_eq!(logcall::trace_id().as_deref(), Some("hooked"));
+
+    let lines = LINES.lock().unwrap();
+    assert!(lines[0].ends_with(
+        "::f(a = 1) => 1 request_id=abc trace_id=4bf92f3577b34da6a3ce929d0e0e4736 span_id=00f067aa0ba902b7"
+    ));
+    assert!(lines[1].ends_with("::f(a = 2) => 2 trace_id=0af7651916cd43dd8448eb211c80319c"));
+    assert!(lines[2].ends_with("::g(a = 2) => 2 trace_id=0af7651916cd43dd8448eb211c80319c"));
+    assert!(lines[3].ends_with("::f(a = 3) => 3"));
+    assert!(lines[4].ends_with("::f(a = 4) => 4"));
+    assert!(lines[5].ends_with("::f(a = 5) => 5 trace_id=hooked"));
+    assert!(lines[6].ends_with("::f(a = 6) => 6 trace_id=scoped"));
+}
@@ -0,0 +1,77 @@
+use std::num::ParseIntError;
+use std::panic::Location;
+
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall("info")]
+#[track_caller]
+fn caller_line() -> u32 {
+    Location::caller().line()
+}
+
+#[logcall::logcall(ok = "info", err = "error")]
+#[track_caller]
+fn parse(input: &str) -> Result<u32, ParseIntError> {
+    let value = input.parse::<u32>()?;
+    if value == 0 {
+        return Ok(Location::caller().line());
+    }
+    // The exits of closures and nested functions are left alone.
+    let double = |value: u32| -> Option<u32> {
+        let value = value.checked_mul(2)?;
+        Some(value)
+    };
+    fn add(a: u32, b: u32) -> u32 {
+        return a + b;
+    }
+    Ok(add(double(value).unwrap(), 1))
+}
+
+#[logcall::logcall("info")]
+#[track_caller]
+fn first(values: &[u32]) -> Option<u32> {
+    let first = values.first()?;
+    Some(*first)
+}
+
+#[logcall::logcall("info")]
+#[track_caller]
+fn unwrap_line(value: Option<u32>) -> u32 {
+    value.unwrap()
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    assert_eq!(caller_line(), line!());
+    assert_eq!(parse("0").unwrap(), line!());
+    assert_eq!(parse("2").unwrap(), 5);
+    parse("a").unwrap_err();
+    assert_eq!(first(&[]), None);
+    assert_eq!(first(&[3]), Some(3));
+
+    // The panic of the body is reported at the call site.
+    std::panic::set_hook(Box::new(|info| {
+        LINES
+            .lock()
+            .unwrap()
+            .push(format!("panicked at line {}", info.location().unwrap().line()));
+    }));
+    let line = line!() + 1;
+    std::panic::catch_unwind(|| unwrap_line(None)).unwrap_err();
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 7, "{lines:?}");
+    assert!(lines[0].contains("::caller_line() => "));
+    assert!(lines[1].contains("::parse(input = \"0\") => Ok("));
+    assert!(lines[2].ends_with("::parse(input = \"2\") => Ok(5)"));
+    assert!(lines[3].ends_with("::parse(input = \"a\") => Err(ParseIntError { kind: InvalidDigit })"));
+    assert!(lines[4].ends_with("::first(values = []) => None"));
+    assert!(lines[5].ends_with("::first(values = [3]) => Some(3)"));
+    assert_eq!(lines[6], format!("panicked at line {line}"));
+}
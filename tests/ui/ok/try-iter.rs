@@ -0,0 +1,46 @@
+use logger::Logger;
+use logger::RECORDS;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+#[logcall::logcall(ok = "debug", err = "error", try_iter)]
+fn parse_all<'a>(
+    inputs: &'a [&str],
+) -> impl Iterator<Item = Result<u32, std::num::ParseIntError>> + 'a {
+    inputs.iter().map(|input| input.parse())
+}
+
+#[logcall::logcall("info", try_iter)]
+async fn numbers(n: u32) -> impl Iterator<Item = Result<u32, String>> + Send {
+    (0..n).map(Ok)
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let results: Vec<_> = parse_all(&["1", "2", "x", "y", "3"]).collect();
+    assert_eq!(results.len(), 5);
+    assert_eq!(parse_all(&["1", "2"]).count(), 2);
+    parse_all(&["1", "2", "3"]).next().unwrap().unwrap();
+    assert_eq!(pollster::block_on(numbers(3)).count(), 3);
+
+    let lines = RECORDS.lock().unwrap();
+    assert_eq!(lines.len(), 4, "{lines:?}");
+    assert_eq!(lines[0].0, log::Level::Error);
+    assert!(
+        lines[0].1.ends_with("::parse_all(inputs = [\"1\", \"2\", \"x\", \"y\", \"3\"]) => Err(ParseIntError { kind: InvalidDigit }) after 2 Ok items"),
+        "{}",
+        lines[0].1
+    );
+    assert_eq!(lines[1].0, log::Level::Debug);
+    assert!(lines[1]
+        .1
+        .ends_with("::parse_all(inputs = [\"1\", \"2\"]) => 2 Ok items"));
+    assert!(lines[2]
+        .1
+        .ends_with("::parse_all(inputs = [\"1\", \"2\", \"3\"]) => 1 Ok items, dropped"));
+    assert_eq!(lines[3].0, log::Level::Info);
+    assert!(lines[3].1.ends_with("::numbers(n = 3) => 3 Ok items"));
+}
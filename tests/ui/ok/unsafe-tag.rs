@@ -0,0 +1,46 @@
+use logger::Logger;
+use logger::LINES;
+
+#[path = "../common/logger.rs"]
+mod logger;
+
+/// # Safety
+///
+/// `ptr` must be valid for reads.
+#[logcall::logcall("info", unsafe_tag)]
+unsafe fn read(ptr: *const u32) -> u32 {
+    *ptr
+}
+
+struct Buffer(Vec<u32>);
+
+#[logcall::logcall("info", unsafe_tag)]
+impl Buffer {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// # Safety
+    ///
+    /// `index` must be in bounds.
+    unsafe fn get_unchecked(&self, index: usize) -> u32 {
+        *self.0.get_unchecked(index)
+    }
+}
+
+fn main() {
+    log::set_logger(&Logger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let value = 7;
+    unsafe { read(&value) };
+    let buffer = Buffer(vec![1, 2]);
+    buffer.len();
+    unsafe { buffer.get_unchecked(1) };
+
+    let lines = LINES.lock().unwrap();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].ends_with(" => 7 unsafe=true"), "{lines:?}");
+    assert!(lines[1].ends_with("::len(self) => 2"), "{lines:?}");
+    assert!(lines[2].ends_with("::get_unchecked(self, index = 1) => 2 unsafe=true"), "{lines:?}");
+}
@@ -0,0 +1,164 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+
+#[async_trait::async_trait]
+trait Repository {
+    async fn get<K>(&self, key: K) -> Option<u32>
+    where
+        K: Debug + Hash + Send + 'static;
+
+    async fn into_all(self) -> Vec<u32>
+    where
+        Self: Sized;
+
+    fn count(&self) -> usize
+    where
+        Self: Sized;
+}
+
+struct Memory(Vec<u32>);
+
+#[async_trait::async_trait]
+impl Repository for Memory {
+    #[logcall::logcall("info")]
+    async fn get<K>(&self, key: K) -> Option<u32>
+    where
+        K: Debug + Hash + Send + 'static,
+    {
+        let _ = key;
+        self.0.first().copied()
+    }
+
+    #[logcall::logcall("info")]
+    async fn into_all(self) -> Vec<u32>
+    where
+        Self: Sized,
+    {
+        self.0
+    }
+
+    #[logcall::logcall("info")]
+    fn count(&self) -> usize
+    where
+        Self: Sized,
+    {
+        self.0.len()
+    }
+}
+
+trait Native {
+    async fn load<T>(&self, value: T) -> T
+    where
+        T: Debug + Clone + Send,
+        Self: Sync;
+
+    fn size(self) -> usize
+    where
+        Self: Sized;
+}
+
+impl Native for Memory {
+    #[logcall::logcall("info")]
+    async fn load<T>(&self, value: T) -> T
+    where
+        T: Debug + Clone + Send,
+        Self: Sync,
+    {
+        value.clone()
+    }
+
+    #[logcall::logcall("info")]
+    fn size(self) -> usize
+    where
+        Self: Sized,
+    {
+        self.0.len()
+    }
+}
+
+#[logcall::logcall(ok = "info", err = "error")]
+fn parse<'a, T, E>(input: &'a str) -> Result<T, E>
+where
+    T: std::str::FromStr<Err = E> + Debug + 'a,
+    E: Debug,
+    for<'b> &'b T: Debug,
+{
+    input.parse()
+}
+
+#[logcall::logcall("info")]
+async fn spawnable<F, T>(f: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Debug + Send + 'static,
+{
+    f()
+}
+
+#[async_trait::async_trait]
+trait Job {
+    #[logcall::logcall("info", exec_context)]
+    async fn run(&self) -> u32
+    where
+        Self: Sync,
+    {
+        1
+    }
+}
+
+impl Job for Memory {}
+
+#[logcall::wrap_dyn("info")]
+trait Cache {
+    fn lookup<K>(&self, key: K) -> Option<u32>
+    where
+        K: Debug + Hash,
+        Self: Sized;
+
+    async fn refresh<K>(&mut self, key: K) -> bool
+    where
+        K: Debug + Send;
+}
+
+struct Wrapper<T>(T);
+
+#[logcall::logcall("info")]
+impl<T> Wrapper<T>
+where
+    T: Debug + Clone,
+{
+    fn get(&self) -> T
+    where
+        T: Default,
+    {
+        self.0.clone()
+    }
+
+    async fn map<U, F>(self, f: F) -> U
+    where
+        F: FnOnce(T) -> U + Send,
+        U: Debug,
+        T: Send,
+    {
+        f(self.0)
+    }
+}
+
+fn assert_send<T: Send>(_: T) {}
+
+fn main() {
+    let memory = Memory(vec![1, 2]);
+    assert_send(memory.get("key"));
+    assert_eq!(pollster::block_on(memory.get("key")), Some(1));
+    assert_eq!(memory.count(), 2);
+    assert_eq!(pollster::block_on(memory.load(3)), 3);
+    assert_send(spawnable(|| 4));
+    assert_eq!(pollster::block_on(spawnable(|| 4)), 4);
+    assert_eq!(parse::<u32, _>("5"), Ok(5));
+    assert_eq!(pollster::block_on(Memory(vec![6]).into_all()), vec![6]);
+    assert_eq!(Memory(vec![7]).size(), 1);
+    assert_send(memory.run());
+    assert_eq!(Wrapper(8).get(), 8);
+    assert_eq!(pollster::block_on(Wrapper(9).map(|x| x + 1)), 10);
+    assert_eq!(pollster::block_on(memory.run()), 1);
+}
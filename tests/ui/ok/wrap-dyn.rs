@@ -0,0 +1,79 @@
+#[logcall::wrap_dyn("info")]
+pub trait Plugin {
+    const NAME: &'static str;
+    type Output: std::fmt::Debug;
+
+    fn handle(&self, request: u32) -> Self::Output;
+
+    fn reset(&mut self) {}
+
+    fn split(&self, (a, b): (u32, u32)) -> u32 {
+        a + b
+    }
+}
+
+#[logcall::wrap_dyn(ok = "info", err = "error")]
+trait Storage<'a, K: std::fmt::Debug> {
+    fn get(&self, key: &'a K) -> Result<u32, String>;
+
+    async fn put(&mut self, key: K, value: u32) -> Result<(), String>;
+
+    fn close(self) -> Result<(), String>
+    where
+        Self: Sized;
+}
+
+#[logcall::wrap_dyn]
+trait Handler {
+    fn call(&self, request: u32) -> u32;
+}
+
+struct Echo;
+
+impl Plugin for Echo {
+    const NAME: &'static str = "echo";
+    type Output = u32;
+
+    fn handle(&self, request: u32) -> u32 {
+        request
+    }
+}
+
+impl Handler for Echo {
+    fn call(&self, request: u32) -> u32 {
+        request
+    }
+}
+
+struct Memory(Vec<u32>);
+
+impl<'a> Storage<'a, usize> for Memory {
+    fn get(&self, key: &'a usize) -> Result<u32, String> {
+        self.0.get(*key).copied().ok_or_else(|| "not found".to_string())
+    }
+
+    async fn put(&mut self, key: usize, value: u32) -> Result<(), String> {
+        self.0.insert(key, value);
+        Ok(())
+    }
+
+    fn close(self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+fn main() {
+    let mut plugin = LoggedPlugin::new(Echo);
+    plugin.handle(1);
+    plugin.reset();
+    plugin.split((1, 2));
+    assert_eq!(<LoggedPlugin<Echo> as Plugin>::NAME, "echo");
+
+    let handler: Box<dyn Handler> = Box::new(LoggedHandler::new(Echo));
+    handler.call(1);
+
+    let mut storage = LoggedStorage::new(Memory(vec![]));
+    pollster::block_on(storage.put(0, 1)).ok();
+    storage.get(&0).ok();
+    storage.close().ok();
+}